@@ -138,9 +138,9 @@ crate::cipher::passphrase::{}::decrypt(&$self.key, metadata, $encrypted_data),",
         let latest = self.versions.last().unwrap();
 
         format!(
-"pub fn encrypt_latest(key: &str, data: &[u8]) -> crate::error::Result<(Vec<u8>, VersionedMetadata)> {{
+"pub fn encrypt_latest(key: &str, params: &KdfParams, data: &[u8]) -> crate::error::Result<(Vec<u8>, VersionedMetadata)> {{
     use {}::encrypt;
-    let (encrypted, metadata) = encrypt(key, data)?;
+    let (encrypted, metadata) = encrypt(key, params, data)?;
     Ok((encrypted, metadata.into()))
 }}",
             latest.module_name
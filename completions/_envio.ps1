@@ -21,6 +21,8 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
 
     $completions = @(switch ($command) {
         'envio' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
@@ -28,16 +30,42 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
             [CompletionResult]::new('new', 'new', [CompletionResultType]::ParameterValue, 'Create a new profile')
             [CompletionResult]::new('delete', 'delete', [CompletionResultType]::ParameterValue, 'Delete a profile')
             [CompletionResult]::new('remove', 'remove', [CompletionResultType]::ParameterValue, 'Delete a profile')
+            [CompletionResult]::new('trash', 'trash', [CompletionResultType]::ParameterValue, 'Manage deleted profiles')
+            [CompletionResult]::new('rename', 'rename', [CompletionResultType]::ParameterValue, 'Rename a profile')
+            [CompletionResult]::new('mv', 'mv', [CompletionResultType]::ParameterValue, 'Rename a profile')
+            [CompletionResult]::new('describe', 'describe', [CompletionResultType]::ParameterValue, 'Change the description of a profile')
+            [CompletionResult]::new('copy', 'copy', [CompletionResultType]::ParameterValue, 'Copy a profile, optionally encrypting the copy with a different cipher')
+            [CompletionResult]::new('cp', 'cp', [CompletionResultType]::ParameterValue, 'Copy a profile, optionally encrypting the copy with a different cipher')
             [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List all profiles')
             [CompletionResult]::new('ls', 'ls', [CompletionResultType]::ParameterValue, 'List all profiles')
             [CompletionResult]::new('show', 'show', [CompletionResultType]::ParameterValue, 'Show environment variables in a profile')
             [CompletionResult]::new('set', 'set', [CompletionResultType]::ParameterValue, 'Set or update environment variables in a profile')
             [CompletionResult]::new('unset', 'unset', [CompletionResultType]::ParameterValue, 'Remove environment variables from a profile')
+            [CompletionResult]::new('edit', 'edit', [CompletionResultType]::ParameterValue, 'Edit the environment variables of a profile in your editor')
+            [CompletionResult]::new('merge', 'merge', [CompletionResultType]::ParameterValue, 'Merge the environment variables of a dotenv file or another profile into a profile')
+            [CompletionResult]::new('apply', 'apply', [CompletionResultType]::ParameterValue, 'Apply a batch of changes to a profile at once')
             [CompletionResult]::new('load', 'load', [CompletionResultType]::ParameterValue, 'Load environment variables from a profile for use in terminal sessions')
             [CompletionResult]::new('unload', 'unload', [CompletionResultType]::ParameterValue, 'Unload previously loaded environment variables from terminal sessions')
             [CompletionResult]::new('run', 'run', [CompletionResultType]::ParameterValue, 'Run a command using environment variables from a profile')
             [CompletionResult]::new('import', 'import', [CompletionResultType]::ParameterValue, 'Import a profile from a file or url')
             [CompletionResult]::new('export', 'export', [CompletionResultType]::ParameterValue, 'Export the environment variables of a profile to a file')
+            [CompletionResult]::new('render', 'render', [CompletionResultType]::ParameterValue, 'Fill in the {{ KEY }} placeholders of a config file template')
+            [CompletionResult]::new('example', 'example', [CompletionResultType]::ParameterValue, 'Write the keys of a profile without their values, as a .env.example or Markdown')
+            [CompletionResult]::new('verify', 'verify', [CompletionResultType]::ParameterValue, 'Compare the keys of a profile with the ones documented in a .env.example')
+            [CompletionResult]::new('expiring', 'expiring', [CompletionResultType]::ParameterValue, 'List environment variables that have expired or expire soon across all profiles')
+            [CompletionResult]::new('on-expired', 'on-expired', [CompletionResultType]::ParameterValue, 'Set what happens to expired environment variables when a profile is used')
+            [CompletionResult]::new('key-policy', 'key-policy', [CompletionResultType]::ParameterValue, 'Set which names new environment variables of a profile may have')
+            [CompletionResult]::new('kdf', 'kdf', [CompletionResultType]::ParameterValue, 'Show or change how the key of a passphrase profile is derived')
+            [CompletionResult]::new('lint', 'lint', [CompletionResultType]::ParameterValue, 'Check the names of the environment variables in a profile')
+            [CompletionResult]::new('schema', 'schema', [CompletionResultType]::ParameterValue, 'Show or set the schema the environment variables of a profile are checked against')
+            [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'Check the environment variables of a profile against its schema')
+            [CompletionResult]::new('tag', 'tag', [CompletionResultType]::ParameterValue, 'Show or change the tags of a profile or one of its environment variables')
+            [CompletionResult]::new('rotation-policy', 'rotation-policy', [CompletionResultType]::ParameterValue, 'Show or set how often an environment variable needs a new value')
+            [CompletionResult]::new('rotate', 'rotate', [CompletionResultType]::ParameterValue, 'Give environment variables a new value')
+            [CompletionResult]::new('generate', 'generate', [CompletionResultType]::ParameterValue, 'Set an environment variable to a randomly generated value')
+            [CompletionResult]::new('layout', 'layout', [CompletionResultType]::ParameterValue, 'Change how the environment variables of a profile are stored')
+            [CompletionResult]::new('store', 'store', [CompletionResultType]::ParameterValue, 'Manage named profile stores')
+            [CompletionResult]::new('sync', 'sync', [CompletionResultType]::ParameterValue, 'Pull and push the profiles of a git backed store')
             [CompletionResult]::new('tui', 'tui', [CompletionResultType]::ParameterValue, 'Launch the interactive TUI application')
             [CompletionResult]::new('completion', 'completion', [CompletionResultType]::ParameterValue, 'Show shell completion for the provided shell')
             [CompletionResult]::new('version', 'version', [CompletionResultType]::ParameterValue, 'Print version information')
@@ -48,14 +76,29 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
             [CompletionResult]::new('--description', '--description', [CompletionResultType]::ParameterName, 'optional note or description of the profile')
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'file path to load environment variables from')
             [CompletionResult]::new('--from-file', '--from-file', [CompletionResultType]::ParameterName, 'file path to load environment variables from')
-            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'environment variables to add (format: KEY=VALUE or only provide KEY and the value will be prompted for)')
-            [CompletionResult]::new('--envs', '--envs', [CompletionResultType]::ParameterName, 'environment variables to add (format: KEY=VALUE or only provide KEY and the value will be prompted for)')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'environment variables to add (format: KEY=VALUE, KEY=@FILE to read the value from a file or only provide KEY and the value will be prompted for)')
+            [CompletionResult]::new('--envs', '--envs', [CompletionResultType]::ParameterName, 'environment variables to add (format: KEY=VALUE, KEY=@FILE to read the value from a file or only provide KEY and the value will be prompted for)')
             [CompletionResult]::new('-k', '-k', [CompletionResultType]::ParameterName, 'encryption cipher to use')
             [CompletionResult]::new('--cipher-kind', '--cipher-kind', [CompletionResultType]::ParameterName, 'encryption cipher to use')
+            [CompletionResult]::new('--comment', '--comment', [CompletionResultType]::ParameterName, 'set the comment of an environment variable without being prompted')
+            [CompletionResult]::new('--expire', '--expire', [CompletionResultType]::ParameterName, 'set the expiration date of an environment variable without being prompted (YYYY-MM-DD or a duration such as 90d)')
+            [CompletionResult]::new('--expires-in', '--expires-in', [CompletionResultType]::ParameterName, 'let the provided environment variables expire after a duration (e.g. 90d, 12w, 6m, 1y)')
+            [CompletionResult]::new('--layout', '--layout', [CompletionResultType]::ParameterName, 'how the environment variables are stored (blob, per-entry)')
+            [CompletionResult]::new('--kdf-memory', '--kdf-memory', [CompletionResultType]::ParameterName, 'memory used to derive the key from the passphrase, in KiB (passphrase only)')
+            [CompletionResult]::new('--kdf-iterations', '--kdf-iterations', [CompletionResultType]::ParameterName, 'iterations used to derive the key from the passphrase (passphrase only)')
+            [CompletionResult]::new('--kdf-parallelism', '--kdf-parallelism', [CompletionResultType]::ParameterName, 'lanes used to derive the key from the passphrase (passphrase only)')
+            [CompletionResult]::new('--on-expired', '--on-expired', [CompletionResultType]::ParameterName, 'what to do with expired environment variables when the profile is used (warn, skip, fail)')
+            [CompletionResult]::new('--key-policy', '--key-policy', [CompletionResultType]::ParameterName, 'which names environment variables may have (posix, lenient)')
+            [CompletionResult]::new('--tag', '--tag', [CompletionResultType]::ParameterName, 'add a tag to the profile, the value can be left out (e.g. owner=platform)')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--stdin', '--stdin', [CompletionResultType]::ParameterName, 'read the value of the environment variable provided without one from stdin')
+            [CompletionResult]::new('--hidden', '--hidden', [CompletionResultType]::ParameterName, 'hide values while typing them into prompts')
             [CompletionResult]::new('-c', '-c', [CompletionResultType]::ParameterName, 'add comments to the provided environment variables')
             [CompletionResult]::new('--comments', '--comments', [CompletionResultType]::ParameterName, 'add comments to the provided environment variables')
             [CompletionResult]::new('-x', '-x', [CompletionResultType]::ParameterName, 'add expiration dates to the provided environment variables')
             [CompletionResult]::new('--expires', '--expires', [CompletionResultType]::ParameterName, 'add expiration dates to the provided environment variables')
+            [CompletionResult]::new('--clear-comments', '--clear-comments', [CompletionResultType]::ParameterName, 'keep comments in cleartext (per-entry layout only)')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
@@ -66,57 +109,207 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
             [CompletionResult]::new('--description', '--description', [CompletionResultType]::ParameterName, 'optional note or description of the profile')
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'file path to load environment variables from')
             [CompletionResult]::new('--from-file', '--from-file', [CompletionResultType]::ParameterName, 'file path to load environment variables from')
-            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'environment variables to add (format: KEY=VALUE or only provide KEY and the value will be prompted for)')
-            [CompletionResult]::new('--envs', '--envs', [CompletionResultType]::ParameterName, 'environment variables to add (format: KEY=VALUE or only provide KEY and the value will be prompted for)')
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'environment variables to add (format: KEY=VALUE, KEY=@FILE to read the value from a file or only provide KEY and the value will be prompted for)')
+            [CompletionResult]::new('--envs', '--envs', [CompletionResultType]::ParameterName, 'environment variables to add (format: KEY=VALUE, KEY=@FILE to read the value from a file or only provide KEY and the value will be prompted for)')
             [CompletionResult]::new('-k', '-k', [CompletionResultType]::ParameterName, 'encryption cipher to use')
             [CompletionResult]::new('--cipher-kind', '--cipher-kind', [CompletionResultType]::ParameterName, 'encryption cipher to use')
+            [CompletionResult]::new('--comment', '--comment', [CompletionResultType]::ParameterName, 'set the comment of an environment variable without being prompted')
+            [CompletionResult]::new('--expire', '--expire', [CompletionResultType]::ParameterName, 'set the expiration date of an environment variable without being prompted (YYYY-MM-DD or a duration such as 90d)')
+            [CompletionResult]::new('--expires-in', '--expires-in', [CompletionResultType]::ParameterName, 'let the provided environment variables expire after a duration (e.g. 90d, 12w, 6m, 1y)')
+            [CompletionResult]::new('--layout', '--layout', [CompletionResultType]::ParameterName, 'how the environment variables are stored (blob, per-entry)')
+            [CompletionResult]::new('--kdf-memory', '--kdf-memory', [CompletionResultType]::ParameterName, 'memory used to derive the key from the passphrase, in KiB (passphrase only)')
+            [CompletionResult]::new('--kdf-iterations', '--kdf-iterations', [CompletionResultType]::ParameterName, 'iterations used to derive the key from the passphrase (passphrase only)')
+            [CompletionResult]::new('--kdf-parallelism', '--kdf-parallelism', [CompletionResultType]::ParameterName, 'lanes used to derive the key from the passphrase (passphrase only)')
+            [CompletionResult]::new('--on-expired', '--on-expired', [CompletionResultType]::ParameterName, 'what to do with expired environment variables when the profile is used (warn, skip, fail)')
+            [CompletionResult]::new('--key-policy', '--key-policy', [CompletionResultType]::ParameterName, 'which names environment variables may have (posix, lenient)')
+            [CompletionResult]::new('--tag', '--tag', [CompletionResultType]::ParameterName, 'add a tag to the profile, the value can be left out (e.g. owner=platform)')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--stdin', '--stdin', [CompletionResultType]::ParameterName, 'read the value of the environment variable provided without one from stdin')
+            [CompletionResult]::new('--hidden', '--hidden', [CompletionResultType]::ParameterName, 'hide values while typing them into prompts')
             [CompletionResult]::new('-c', '-c', [CompletionResultType]::ParameterName, 'add comments to the provided environment variables')
             [CompletionResult]::new('--comments', '--comments', [CompletionResultType]::ParameterName, 'add comments to the provided environment variables')
             [CompletionResult]::new('-x', '-x', [CompletionResultType]::ParameterName, 'add expiration dates to the provided environment variables')
             [CompletionResult]::new('--expires', '--expires', [CompletionResultType]::ParameterName, 'add expiration dates to the provided environment variables')
+            [CompletionResult]::new('--clear-comments', '--clear-comments', [CompletionResultType]::ParameterName, 'keep comments in cleartext (per-entry layout only)')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'envio;delete' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'do not ask for confirmation')
+            [CompletionResult]::new('--force', '--force', [CompletionResultType]::ParameterName, 'do not ask for confirmation')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'envio;remove' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'do not ask for confirmation')
+            [CompletionResult]::new('--force', '--force', [CompletionResultType]::ParameterName, 'do not ask for confirmation')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;trash' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List deleted profiles')
+            [CompletionResult]::new('ls', 'ls', [CompletionResultType]::ParameterValue, 'List deleted profiles')
+            [CompletionResult]::new('restore', 'restore', [CompletionResultType]::ParameterValue, 'Restore a deleted profile to where it was deleted from')
+            [CompletionResult]::new('empty', 'empty', [CompletionResultType]::ParameterValue, 'Permanently delete everything in the trash')
+            [CompletionResult]::new('retention', 'retention', [CompletionResultType]::ParameterValue, 'Show or change how many days deleted profiles are kept for (0 keeps them forever)')
+            break
+        }
+        'envio;trash;list' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;trash;ls' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;trash;restore' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;trash;empty' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'do not ask for confirmation')
+            [CompletionResult]::new('--force', '--force', [CompletionResultType]::ParameterName, 'do not ask for confirmation')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;trash;retention' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;rename' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;mv' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;describe' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--remove', '--remove', [CompletionResultType]::ParameterName, 'remove the description')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;copy' {
+            [CompletionResult]::new('-k', '-k', [CompletionResultType]::ParameterName, 'encryption cipher to use for the copy, the same as the original by default')
+            [CompletionResult]::new('--cipher-kind', '--cipher-kind', [CompletionResultType]::ParameterName, 'encryption cipher to use for the copy, the same as the original by default')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;cp' {
+            [CompletionResult]::new('-k', '-k', [CompletionResultType]::ParameterName, 'encryption cipher to use for the copy, the same as the original by default')
+            [CompletionResult]::new('--cipher-kind', '--cipher-kind', [CompletionResultType]::ParameterName, 'encryption cipher to use for the copy, the same as the original by default')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'envio;list' {
+            [CompletionResult]::new('--tag', '--tag', [CompletionResultType]::ParameterName, 'only list profiles with a tag, or with a tag of a value')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
             [CompletionResult]::new('--no-pretty-print', '--no-pretty-print', [CompletionResultType]::ParameterName, 'disable pretty printing')
+            [CompletionResult]::new('-t', '-t', [CompletionResultType]::ParameterName, 'show profiles as a tree of their namespaces (default when listing a namespace)')
+            [CompletionResult]::new('--tree', '--tree', [CompletionResultType]::ParameterName, 'show profiles as a tree of their namespaces (default when listing a namespace)')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'envio;ls' {
+            [CompletionResult]::new('--tag', '--tag', [CompletionResultType]::ParameterName, 'only list profiles with a tag, or with a tag of a value')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
             [CompletionResult]::new('--no-pretty-print', '--no-pretty-print', [CompletionResultType]::ParameterName, 'disable pretty printing')
+            [CompletionResult]::new('-t', '-t', [CompletionResultType]::ParameterName, 'show profiles as a tree of their namespaces (default when listing a namespace)')
+            [CompletionResult]::new('--tree', '--tree', [CompletionResultType]::ParameterName, 'show profiles as a tree of their namespaces (default when listing a namespace)')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'envio;show' {
+            [CompletionResult]::new('--tag', '--tag', [CompletionResultType]::ParameterName, 'only show environment variables with a tag, or with a tag of a value')
+            [CompletionResult]::new('--on-expired', '--on-expired', [CompletionResultType]::ParameterName, 'handle expired environment variables instead of only warning about them (warn, skip, fail)')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
             [CompletionResult]::new('-c', '-c', [CompletionResultType]::ParameterName, 'display comments')
             [CompletionResult]::new('--show-comments', '--show-comments', [CompletionResultType]::ParameterName, 'display comments')
             [CompletionResult]::new('-x', '-x', [CompletionResultType]::ParameterName, 'display expiration dates')
             [CompletionResult]::new('--show-expiration', '--show-expiration', [CompletionResultType]::ParameterName, 'display expiration dates')
+            [CompletionResult]::new('-g', '-g', [CompletionResultType]::ParameterName, 'display tags')
+            [CompletionResult]::new('--show-tags', '--show-tags', [CompletionResultType]::ParameterName, 'display tags')
+            [CompletionResult]::new('--show-history-columns', '--show-history-columns', [CompletionResultType]::ParameterName, 'display when each value was created and last changed, and by whom')
             [CompletionResult]::new('--no-pretty-print', '--no-pretty-print', [CompletionResultType]::ParameterName, 'disable pretty printing')
+            [CompletionResult]::new('--quote', '--quote', [CompletionResultType]::ParameterName, 'print values spanning several lines or starting with `"` in double quotes with escape sequences')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'envio;set' {
+            [CompletionResult]::new('--comment', '--comment', [CompletionResultType]::ParameterName, 'set the comment of an environment variable without being prompted')
+            [CompletionResult]::new('--expire', '--expire', [CompletionResultType]::ParameterName, 'set the expiration date of an environment variable without being prompted (YYYY-MM-DD or a duration such as 90d)')
+            [CompletionResult]::new('--expires-in', '--expires-in', [CompletionResultType]::ParameterName, 'let the provided environment variables expire after a duration (e.g. 90d, 12w, 6m, 1y)')
+            [CompletionResult]::new('--unset-comment', '--unset-comment', [CompletionResultType]::ParameterName, 'remove the comments of environment variables')
+            [CompletionResult]::new('--unset-expiration', '--unset-expiration', [CompletionResultType]::ParameterName, 'remove the expiration dates of environment variables')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--stdin', '--stdin', [CompletionResultType]::ParameterName, 'read the value of the environment variable provided without one from stdin')
+            [CompletionResult]::new('--hidden', '--hidden', [CompletionResultType]::ParameterName, 'hide values while typing them into prompts')
             [CompletionResult]::new('-c', '-c', [CompletionResultType]::ParameterName, 'add comments to the provided environment variables')
             [CompletionResult]::new('--comments', '--comments', [CompletionResultType]::ParameterName, 'add comments to the provided environment variables')
             [CompletionResult]::new('-x', '-x', [CompletionResultType]::ParameterName, 'add expiration dates to the provided environment variables')
@@ -127,24 +320,71 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
             break
         }
         'envio;unset' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;edit' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;merge' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'dotenv file or name of the profile to merge from')
+            [CompletionResult]::new('--from', '--from', [CompletionResultType]::ParameterName, 'dotenv file or name of the profile to merge from')
+            [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 'what to do with variables that exist in both with different values (keep, overwrite, prompt)')
+            [CompletionResult]::new('--strategy', '--strategy', [CompletionResultType]::ParameterName, 'what to do with variables that exist in both with different values (keep, overwrite, prompt)')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'only show what would be merged without saving anything')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;apply' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'read the changes from a file instead of stdin')
+            [CompletionResult]::new('--file', '--file', [CompletionResultType]::ParameterName, 'read the changes from a file instead of stdin')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'envio;load' {
+            [CompletionResult]::new('--on-expired', '--on-expired', [CompletionResultType]::ParameterName, 'override what to do with expired environment variables (warn, skip, fail)')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'envio;unload' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'envio;run' {
+            [CompletionResult]::new('-k', '-k', [CompletionResultType]::ParameterName, 'comma-separated list of keys to decrypt and pass to the command')
+            [CompletionResult]::new('--keys', '--keys', [CompletionResultType]::ParameterName, 'comma-separated list of keys to decrypt and pass to the command')
+            [CompletionResult]::new('--on-expired', '--on-expired', [CompletionResultType]::ParameterName, 'override what to do with expired environment variables (warn, skip, fail)')
+            [CompletionResult]::new('--schema', '--schema', [CompletionResultType]::ParameterName, 'check the environment variables against this schema file before running')
+            [CompletionResult]::new('--file-var', '--file-var', [CompletionResultType]::ParameterName, 'write the value of KEY to a private temporary file and set VAR to its path')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--no-check', '--no-check', [CompletionResultType]::ParameterName, 'run even if the environment variables do not match the schema')
+            [CompletionResult]::new('--memfd', '--memfd', [CompletionResultType]::ParameterName, 'keep the files of --file-var in memory and pass them as /dev/fd paths (linux only)')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
@@ -153,6 +393,8 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
         'envio;import' {
             [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'name for the imported profile')
             [CompletionResult]::new('--profile-name', '--profile-name', [CompletionResultType]::ParameterName, 'name for the imported profile')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
@@ -163,24 +405,261 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
             [CompletionResult]::new('--output-file-path', '--output-file-path', [CompletionResultType]::ParameterName, 'output file path (default: .env)')
             [CompletionResult]::new('-k', '-k', [CompletionResultType]::ParameterName, 'comma-separated list of keys to export (type ''select'' to choose interactively)')
             [CompletionResult]::new('--keys', '--keys', [CompletionResultType]::ParameterName, 'comma-separated list of keys to export (type ''select'' to choose interactively)')
+            [CompletionResult]::new('--on-expired', '--on-expired', [CompletionResultType]::ParameterName, 'override what to do with expired environment variables (warn, skip, fail)')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;render' {
+            [CompletionResult]::new('-o', '-o', [CompletionResultType]::ParameterName, 'file to write, only the current user can read it')
+            [CompletionResult]::new('--output-file-path', '--output-file-path', [CompletionResultType]::ParameterName, 'file to write, only the current user can read it')
+            [CompletionResult]::new('--on-expired', '--on-expired', [CompletionResultType]::ParameterName, 'override what to do with expired environment variables (warn, skip, fail)')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--strict', '--strict', [CompletionResultType]::ParameterName, 'fail instead of leaving placeholders empty when their key is not in the profile')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;example' {
+            [CompletionResult]::new('-o', '-o', [CompletionResultType]::ParameterName, 'output file path (default: print to stdout)')
+            [CompletionResult]::new('--output-file-path', '--output-file-path', [CompletionResultType]::ParameterName, 'output file path (default: print to stdout)')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--markdown', '--markdown', [CompletionResultType]::ParameterName, 'write a Markdown table instead of a .env.example')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;verify' {
+            [CompletionResult]::new('--against', '--against', [CompletionResultType]::ParameterName, 'a .env.example or a Markdown file written by `envio example --markdown`')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;expiring' {
+            [CompletionResult]::new('-w', '-w', [CompletionResultType]::ParameterName, 'how far ahead to look (e.g. 7d, 2w, 3m, 1y)')
+            [CompletionResult]::new('--within', '--within', [CompletionResultType]::ParameterName, 'how far ahead to look (e.g. 7d, 2w, 3m, 1y)')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--json', '--json', [CompletionResultType]::ParameterName, 'print the report as JSON')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'envio;on-expired' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;key-policy' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;kdf' {
+            [CompletionResult]::new('--memory', '--memory', [CompletionResultType]::ParameterName, 'memory used to derive the key from the passphrase, in KiB')
+            [CompletionResult]::new('--iterations', '--iterations', [CompletionResultType]::ParameterName, 'iterations used to derive the key from the passphrase')
+            [CompletionResult]::new('--parallelism', '--parallelism', [CompletionResultType]::ParameterName, 'lanes used to derive the key from the passphrase')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;lint' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--fix', '--fix', [CompletionResultType]::ParameterName, 'rename invalid environment variables')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;schema' {
+            [CompletionResult]::new('--set', '--set', [CompletionResultType]::ParameterName, 'store the schema in this json file with the profile')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--remove', '--remove', [CompletionResultType]::ParameterName, 'remove the schema stored with the profile')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;check' {
+            [CompletionResult]::new('--schema', '--schema', [CompletionResultType]::ParameterName, 'schema file to check against (default: envio.schema.json or the schema of the profile)')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;tag' {
+            [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'tag an environment variable instead of the profile')
+            [CompletionResult]::new('--env', '--env', [CompletionResultType]::ParameterName, 'tag an environment variable instead of the profile')
+            [CompletionResult]::new('-r', '-r', [CompletionResultType]::ParameterName, 'remove a tag')
+            [CompletionResult]::new('--remove', '--remove', [CompletionResultType]::ParameterName, 'remove a tag')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;rotation-policy' {
+            [CompletionResult]::new('--max-age', '--max-age', [CompletionResultType]::ParameterName, 'how long a value may be used before it is due for rotation (e.g. 90d, 12w, 6m, 1y), which replaces its expiration date')
+            [CompletionResult]::new('-g', '-g', [CompletionResultType]::ParameterName, 'generate new values randomly (alnum, alpha, digits, ascii, words, hex, base64 or uuid, optionally followed by :LENGTH)')
+            [CompletionResult]::new('--generator', '--generator', [CompletionResultType]::ParameterName, 'generate new values randomly (alnum, alpha, digits, ascii, words, hex, base64 or uuid, optionally followed by :LENGTH)')
+            [CompletionResult]::new('--command', '--command', [CompletionResultType]::ParameterName, 'shell command that prints new values, it receives the current one on stdin')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--remove', '--remove', [CompletionResultType]::ParameterName, 'remove the rotation policy')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;rotate' {
+            [CompletionResult]::new('-g', '-g', [CompletionResultType]::ParameterName, 'generate the new value randomly instead of how the rotation policy says (alnum, alpha, digits, ascii, words, hex, base64 or uuid, optionally followed by :LENGTH)')
+            [CompletionResult]::new('--generator', '--generator', [CompletionResultType]::ParameterName, 'generate the new value randomly instead of how the rotation policy says (alnum, alpha, digits, ascii, words, hex, base64 or uuid, optionally followed by :LENGTH)')
+            [CompletionResult]::new('--command', '--command', [CompletionResultType]::ParameterName, 'shell command that prints the new value, it receives the current one on stdin')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--due', '--due', [CompletionResultType]::ParameterName, 'rotate every environment variable that is older than the max age of its rotation policy')
+            [CompletionResult]::new('--list', '--list', [CompletionResultType]::ParameterName, 'only list the environment variables that are due for rotation')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;generate' {
+            [CompletionResult]::new('-l', '-l', [CompletionResultType]::ParameterName, 'number of characters, words with --words or bytes with --hex and --base64 (default: 32, 6 words)')
+            [CompletionResult]::new('--length', '--length', [CompletionResultType]::ParameterName, 'number of characters, words with --words or bytes with --hex and --base64 (default: 32, 6 words)')
+            [CompletionResult]::new('--charset', '--charset', [CompletionResultType]::ParameterName, 'characters to pick from (alnum, alpha, digits, ascii)')
+            [CompletionResult]::new('--chars', '--chars', [CompletionResultType]::ParameterName, 'custom characters to pick from')
+            [CompletionResult]::new('--separator', '--separator', [CompletionResultType]::ParameterName, 'what to put between the words of a passphrase (default: -)')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--words', '--words', [CompletionResultType]::ParameterName, 'generate a passphrase of words from a built-in wordlist')
+            [CompletionResult]::new('--hex', '--hex', [CompletionResultType]::ParameterName, 'generate random bytes encoded as hex')
+            [CompletionResult]::new('--base64', '--base64', [CompletionResultType]::ParameterName, 'generate random bytes encoded as base64')
+            [CompletionResult]::new('--uuid', '--uuid', [CompletionResultType]::ParameterName, 'generate a random UUID')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'replace the value if the variable exists')
+            [CompletionResult]::new('--force', '--force', [CompletionResultType]::ParameterName, 'replace the value if the variable exists')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;layout' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--clear-comments', '--clear-comments', [CompletionResultType]::ParameterName, 'keep comments in cleartext (per-entry layout only)')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;store' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'Register a directory as a named profile store')
+            [CompletionResult]::new('remove', 'remove', [CompletionResultType]::ParameterValue, 'Unregister a named profile store (its profiles are left untouched)')
+            [CompletionResult]::new('rm', 'rm', [CompletionResultType]::ParameterValue, 'Unregister a named profile store (its profiles are left untouched)')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List all profile stores')
+            [CompletionResult]::new('ls', 'ls', [CompletionResultType]::ParameterValue, 'List all profile stores')
+            break
+        }
+        'envio;store;add' {
+            [CompletionResult]::new('--remote', '--remote', [CompletionResultType]::ParameterName, 'git remote to clone from or sync with (implies --git)')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--git', '--git', [CompletionResultType]::ParameterName, 'keep the store in a git repository and commit every change')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;store;remove' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;store;rm' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;store;list' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;store;ls' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
+            [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;sync' {
+            [CompletionResult]::new('--keep', '--keep', [CompletionResultType]::ParameterName, 'resolve conflicting profiles by keeping one side (local, remote)')
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'envio;tui' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'envio;completion' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'envio;version' {
+            [CompletionResult]::new('--profile-dir', '--profile-dir', [CompletionResultType]::ParameterName, 'directory to read and write profiles from (overrides --store)')
+            [CompletionResult]::new('--store', '--store', [CompletionResultType]::ParameterName, 'name of the profile store to use')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'show verbose version information')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'show verbose version information')
             [CompletionResult]::new('--diagnostic', '--diagnostic', [CompletionResultType]::ParameterName, 'Show diagnostic information for bug reports')
@@ -1,6 +1,6 @@
 # Print an optspec for argparse to handle cmd's options that are independent of any subcommand.
 function __fish_envio_global_optspecs
-	string join \n diagnostic h/help
+	string join \n diagnostic profile-dir= store= h/help
 end
 
 function __fish_envio_needs_command
@@ -24,79 +24,417 @@ function __fish_envio_using_subcommand
 	contains -- $cmd[1] $argv
 end
 
+complete -c envio -n "__fish_envio_needs_command" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_needs_command" -l store -d 'name of the profile store to use' -r
 complete -c envio -n "__fish_envio_needs_command" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_needs_command" -s h -l help -d 'Print help'
 complete -c envio -n "__fish_envio_needs_command" -f -a "create" -d 'Create a new profile'
 complete -c envio -n "__fish_envio_needs_command" -f -a "new" -d 'Create a new profile'
 complete -c envio -n "__fish_envio_needs_command" -f -a "delete" -d 'Delete a profile'
 complete -c envio -n "__fish_envio_needs_command" -f -a "remove" -d 'Delete a profile'
+complete -c envio -n "__fish_envio_needs_command" -f -a "trash" -d 'Manage deleted profiles'
+complete -c envio -n "__fish_envio_needs_command" -f -a "rename" -d 'Rename a profile'
+complete -c envio -n "__fish_envio_needs_command" -f -a "mv" -d 'Rename a profile'
+complete -c envio -n "__fish_envio_needs_command" -f -a "describe" -d 'Change the description of a profile'
+complete -c envio -n "__fish_envio_needs_command" -f -a "copy" -d 'Copy a profile, optionally encrypting the copy with a different cipher'
+complete -c envio -n "__fish_envio_needs_command" -f -a "cp" -d 'Copy a profile, optionally encrypting the copy with a different cipher'
 complete -c envio -n "__fish_envio_needs_command" -f -a "list" -d 'List all profiles'
 complete -c envio -n "__fish_envio_needs_command" -f -a "ls" -d 'List all profiles'
 complete -c envio -n "__fish_envio_needs_command" -f -a "show" -d 'Show environment variables in a profile'
 complete -c envio -n "__fish_envio_needs_command" -f -a "set" -d 'Set or update environment variables in a profile'
 complete -c envio -n "__fish_envio_needs_command" -f -a "unset" -d 'Remove environment variables from a profile'
+complete -c envio -n "__fish_envio_needs_command" -f -a "edit" -d 'Edit the environment variables of a profile in your editor'
+complete -c envio -n "__fish_envio_needs_command" -f -a "merge" -d 'Merge the environment variables of a dotenv file or another profile into a profile'
+complete -c envio -n "__fish_envio_needs_command" -f -a "apply" -d 'Apply a batch of changes to a profile at once'
 complete -c envio -n "__fish_envio_needs_command" -f -a "load" -d 'Load environment variables from a profile for use in terminal sessions'
 complete -c envio -n "__fish_envio_needs_command" -f -a "unload" -d 'Unload previously loaded environment variables from terminal sessions'
 complete -c envio -n "__fish_envio_needs_command" -f -a "run" -d 'Run a command using environment variables from a profile'
 complete -c envio -n "__fish_envio_needs_command" -f -a "import" -d 'Import a profile from a file or url'
 complete -c envio -n "__fish_envio_needs_command" -f -a "export" -d 'Export the environment variables of a profile to a file'
+complete -c envio -n "__fish_envio_needs_command" -f -a "render" -d 'Fill in the {{ KEY }} placeholders of a config file template'
+complete -c envio -n "__fish_envio_needs_command" -f -a "example" -d 'Write the keys of a profile without their values, as a .env.example or Markdown'
+complete -c envio -n "__fish_envio_needs_command" -f -a "verify" -d 'Compare the keys of a profile with the ones documented in a .env.example'
+complete -c envio -n "__fish_envio_needs_command" -f -a "expiring" -d 'List environment variables that have expired or expire soon across all profiles'
+complete -c envio -n "__fish_envio_needs_command" -f -a "on-expired" -d 'Set what happens to expired environment variables when a profile is used'
+complete -c envio -n "__fish_envio_needs_command" -f -a "key-policy" -d 'Set which names new environment variables of a profile may have'
+complete -c envio -n "__fish_envio_needs_command" -f -a "kdf" -d 'Show or change how the key of a passphrase profile is derived'
+complete -c envio -n "__fish_envio_needs_command" -f -a "lint" -d 'Check the names of the environment variables in a profile'
+complete -c envio -n "__fish_envio_needs_command" -f -a "schema" -d 'Show or set the schema the environment variables of a profile are checked against'
+complete -c envio -n "__fish_envio_needs_command" -f -a "check" -d 'Check the environment variables of a profile against its schema'
+complete -c envio -n "__fish_envio_needs_command" -f -a "tag" -d 'Show or change the tags of a profile or one of its environment variables'
+complete -c envio -n "__fish_envio_needs_command" -f -a "rotation-policy" -d 'Show or set how often an environment variable needs a new value'
+complete -c envio -n "__fish_envio_needs_command" -f -a "rotate" -d 'Give environment variables a new value'
+complete -c envio -n "__fish_envio_needs_command" -f -a "generate" -d 'Set an environment variable to a randomly generated value'
+complete -c envio -n "__fish_envio_needs_command" -f -a "layout" -d 'Change how the environment variables of a profile are stored'
+complete -c envio -n "__fish_envio_needs_command" -f -a "store" -d 'Manage named profile stores'
+complete -c envio -n "__fish_envio_needs_command" -f -a "sync" -d 'Pull and push the profiles of a git backed store'
 complete -c envio -n "__fish_envio_needs_command" -f -a "tui" -d 'Launch the interactive TUI application'
 complete -c envio -n "__fish_envio_needs_command" -f -a "completion" -d 'Show shell completion for the provided shell'
 complete -c envio -n "__fish_envio_needs_command" -f -a "version" -d 'Print version information'
 complete -c envio -n "__fish_envio_using_subcommand create" -s d -l description -d 'optional note or description of the profile' -r
 complete -c envio -n "__fish_envio_using_subcommand create" -s f -l from-file -d 'file path to load environment variables from' -r
-complete -c envio -n "__fish_envio_using_subcommand create" -s e -l envs -d 'environment variables to add (format: KEY=VALUE or only provide KEY and the value will be prompted for)' -r
+complete -c envio -n "__fish_envio_using_subcommand create" -s e -l envs -d 'environment variables to add (format: KEY=VALUE, KEY=@FILE to read the value from a file or only provide KEY and the value will be prompted for)' -r
 complete -c envio -n "__fish_envio_using_subcommand create" -s k -l cipher-kind -d 'encryption cipher to use' -r
+complete -c envio -n "__fish_envio_using_subcommand create" -l comment -d 'set the comment of an environment variable without being prompted' -r
+complete -c envio -n "__fish_envio_using_subcommand create" -l expire -d 'set the expiration date of an environment variable without being prompted (YYYY-MM-DD or a duration such as 90d)' -r
+complete -c envio -n "__fish_envio_using_subcommand create" -l expires-in -d 'let the provided environment variables expire after a duration (e.g. 90d, 12w, 6m, 1y)' -r
+complete -c envio -n "__fish_envio_using_subcommand create" -l layout -d 'how the environment variables are stored (blob, per-entry)' -r -f -a "blob\t''
+per-entry\t''"
+complete -c envio -n "__fish_envio_using_subcommand create" -l kdf-memory -d 'memory used to derive the key from the passphrase, in KiB (passphrase only)' -r
+complete -c envio -n "__fish_envio_using_subcommand create" -l kdf-iterations -d 'iterations used to derive the key from the passphrase (passphrase only)' -r
+complete -c envio -n "__fish_envio_using_subcommand create" -l kdf-parallelism -d 'lanes used to derive the key from the passphrase (passphrase only)' -r
+complete -c envio -n "__fish_envio_using_subcommand create" -l on-expired -d 'what to do with expired environment variables when the profile is used (warn, skip, fail)' -r -f -a "warn\t''
+skip\t''
+fail\t''"
+complete -c envio -n "__fish_envio_using_subcommand create" -l key-policy -d 'which names environment variables may have (posix, lenient)' -r -f -a "posix\t''
+lenient\t''"
+complete -c envio -n "__fish_envio_using_subcommand create" -l tag -d 'add a tag to the profile, the value can be left out (e.g. owner=platform)' -r
+complete -c envio -n "__fish_envio_using_subcommand create" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand create" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand create" -l stdin -d 'read the value of the environment variable provided without one from stdin'
+complete -c envio -n "__fish_envio_using_subcommand create" -l hidden -d 'hide values while typing them into prompts'
 complete -c envio -n "__fish_envio_using_subcommand create" -s c -l comments -d 'add comments to the provided environment variables'
 complete -c envio -n "__fish_envio_using_subcommand create" -s x -l expires -d 'add expiration dates to the provided environment variables'
+complete -c envio -n "__fish_envio_using_subcommand create" -l clear-comments -d 'keep comments in cleartext (per-entry layout only)'
 complete -c envio -n "__fish_envio_using_subcommand create" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand create" -s h -l help -d 'Print help'
 complete -c envio -n "__fish_envio_using_subcommand new" -s d -l description -d 'optional note or description of the profile' -r
 complete -c envio -n "__fish_envio_using_subcommand new" -s f -l from-file -d 'file path to load environment variables from' -r
-complete -c envio -n "__fish_envio_using_subcommand new" -s e -l envs -d 'environment variables to add (format: KEY=VALUE or only provide KEY and the value will be prompted for)' -r
+complete -c envio -n "__fish_envio_using_subcommand new" -s e -l envs -d 'environment variables to add (format: KEY=VALUE, KEY=@FILE to read the value from a file or only provide KEY and the value will be prompted for)' -r
 complete -c envio -n "__fish_envio_using_subcommand new" -s k -l cipher-kind -d 'encryption cipher to use' -r
+complete -c envio -n "__fish_envio_using_subcommand new" -l comment -d 'set the comment of an environment variable without being prompted' -r
+complete -c envio -n "__fish_envio_using_subcommand new" -l expire -d 'set the expiration date of an environment variable without being prompted (YYYY-MM-DD or a duration such as 90d)' -r
+complete -c envio -n "__fish_envio_using_subcommand new" -l expires-in -d 'let the provided environment variables expire after a duration (e.g. 90d, 12w, 6m, 1y)' -r
+complete -c envio -n "__fish_envio_using_subcommand new" -l layout -d 'how the environment variables are stored (blob, per-entry)' -r -f -a "blob\t''
+per-entry\t''"
+complete -c envio -n "__fish_envio_using_subcommand new" -l kdf-memory -d 'memory used to derive the key from the passphrase, in KiB (passphrase only)' -r
+complete -c envio -n "__fish_envio_using_subcommand new" -l kdf-iterations -d 'iterations used to derive the key from the passphrase (passphrase only)' -r
+complete -c envio -n "__fish_envio_using_subcommand new" -l kdf-parallelism -d 'lanes used to derive the key from the passphrase (passphrase only)' -r
+complete -c envio -n "__fish_envio_using_subcommand new" -l on-expired -d 'what to do with expired environment variables when the profile is used (warn, skip, fail)' -r -f -a "warn\t''
+skip\t''
+fail\t''"
+complete -c envio -n "__fish_envio_using_subcommand new" -l key-policy -d 'which names environment variables may have (posix, lenient)' -r -f -a "posix\t''
+lenient\t''"
+complete -c envio -n "__fish_envio_using_subcommand new" -l tag -d 'add a tag to the profile, the value can be left out (e.g. owner=platform)' -r
+complete -c envio -n "__fish_envio_using_subcommand new" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand new" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand new" -l stdin -d 'read the value of the environment variable provided without one from stdin'
+complete -c envio -n "__fish_envio_using_subcommand new" -l hidden -d 'hide values while typing them into prompts'
 complete -c envio -n "__fish_envio_using_subcommand new" -s c -l comments -d 'add comments to the provided environment variables'
 complete -c envio -n "__fish_envio_using_subcommand new" -s x -l expires -d 'add expiration dates to the provided environment variables'
+complete -c envio -n "__fish_envio_using_subcommand new" -l clear-comments -d 'keep comments in cleartext (per-entry layout only)'
 complete -c envio -n "__fish_envio_using_subcommand new" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand new" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand delete" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand delete" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand delete" -s f -l force -d 'do not ask for confirmation'
 complete -c envio -n "__fish_envio_using_subcommand delete" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand delete" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand remove" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand remove" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand remove" -s f -l force -d 'do not ask for confirmation'
 complete -c envio -n "__fish_envio_using_subcommand remove" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand remove" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand trash; and not __fish_seen_subcommand_from list ls restore empty retention" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand trash; and not __fish_seen_subcommand_from list ls restore empty retention" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand trash; and not __fish_seen_subcommand_from list ls restore empty retention" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand trash; and not __fish_seen_subcommand_from list ls restore empty retention" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand trash; and not __fish_seen_subcommand_from list ls restore empty retention" -f -a "list" -d 'List deleted profiles'
+complete -c envio -n "__fish_envio_using_subcommand trash; and not __fish_seen_subcommand_from list ls restore empty retention" -f -a "ls" -d 'List deleted profiles'
+complete -c envio -n "__fish_envio_using_subcommand trash; and not __fish_seen_subcommand_from list ls restore empty retention" -f -a "restore" -d 'Restore a deleted profile to where it was deleted from'
+complete -c envio -n "__fish_envio_using_subcommand trash; and not __fish_seen_subcommand_from list ls restore empty retention" -f -a "empty" -d 'Permanently delete everything in the trash'
+complete -c envio -n "__fish_envio_using_subcommand trash; and not __fish_seen_subcommand_from list ls restore empty retention" -f -a "retention" -d 'Show or change how many days deleted profiles are kept for (0 keeps them forever)'
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from list" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from list" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from list" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from list" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from ls" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from ls" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from ls" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from ls" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from restore" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from restore" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from restore" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from restore" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from empty" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from empty" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from empty" -s f -l force -d 'do not ask for confirmation'
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from empty" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from empty" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from retention" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from retention" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from retention" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand trash; and __fish_seen_subcommand_from retention" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand rename" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand rename" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand rename" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand rename" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand mv" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand mv" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand mv" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand mv" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand describe" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand describe" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand describe" -l remove -d 'remove the description'
+complete -c envio -n "__fish_envio_using_subcommand describe" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand describe" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand copy" -s k -l cipher-kind -d 'encryption cipher to use for the copy, the same as the original by default' -r
+complete -c envio -n "__fish_envio_using_subcommand copy" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand copy" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand copy" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand copy" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand cp" -s k -l cipher-kind -d 'encryption cipher to use for the copy, the same as the original by default' -r
+complete -c envio -n "__fish_envio_using_subcommand cp" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand cp" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand cp" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand cp" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand list" -l tag -d 'only list profiles with a tag, or with a tag of a value' -r
+complete -c envio -n "__fish_envio_using_subcommand list" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand list" -l store -d 'name of the profile store to use' -r
 complete -c envio -n "__fish_envio_using_subcommand list" -l no-pretty-print -d 'disable pretty printing'
+complete -c envio -n "__fish_envio_using_subcommand list" -s t -l tree -d 'show profiles as a tree of their namespaces (default when listing a namespace)'
 complete -c envio -n "__fish_envio_using_subcommand list" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand list" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand ls" -l tag -d 'only list profiles with a tag, or with a tag of a value' -r
+complete -c envio -n "__fish_envio_using_subcommand ls" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand ls" -l store -d 'name of the profile store to use' -r
 complete -c envio -n "__fish_envio_using_subcommand ls" -l no-pretty-print -d 'disable pretty printing'
+complete -c envio -n "__fish_envio_using_subcommand ls" -s t -l tree -d 'show profiles as a tree of their namespaces (default when listing a namespace)'
 complete -c envio -n "__fish_envio_using_subcommand ls" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand ls" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand show" -l tag -d 'only show environment variables with a tag, or with a tag of a value' -r
+complete -c envio -n "__fish_envio_using_subcommand show" -l on-expired -d 'handle expired environment variables instead of only warning about them (warn, skip, fail)' -r -f -a "warn\t''
+skip\t''
+fail\t''"
+complete -c envio -n "__fish_envio_using_subcommand show" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand show" -l store -d 'name of the profile store to use' -r
 complete -c envio -n "__fish_envio_using_subcommand show" -s c -l show-comments -d 'display comments'
 complete -c envio -n "__fish_envio_using_subcommand show" -s x -l show-expiration -d 'display expiration dates'
+complete -c envio -n "__fish_envio_using_subcommand show" -s g -l show-tags -d 'display tags'
+complete -c envio -n "__fish_envio_using_subcommand show" -l show-history-columns -d 'display when each value was created and last changed, and by whom'
 complete -c envio -n "__fish_envio_using_subcommand show" -l no-pretty-print -d 'disable pretty printing'
+complete -c envio -n "__fish_envio_using_subcommand show" -l quote -d 'print values spanning several lines or starting with `"` in double quotes with escape sequences'
 complete -c envio -n "__fish_envio_using_subcommand show" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand show" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand set" -l comment -d 'set the comment of an environment variable without being prompted' -r
+complete -c envio -n "__fish_envio_using_subcommand set" -l expire -d 'set the expiration date of an environment variable without being prompted (YYYY-MM-DD or a duration such as 90d)' -r
+complete -c envio -n "__fish_envio_using_subcommand set" -l expires-in -d 'let the provided environment variables expire after a duration (e.g. 90d, 12w, 6m, 1y)' -r
+complete -c envio -n "__fish_envio_using_subcommand set" -l unset-comment -d 'remove the comments of environment variables' -r
+complete -c envio -n "__fish_envio_using_subcommand set" -l unset-expiration -d 'remove the expiration dates of environment variables' -r
+complete -c envio -n "__fish_envio_using_subcommand set" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand set" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand set" -l stdin -d 'read the value of the environment variable provided without one from stdin'
+complete -c envio -n "__fish_envio_using_subcommand set" -l hidden -d 'hide values while typing them into prompts'
 complete -c envio -n "__fish_envio_using_subcommand set" -s c -l comments -d 'add comments to the provided environment variables'
 complete -c envio -n "__fish_envio_using_subcommand set" -s x -l expires -d 'add expiration dates to the provided environment variables'
 complete -c envio -n "__fish_envio_using_subcommand set" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand set" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand unset" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand unset" -l store -d 'name of the profile store to use' -r
 complete -c envio -n "__fish_envio_using_subcommand unset" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand unset" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand edit" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand edit" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand edit" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand edit" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand merge" -s f -l from -d 'dotenv file or name of the profile to merge from' -r
+complete -c envio -n "__fish_envio_using_subcommand merge" -s s -l strategy -d 'what to do with variables that exist in both with different values (keep, overwrite, prompt)' -r -f -a "keep\t''
+overwrite\t''
+prompt\t''"
+complete -c envio -n "__fish_envio_using_subcommand merge" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand merge" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand merge" -l dry-run -d 'only show what would be merged without saving anything'
+complete -c envio -n "__fish_envio_using_subcommand merge" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand merge" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand apply" -s f -l file -d 'read the changes from a file instead of stdin' -r
+complete -c envio -n "__fish_envio_using_subcommand apply" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand apply" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand apply" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand apply" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand load" -l on-expired -d 'override what to do with expired environment variables (warn, skip, fail)' -r -f -a "warn\t''
+skip\t''
+fail\t''"
+complete -c envio -n "__fish_envio_using_subcommand load" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand load" -l store -d 'name of the profile store to use' -r
 complete -c envio -n "__fish_envio_using_subcommand load" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand load" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand unload" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand unload" -l store -d 'name of the profile store to use' -r
 complete -c envio -n "__fish_envio_using_subcommand unload" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand unload" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand run" -s k -l keys -d 'comma-separated list of keys to decrypt and pass to the command' -r
+complete -c envio -n "__fish_envio_using_subcommand run" -l on-expired -d 'override what to do with expired environment variables (warn, skip, fail)' -r -f -a "warn\t''
+skip\t''
+fail\t''"
+complete -c envio -n "__fish_envio_using_subcommand run" -l schema -d 'check the environment variables against this schema file before running' -r
+complete -c envio -n "__fish_envio_using_subcommand run" -l file-var -d 'write the value of KEY to a private temporary file and set VAR to its path' -r
+complete -c envio -n "__fish_envio_using_subcommand run" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand run" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand run" -l no-check -d 'run even if the environment variables do not match the schema'
+complete -c envio -n "__fish_envio_using_subcommand run" -l memfd -d 'keep the files of --file-var in memory and pass them as /dev/fd paths (linux only)'
 complete -c envio -n "__fish_envio_using_subcommand run" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand run" -s h -l help -d 'Print help'
 complete -c envio -n "__fish_envio_using_subcommand import" -s n -l profile-name -d 'name for the imported profile' -r
+complete -c envio -n "__fish_envio_using_subcommand import" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand import" -l store -d 'name of the profile store to use' -r
 complete -c envio -n "__fish_envio_using_subcommand import" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand import" -s h -l help -d 'Print help'
 complete -c envio -n "__fish_envio_using_subcommand export" -s o -l output-file-path -d 'output file path (default: .env)' -r
 complete -c envio -n "__fish_envio_using_subcommand export" -s k -l keys -d 'comma-separated list of keys to export (type \'select\' to choose interactively)' -r
+complete -c envio -n "__fish_envio_using_subcommand export" -l on-expired -d 'override what to do with expired environment variables (warn, skip, fail)' -r -f -a "warn\t''
+skip\t''
+fail\t''"
+complete -c envio -n "__fish_envio_using_subcommand export" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand export" -l store -d 'name of the profile store to use' -r
 complete -c envio -n "__fish_envio_using_subcommand export" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand export" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand render" -s o -l output-file-path -d 'file to write, only the current user can read it' -r
+complete -c envio -n "__fish_envio_using_subcommand render" -l on-expired -d 'override what to do with expired environment variables (warn, skip, fail)' -r -f -a "warn\t''
+skip\t''
+fail\t''"
+complete -c envio -n "__fish_envio_using_subcommand render" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand render" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand render" -l strict -d 'fail instead of leaving placeholders empty when their key is not in the profile'
+complete -c envio -n "__fish_envio_using_subcommand render" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand render" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand example" -s o -l output-file-path -d 'output file path (default: print to stdout)' -r
+complete -c envio -n "__fish_envio_using_subcommand example" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand example" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand example" -l markdown -d 'write a Markdown table instead of a .env.example'
+complete -c envio -n "__fish_envio_using_subcommand example" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand example" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand verify" -l against -d 'a .env.example or a Markdown file written by `envio example --markdown`' -r
+complete -c envio -n "__fish_envio_using_subcommand verify" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand verify" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand verify" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand verify" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand expiring" -s w -l within -d 'how far ahead to look (e.g. 7d, 2w, 3m, 1y)' -r
+complete -c envio -n "__fish_envio_using_subcommand expiring" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand expiring" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand expiring" -l json -d 'print the report as JSON'
+complete -c envio -n "__fish_envio_using_subcommand expiring" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand expiring" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c envio -n "__fish_envio_using_subcommand on-expired" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand on-expired" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand on-expired" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand on-expired" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand key-policy" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand key-policy" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand key-policy" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand key-policy" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand kdf" -l memory -d 'memory used to derive the key from the passphrase, in KiB' -r
+complete -c envio -n "__fish_envio_using_subcommand kdf" -l iterations -d 'iterations used to derive the key from the passphrase' -r
+complete -c envio -n "__fish_envio_using_subcommand kdf" -l parallelism -d 'lanes used to derive the key from the passphrase' -r
+complete -c envio -n "__fish_envio_using_subcommand kdf" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand kdf" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand kdf" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand kdf" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand lint" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand lint" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand lint" -l fix -d 'rename invalid environment variables'
+complete -c envio -n "__fish_envio_using_subcommand lint" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand lint" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand schema" -l set -d 'store the schema in this json file with the profile' -r
+complete -c envio -n "__fish_envio_using_subcommand schema" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand schema" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand schema" -l remove -d 'remove the schema stored with the profile'
+complete -c envio -n "__fish_envio_using_subcommand schema" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand schema" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand check" -l schema -d 'schema file to check against (default: envio.schema.json or the schema of the profile)' -r
+complete -c envio -n "__fish_envio_using_subcommand check" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand check" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand check" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand check" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand tag" -s e -l env -d 'tag an environment variable instead of the profile' -r
+complete -c envio -n "__fish_envio_using_subcommand tag" -s r -l remove -d 'remove a tag' -r
+complete -c envio -n "__fish_envio_using_subcommand tag" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand tag" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand tag" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand tag" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand rotation-policy" -l max-age -d 'how long a value may be used before it is due for rotation (e.g. 90d, 12w, 6m, 1y), which replaces its expiration date' -r
+complete -c envio -n "__fish_envio_using_subcommand rotation-policy" -s g -l generator -d 'generate new values randomly (alnum, alpha, digits, ascii, words, hex, base64 or uuid, optionally followed by :LENGTH)' -r
+complete -c envio -n "__fish_envio_using_subcommand rotation-policy" -l command -d 'shell command that prints new values, it receives the current one on stdin' -r
+complete -c envio -n "__fish_envio_using_subcommand rotation-policy" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand rotation-policy" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand rotation-policy" -l remove -d 'remove the rotation policy'
+complete -c envio -n "__fish_envio_using_subcommand rotation-policy" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand rotation-policy" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand rotate" -s g -l generator -d 'generate the new value randomly instead of how the rotation policy says (alnum, alpha, digits, ascii, words, hex, base64 or uuid, optionally followed by :LENGTH)' -r
+complete -c envio -n "__fish_envio_using_subcommand rotate" -l command -d 'shell command that prints the new value, it receives the current one on stdin' -r
+complete -c envio -n "__fish_envio_using_subcommand rotate" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand rotate" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand rotate" -l due -d 'rotate every environment variable that is older than the max age of its rotation policy'
+complete -c envio -n "__fish_envio_using_subcommand rotate" -l list -d 'only list the environment variables that are due for rotation'
+complete -c envio -n "__fish_envio_using_subcommand rotate" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand rotate" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand generate" -s l -l length -d 'number of characters, words with --words or bytes with --hex and --base64 (default: 32, 6 words)' -r
+complete -c envio -n "__fish_envio_using_subcommand generate" -l charset -d 'characters to pick from (alnum, alpha, digits, ascii)' -r -f -a "alnum\t''
+alpha\t''
+digits\t''
+ascii\t''"
+complete -c envio -n "__fish_envio_using_subcommand generate" -l chars -d 'custom characters to pick from' -r
+complete -c envio -n "__fish_envio_using_subcommand generate" -l separator -d 'what to put between the words of a passphrase (default: -)' -r
+complete -c envio -n "__fish_envio_using_subcommand generate" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand generate" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand generate" -l words -d 'generate a passphrase of words from a built-in wordlist'
+complete -c envio -n "__fish_envio_using_subcommand generate" -l hex -d 'generate random bytes encoded as hex'
+complete -c envio -n "__fish_envio_using_subcommand generate" -l base64 -d 'generate random bytes encoded as base64'
+complete -c envio -n "__fish_envio_using_subcommand generate" -l uuid -d 'generate a random UUID'
+complete -c envio -n "__fish_envio_using_subcommand generate" -s f -l force -d 'replace the value if the variable exists'
+complete -c envio -n "__fish_envio_using_subcommand generate" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand generate" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand layout" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand layout" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand layout" -l clear-comments -d 'keep comments in cleartext (per-entry layout only)'
+complete -c envio -n "__fish_envio_using_subcommand layout" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand layout" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand store; and not __fish_seen_subcommand_from add remove rm list ls" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand store; and not __fish_seen_subcommand_from add remove rm list ls" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand store; and not __fish_seen_subcommand_from add remove rm list ls" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand store; and not __fish_seen_subcommand_from add remove rm list ls" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand store; and not __fish_seen_subcommand_from add remove rm list ls" -f -a "add" -d 'Register a directory as a named profile store'
+complete -c envio -n "__fish_envio_using_subcommand store; and not __fish_seen_subcommand_from add remove rm list ls" -f -a "remove" -d 'Unregister a named profile store (its profiles are left untouched)'
+complete -c envio -n "__fish_envio_using_subcommand store; and not __fish_seen_subcommand_from add remove rm list ls" -f -a "rm" -d 'Unregister a named profile store (its profiles are left untouched)'
+complete -c envio -n "__fish_envio_using_subcommand store; and not __fish_seen_subcommand_from add remove rm list ls" -f -a "list" -d 'List all profile stores'
+complete -c envio -n "__fish_envio_using_subcommand store; and not __fish_seen_subcommand_from add remove rm list ls" -f -a "ls" -d 'List all profile stores'
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from add" -l remote -d 'git remote to clone from or sync with (implies --git)' -r
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from add" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from add" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from add" -l git -d 'keep the store in a git repository and commit every change'
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from add" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from add" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from remove" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from remove" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from remove" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from remove" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from rm" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from rm" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from rm" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from rm" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from list" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from list" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from list" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from list" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from ls" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from ls" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from ls" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand store; and __fish_seen_subcommand_from ls" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand sync" -l keep -d 'resolve conflicting profiles by keeping one side (local, remote)' -r -f -a "local\t''
+remote\t''"
+complete -c envio -n "__fish_envio_using_subcommand sync" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand sync" -l store -d 'name of the profile store to use' -r
+complete -c envio -n "__fish_envio_using_subcommand sync" -l diagnostic -d 'Show diagnostic information for bug reports'
+complete -c envio -n "__fish_envio_using_subcommand sync" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand tui" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand tui" -l store -d 'name of the profile store to use' -r
 complete -c envio -n "__fish_envio_using_subcommand tui" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand tui" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand completion" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand completion" -l store -d 'name of the profile store to use' -r
 complete -c envio -n "__fish_envio_using_subcommand completion" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand completion" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_envio_using_subcommand version" -l profile-dir -d 'directory to read and write profiles from (overrides --store)' -r
+complete -c envio -n "__fish_envio_using_subcommand version" -l store -d 'name of the profile store to use' -r
 complete -c envio -n "__fish_envio_using_subcommand version" -s v -l verbose -d 'show verbose version information'
 complete -c envio -n "__fish_envio_using_subcommand version" -l diagnostic -d 'Show diagnostic information for bug reports'
 complete -c envio -n "__fish_envio_using_subcommand version" -s h -l help -d 'Print help'
@@ -0,0 +1,403 @@
+use chrono::NaiveDate;
+use envio::{Env, EnvMap};
+use serde::Deserialize;
+
+use crate::{
+    dotenv,
+    error::{AppError, AppResult},
+    utils,
+};
+
+/// a single change made by `envio apply`
+pub enum Operation {
+    Set {
+        key: String,
+        value: String,
+        comment: Option<String>,
+        expiration_date: Option<NaiveDate>,
+    },
+    Unset {
+        key: String,
+    },
+    /// sets or, with `None`, removes the comment of an existing environment variable
+    Comment {
+        key: String,
+        comment: Option<String>,
+    },
+    /// sets or, with `None`, removes the expiration date of an existing environment variable
+    Expire {
+        key: String,
+        expiration_date: Option<NaiveDate>,
+    },
+}
+
+impl Operation {
+    fn key(&self) -> &str {
+        match self {
+            Operation::Set { key, .. }
+            | Operation::Unset { key }
+            | Operation::Comment { key, .. }
+            | Operation::Expire { key, .. } => key,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase", deny_unknown_fields)]
+enum JsonOperation {
+    Set {
+        key: String,
+        value: String,
+        #[serde(default)]
+        comment: Option<String>,
+        #[serde(default)]
+        expires: Option<String>,
+    },
+    Unset {
+        key: String,
+    },
+    Comment {
+        key: String,
+        #[serde(default)]
+        comment: Option<String>,
+    },
+    Expire {
+        key: String,
+        #[serde(default)]
+        date: Option<String>,
+    },
+}
+
+/// an operation along with where it came from, so errors can point at it
+pub struct LocatedOperation {
+    location: String,
+    operation: Operation,
+}
+
+fn located_error(location: &str, msg: impl std::fmt::Display) -> AppError {
+    AppError::Msg(format!("{}: {}", location, msg))
+}
+
+fn parse_json(input: &str) -> AppResult<Vec<LocatedOperation>> {
+    let operations: Vec<JsonOperation> = serde_json::from_str(input)
+        .map_err(|e| AppError::Msg(format!("Invalid operations: {}", e)))?;
+
+    let mut located = Vec::with_capacity(operations.len());
+
+    for (index, operation) in operations.into_iter().enumerate() {
+        let location = format!("Operation {}", index + 1);
+        let parse_date = |date: Option<String>| {
+            date.map(|d| utils::parse_date(&d))
+                .transpose()
+                .map_err(|e| located_error(&location, e))
+        };
+
+        let operation = match operation {
+            JsonOperation::Set {
+                key,
+                value,
+                comment,
+                expires,
+            } => Operation::Set {
+                key,
+                value,
+                comment,
+                expiration_date: parse_date(expires)?,
+            },
+            JsonOperation::Unset { key } => Operation::Unset { key },
+            JsonOperation::Comment { key, comment } => Operation::Comment { key, comment },
+            JsonOperation::Expire { key, date } => Operation::Expire {
+                key,
+                expiration_date: parse_date(date)?,
+            },
+        };
+
+        located.push(LocatedOperation {
+            location,
+            operation,
+        });
+    }
+
+    Ok(located)
+}
+
+fn parse_script(input: &str) -> AppResult<Vec<LocatedOperation>> {
+    let mut located = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let location = format!("Line {}", line_number);
+        let trimmed = line.trim_start();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let (command, argument) = trimmed.split_once(' ').unwrap_or((trimmed, ""));
+        let argument = argument.trim_start();
+
+        // everything after `=` is kept as is, the key is everything before it
+        let (key, value) = match argument.split_once('=') {
+            Some((key, value)) => (key.trim_end().to_string(), Some(value)),
+            None => (argument.trim_end().to_string(), None),
+        };
+
+        let operation = match (command, value) {
+            ("set", Some(value)) => Operation::Set {
+                key,
+                value: dotenv::parse_value(value, line_number)?,
+                comment: None,
+                expiration_date: None,
+            },
+            ("unset", None) => Operation::Unset { key },
+            ("comment", value) => Operation::Comment {
+                key,
+                comment: value
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty()),
+            },
+            ("expire", value) => Operation::Expire {
+                key,
+                expiration_date: value
+                    .map(|v| utils::parse_date(v.trim()))
+                    .transpose()
+                    .map_err(|e| located_error(&location, e))?,
+            },
+            ("set", None) => return Err(located_error(&location, "expected `set KEY=VALUE`")),
+            ("unset", Some(_)) => return Err(located_error(&location, "expected `unset KEY`")),
+            _ => {
+                return Err(located_error(
+                    &location,
+                    format!(
+                        "unknown operation `{}`, expected set, unset, comment or expire",
+                        command
+                    ),
+                ));
+            }
+        };
+
+        located.push(LocatedOperation {
+            location,
+            operation,
+        });
+    }
+
+    Ok(located)
+}
+
+/// parses a JSON array of operations or, if the input does not look like JSON, a script with one
+/// operation per line
+pub fn parse_operations(input: &str) -> AppResult<Vec<LocatedOperation>> {
+    let operations = if input.trim_start().starts_with('[') {
+        parse_json(input)?
+    } else {
+        parse_script(input)?
+    };
+
+    if operations.is_empty() {
+        return Err(AppError::Msg(
+            "There are no operations to apply".to_string(),
+        ));
+    }
+
+    Ok(operations)
+}
+
+/// applies all operations to a copy of `envs`, which is only returned if every one of them was
+/// valid. only keys of new environment variables are checked against the key policy
+pub fn apply_operations(envs: &EnvMap, operations: &[LocatedOperation]) -> AppResult<EnvMap> {
+    let mut envs = envs.clone();
+
+    for LocatedOperation {
+        location,
+        operation,
+    } in operations
+    {
+        let missing = || {
+            located_error(
+                location,
+                format!(
+                    "there is no environment variable named `{}`",
+                    operation.key()
+                ),
+            )
+        };
+
+        match operation {
+            Operation::Set {
+                key,
+                value,
+                comment,
+                expiration_date,
+            } => {
+                let existing = envs.get(key);
+
+                let mut env = Env::new(
+                    key.clone(),
+                    value.clone(),
+                    comment
+                        .clone()
+                        .or_else(|| existing.and_then(|e| e.comment.clone())),
+                    expiration_date.or_else(|| existing.and_then(|e| e.expiration_date)),
+                );
+                env.tags = existing.map(|e| e.tags.clone()).unwrap_or_default();
+                env.rotation = existing.and_then(|e| e.rotation.clone());
+
+                envs.insert(env).map_err(|e| located_error(location, e))?;
+            }
+            Operation::Unset { key } => {
+                envs.remove(key).map_err(|_| missing())?;
+            }
+            Operation::Comment { key, comment } => {
+                let env = envs
+                    .iter_mut()
+                    .find(|env| &env.key == key)
+                    .ok_or_else(missing)?;
+                env.comment = comment.clone();
+            }
+            Operation::Expire {
+                key,
+                expiration_date,
+            } => {
+                let env = envs
+                    .iter_mut()
+                    .find(|env| &env.key == key)
+                    .ok_or_else(missing)?;
+                env.expiration_date = *expiration_date;
+            }
+        }
+    }
+
+    Ok(envs)
+}
+
+#[cfg(test)]
+mod tests {
+    use envio::KeyPolicy;
+
+    use super::*;
+
+    fn envs() -> EnvMap {
+        EnvMap::from(vec![
+            Env::new(
+                "A",
+                "1",
+                Some("kept".to_string()),
+                NaiveDate::from_ymd_opt(2030, 1, 1),
+            ),
+            Env::from_key_value("B", "2"),
+            Env::from_key_value("old-key", "3"),
+        ])
+    }
+
+    fn apply(input: &str) -> AppResult<EnvMap> {
+        apply_operations(&envs(), &parse_operations(input)?)
+    }
+
+    fn error(input: &str) -> String {
+        apply(input).err().unwrap().to_string()
+    }
+
+    #[test]
+    fn applies_a_script() {
+        let envs = apply(
+            "# comment\n\nset A=new=value\nset C=\"multi\\nline\"\nunset B\ncomment C= a note \nexpire C=2031-02-03\n",
+        )
+        .unwrap();
+
+        let a = envs.get("A").unwrap();
+        assert_eq!(a.value, "new=value");
+        assert_eq!(a.comment.as_deref(), Some("kept"));
+        assert_eq!(a.expiration_date, NaiveDate::from_ymd_opt(2030, 1, 1));
+
+        let c = envs.get("C").unwrap();
+        assert_eq!(c.value, "multi\nline");
+        assert_eq!(c.comment.as_deref(), Some("a note"));
+        assert_eq!(c.expiration_date, NaiveDate::from_ymd_opt(2031, 2, 3));
+
+        assert!(!envs.contains_key("B"));
+    }
+
+    #[test]
+    fn removes_comments_and_expiration_dates_without_a_value() {
+        let envs = apply("comment A\nexpire A\n").unwrap();
+
+        let a = envs.get("A").unwrap();
+        assert_eq!(a.comment, None);
+        assert_eq!(a.expiration_date, None);
+    }
+
+    #[test]
+    fn applies_json() {
+        let envs = apply(
+            r#"[
+                {"op": "set", "key": "C", "value": "3", "comment": "new", "expires": "2031-01-01"},
+                {"op": "unset", "key": "A"},
+                {"op": "comment", "key": "B", "comment": "two"},
+                {"op": "expire", "key": "B", "date": "2032-01-01"}
+            ]"#,
+        )
+        .unwrap();
+
+        let c = envs.get("C").unwrap();
+        assert_eq!(c.value, "3");
+        assert_eq!(c.comment.as_deref(), Some("new"));
+        assert_eq!(c.expiration_date, NaiveDate::from_ymd_opt(2031, 1, 1));
+
+        let b = envs.get("B").unwrap();
+        assert_eq!(b.comment.as_deref(), Some("two"));
+        assert_eq!(b.expiration_date, NaiveDate::from_ymd_opt(2032, 1, 1));
+
+        assert!(!envs.contains_key("A"));
+    }
+
+    #[test]
+    fn reports_where_an_operation_failed() {
+        assert_eq!(
+            error("set C=3\nunset D\n"),
+            "Line 2: there is no environment variable named `D`"
+        );
+        assert_eq!(error("set C\n"), "Line 1: expected `set KEY=VALUE`");
+        assert_eq!(error("unset A=1\n"), "Line 1: expected `unset KEY`");
+        assert!(error("\nrename A B\n").starts_with("Line 2: unknown operation `rename`"));
+        assert!(error("expire A=someday\n").starts_with("Line 1: "));
+        assert_eq!(
+            error(r#"[{"op": "set", "key": "C", "value": "3"}, {"op": "comment", "key": "D"}]"#),
+            "Operation 2: there is no environment variable named `D`"
+        );
+        assert!(error(r#"[{"op": "set", "key": "C"}]"#).starts_with("Invalid operations"));
+        assert!(
+            error(r#"[{"op": "unset", "key": "A", "value": "1"}]"#)
+                .starts_with("Invalid operations")
+        );
+    }
+
+    #[test]
+    fn checks_keys_against_the_key_policy() {
+        assert!(error("set A=1\nset my-key=1\n").starts_with("Line 2: `my-key` is not a valid"));
+
+        let mut lenient = envs();
+        lenient.set_key_policy(KeyPolicy::Lenient);
+        let envs =
+            apply_operations(&lenient, &parse_operations("set my-key=1\n").unwrap()).unwrap();
+        assert_eq!(envs.get("my-key").unwrap().value, "1");
+    }
+
+    #[test]
+    fn changes_existing_keys_the_key_policy_does_not_allow() {
+        let envs = apply("set old-key=4\ncomment old-key=still here\n").unwrap();
+        assert_eq!(envs.get("old-key").unwrap().value, "4");
+        assert_eq!(
+            envs.get("old-key").unwrap().comment.as_deref(),
+            Some("still here")
+        );
+
+        assert!(!apply("unset old-key\n").unwrap().contains_key("old-key"));
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert_eq!(error("# nothing\n"), "There are no operations to apply");
+        assert_eq!(error("[]"), "There are no operations to apply");
+    }
+}
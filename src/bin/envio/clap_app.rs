@@ -11,6 +11,20 @@ pub struct ClapApp {
         global = true
     )]
     pub diagnostic: bool,
+    #[arg(
+        long = "profile-dir",
+        value_name = "DIR",
+        help = "directory to read and write profiles from (overrides --store)",
+        global = true
+    )]
+    pub profile_dir: Option<String>,
+    #[arg(
+        long = "store",
+        value_name = "STORE",
+        help = "name of the profile store to use",
+        global = true
+    )]
+    pub store: Option<String>,
 }
 
 #[derive(clap::Subcommand, Debug)]
@@ -40,9 +54,17 @@ pub enum Command {
             long = "envs",
             short = 'e',
             num_args = 1..,
-            help = "environment variables to add (format: KEY=VALUE or only provide KEY and the value will be prompted for)"
+            help = "environment variables to add (format: KEY=VALUE, KEY=@FILE to read the value from a file or only provide KEY and the value will be prompted for)"
         )]
         envs: Option<Vec<String>>,
+        #[arg(
+            long = "stdin",
+            requires = "envs",
+            help = "read the value of the environment variable provided without one from stdin"
+        )]
+        stdin: bool,
+        #[arg(long = "hidden", help = "hide values while typing them into prompts")]
+        hidden: bool,
         #[arg(long = "cipher-kind", short = 'k', help = "encryption cipher to use")]
         cipher_kind: Option<String>,
         #[arg(
@@ -57,28 +79,177 @@ pub enum Command {
             help = "add expiration dates to the provided environment variables"
         )]
         expires: bool,
+        #[arg(
+            long = "comment",
+            value_name = "KEY=TEXT",
+            action = clap::ArgAction::Append,
+            help = "set the comment of an environment variable without being prompted"
+        )]
+        comment: Vec<String>,
+        #[arg(
+            long = "expire",
+            value_name = "KEY=DATE",
+            action = clap::ArgAction::Append,
+            help = "set the expiration date of an environment variable without being prompted (YYYY-MM-DD or a duration such as 90d)"
+        )]
+        expire: Vec<String>,
+        #[arg(
+            long = "expires-in",
+            value_name = "DURATION",
+            help = "let the provided environment variables expire after a duration (e.g. 90d, 12w, 6m, 1y)"
+        )]
+        expires_in: Option<String>,
+        #[arg(
+            long = "layout",
+            help = "how the environment variables are stored (blob, per-entry)",
+            value_parser = clap::builder::PossibleValuesParser::new(["blob", "per-entry"])
+        )]
+        layout: Option<String>,
+        #[arg(
+            long = "clear-comments",
+            help = "keep comments in cleartext (per-entry layout only)"
+        )]
+        clear_comments: bool,
+        #[arg(
+            long = "kdf-memory",
+            value_name = "KIB",
+            help = "memory used to derive the key from the passphrase, in KiB (passphrase only)"
+        )]
+        kdf_memory: Option<u32>,
+        #[arg(
+            long = "kdf-iterations",
+            value_name = "N",
+            help = "iterations used to derive the key from the passphrase (passphrase only)"
+        )]
+        kdf_iterations: Option<u32>,
+        #[arg(
+            long = "kdf-parallelism",
+            value_name = "N",
+            help = "lanes used to derive the key from the passphrase (passphrase only)"
+        )]
+        kdf_parallelism: Option<u32>,
+        #[arg(
+            long = "on-expired",
+            value_name = "POLICY",
+            help = "what to do with expired environment variables when the profile is used (warn, skip, fail)",
+            value_parser = clap::builder::PossibleValuesParser::new(["warn", "skip", "fail"])
+        )]
+        on_expired: Option<String>,
+        #[arg(
+            long = "key-policy",
+            value_name = "POLICY",
+            help = "which names environment variables may have (posix, lenient)",
+            value_parser = clap::builder::PossibleValuesParser::new(["posix", "lenient"])
+        )]
+        key_policy: Option<String>,
+        #[arg(
+            long = "tag",
+            value_name = "NAME=VALUE",
+            action = clap::ArgAction::Append,
+            help = "add a tag to the profile, the value can be left out (e.g. owner=platform)"
+        )]
+        tags: Option<Vec<String>>,
     },
 
     #[command(
         name = "delete",
         about = "Delete a profile",
         visible_aliases = &["remove"],
-        override_usage = "envio delete <PROFILE_NAME>"
+        override_usage = "envio delete <PROFILE_NAME> [OPTIONS]"
     )]
     Delete {
         #[arg(required = true, help = "name of the profile")]
         profile_name: String,
+        #[arg(long = "force", short = 'f', help = "do not ask for confirmation")]
+        force: bool,
+    },
+
+    #[command(
+        name = "trash",
+        about = "Manage deleted profiles",
+        override_usage = "envio trash <COMMAND>"
+    )]
+    Trash {
+        #[command(subcommand)]
+        command: TrashCommand,
+    },
+
+    #[command(
+        name = "rename",
+        about = "Rename a profile",
+        visible_aliases = &["mv"],
+        override_usage = "envio rename <PROFILE_NAME> <NEW_NAME>"
+    )]
+    Rename {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(required = true, help = "new name of the profile")]
+        new_name: String,
+    },
+
+    #[command(
+        name = "describe",
+        about = "Change the description of a profile",
+        override_usage = "envio describe <PROFILE_NAME> <DESCRIPTION>"
+    )]
+    Describe {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(
+            required_unless_present = "remove",
+            conflicts_with = "remove",
+            help = "new description of the profile"
+        )]
+        description: Option<String>,
+        #[arg(long = "remove", help = "remove the description")]
+        remove: bool,
+    },
+
+    #[command(
+        name = "copy",
+        about = "Copy a profile, optionally encrypting the copy with a different cipher",
+        visible_aliases = &["cp"],
+        override_usage = "envio copy <PROFILE_NAME> <NEW_NAME> [OPTIONS]"
+    )]
+    Copy {
+        #[arg(required = true, help = "name of the profile to copy")]
+        profile_name: String,
+        #[arg(required = true, help = "name of the copy")]
+        new_name: String,
+        #[arg(
+            long = "cipher-kind",
+            short = 'k',
+            help = "encryption cipher to use for the copy, the same as the original by default"
+        )]
+        cipher_kind: Option<String>,
     },
 
     #[command(
         name = "list",
         about = "List all profiles",
         visible_aliases = &["ls"],
-        override_usage = "envio list [OPTIONS]"
+        override_usage = "envio list [PATTERN] [OPTIONS]"
     )]
     List {
+        #[arg(
+            help = "only list profiles matching a pattern (e.g. payments/, 'payments/*', '**/prod')"
+        )]
+        pattern: Option<String>,
+        #[arg(
+            long = "tag",
+            value_name = "NAME[=VALUE]",
+            action = clap::ArgAction::Append,
+            help = "only list profiles with a tag, or with a tag of a value"
+        )]
+        tags: Option<Vec<String>>,
         #[arg(long = "no-pretty-print", help = "disable pretty printing")]
         no_pretty_print: bool,
+        #[arg(
+            long = "tree",
+            short = 't',
+            help = "show profiles as a tree of their namespaces (default when listing a namespace)"
+        )]
+        tree: bool,
     },
 
     #[command(
@@ -97,8 +268,35 @@ pub enum Command {
             help = "display expiration dates"
         )]
         show_expiration: bool,
+        #[arg(long = "show-tags", short = 'g', help = "display tags")]
+        show_tags: bool,
+        #[arg(
+            long = "show-history-columns",
+            help = "display when each value was created and last changed, and by whom"
+        )]
+        show_history_columns: bool,
+        #[arg(
+            long = "tag",
+            value_name = "NAME[=VALUE]",
+            action = clap::ArgAction::Append,
+            help = "only show environment variables with a tag, or with a tag of a value"
+        )]
+        tags: Option<Vec<String>>,
         #[arg(long = "no-pretty-print", help = "disable pretty printing")]
         no_pretty_print: bool,
+        #[arg(
+            long = "quote",
+            requires = "no_pretty_print",
+            help = "print values spanning several lines or starting with `\"` in double quotes with escape sequences"
+        )]
+        quote: bool,
+        #[arg(
+            long = "on-expired",
+            value_name = "POLICY",
+            help = "handle expired environment variables instead of only warning about them (warn, skip, fail)",
+            value_parser = clap::builder::PossibleValuesParser::new(["warn", "skip", "fail"])
+        )]
+        on_expired: Option<String>,
     },
 
     #[command(
@@ -109,8 +307,20 @@ pub enum Command {
     Set {
         #[arg(required = true, help = "name of the profile")]
         profile_name: String,
-        #[arg(required = true, num_args = 1.., help = "environment variables to set (format: KEY=VALUE or only provide KEY and the value will be prompted for)")]
+        #[arg(
+            required_unless_present_any = ["comment", "expire", "unset_comment", "unset_expiration"],
+            num_args = 1..,
+            help = "environment variables to set (format: KEY=VALUE, KEY=@FILE to read the value from a file or only provide KEY and the value will be prompted for)"
+        )]
         envs: Vec<String>,
+        #[arg(
+            long = "stdin",
+            requires = "envs",
+            help = "read the value of the environment variable provided without one from stdin"
+        )]
+        stdin: bool,
+        #[arg(long = "hidden", help = "hide values while typing them into prompts")]
+        hidden: bool,
         #[arg(
             long = "comments",
             short = 'c',
@@ -123,6 +333,42 @@ pub enum Command {
             help = "add expiration dates to the provided environment variables"
         )]
         expires: bool,
+        #[arg(
+            long = "comment",
+            value_name = "KEY=TEXT",
+            action = clap::ArgAction::Append,
+            help = "set the comment of an environment variable without being prompted"
+        )]
+        comment: Vec<String>,
+        #[arg(
+            long = "expire",
+            value_name = "KEY=DATE",
+            action = clap::ArgAction::Append,
+            help = "set the expiration date of an environment variable without being prompted (YYYY-MM-DD or a duration such as 90d)"
+        )]
+        expire: Vec<String>,
+        #[arg(
+            long = "expires-in",
+            value_name = "DURATION",
+            help = "let the provided environment variables expire after a duration (e.g. 90d, 12w, 6m, 1y)"
+        )]
+        expires_in: Option<String>,
+        #[arg(
+            long = "unset-comment",
+            value_name = "KEY",
+            value_delimiter = ',',
+            num_args = 1..,
+            help = "remove the comments of environment variables"
+        )]
+        unset_comment: Vec<String>,
+        #[arg(
+            long = "unset-expiration",
+            value_name = "KEY",
+            value_delimiter = ',',
+            num_args = 1..,
+            help = "remove the expiration dates of environment variables"
+        )]
+        unset_expiration: Vec<String>,
     },
 
     #[command(
@@ -137,6 +383,63 @@ pub enum Command {
         keys: Vec<String>,
     },
 
+    #[command(
+        name = "edit",
+        about = "Edit the environment variables of a profile in your editor",
+        override_usage = "envio edit <PROFILE_NAME>"
+    )]
+    Edit {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+    },
+
+    #[command(
+        name = "merge",
+        about = "Merge the environment variables of a dotenv file or another profile into a profile",
+        override_usage = "envio merge <PROFILE_NAME> --from <SOURCE> [OPTIONS]"
+    )]
+    Merge {
+        #[arg(required = true, help = "name of the profile to merge into")]
+        profile_name: String,
+        #[arg(
+            long = "from",
+            short = 'f',
+            value_name = "SOURCE",
+            required = true,
+            help = "dotenv file or name of the profile to merge from"
+        )]
+        from: String,
+        #[arg(
+            long = "strategy",
+            short = 's',
+            default_value = "prompt",
+            help = "what to do with variables that exist in both with different values (keep, overwrite, prompt)",
+            value_parser = clap::builder::PossibleValuesParser::new(["keep", "overwrite", "prompt"])
+        )]
+        strategy: String,
+        #[arg(
+            long = "dry-run",
+            help = "only show what would be merged without saving anything"
+        )]
+        dry_run: bool,
+    },
+
+    #[command(
+        name = "apply",
+        about = "Apply a batch of changes to a profile at once",
+        override_usage = "envio apply <PROFILE_NAME> [OPTIONS] < <CHANGES>"
+    )]
+    Apply {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(
+            long = "file",
+            short = 'f',
+            help = "read the changes from a file instead of stdin"
+        )]
+        file: Option<String>,
+    },
+
     #[command(
         name = "load",
         about = "Load environment variables from a profile for use in terminal sessions",
@@ -145,6 +448,13 @@ pub enum Command {
     Load {
         #[arg(required = true, help = "name of the profile")]
         profile_name: String,
+        #[arg(
+            long = "on-expired",
+            value_name = "POLICY",
+            help = "override what to do with expired environment variables (warn, skip, fail)",
+            value_parser = clap::builder::PossibleValuesParser::new(["warn", "skip", "fail"])
+        )]
+        on_expired: Option<String>,
     },
 
     #[cfg(target_family = "unix")]
@@ -174,6 +484,46 @@ pub enum Command {
     Run {
         #[arg(required = true, help = "name of the profile")]
         profile_name: String,
+        #[arg(
+            long = "keys",
+            short = 'k',
+            value_delimiter = ',',
+            num_args = 1..,
+            help = "comma-separated list of keys to decrypt and pass to the command"
+        )]
+        keys: Option<Vec<String>>,
+        #[arg(
+            long = "on-expired",
+            value_name = "POLICY",
+            help = "override what to do with expired environment variables (warn, skip, fail)",
+            value_parser = clap::builder::PossibleValuesParser::new(["warn", "skip", "fail"])
+        )]
+        on_expired: Option<String>,
+        #[arg(
+            long = "schema",
+            value_name = "FILE",
+            conflicts_with = "no_check",
+            help = "check the environment variables against this schema file before running"
+        )]
+        schema: Option<String>,
+        #[arg(
+            long = "no-check",
+            help = "run even if the environment variables do not match the schema"
+        )]
+        no_check: bool,
+        #[arg(
+            long = "file-var",
+            value_name = "VAR=KEY",
+            action = clap::ArgAction::Append,
+            help = "write the value of KEY to a private temporary file and set VAR to its path"
+        )]
+        file_vars: Option<Vec<String>>,
+        #[arg(
+            long = "memfd",
+            requires = "file_vars",
+            help = "keep the files of --file-var in memory and pass them as /dev/fd paths (linux only)"
+        )]
+        memfd: bool,
         #[arg(last = true, required = true, help = "command to run")]
         command: Vec<String>,
     },
@@ -216,6 +566,438 @@ pub enum Command {
             help = "comma-separated list of keys to export (type 'select' to choose interactively)"
         )]
         keys: Option<Vec<String>>,
+        #[arg(
+            long = "on-expired",
+            value_name = "POLICY",
+            help = "override what to do with expired environment variables (warn, skip, fail)",
+            value_parser = clap::builder::PossibleValuesParser::new(["warn", "skip", "fail"])
+        )]
+        on_expired: Option<String>,
+    },
+
+    #[command(
+        name = "render",
+        about = "Fill in the {{ KEY }} placeholders of a config file template",
+        override_usage = "envio render <PROFILE_NAME> <TEMPLATE> -o <FILE> [OPTIONS]"
+    )]
+    Render {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(required = true, help = "path of the template")]
+        template: String,
+        #[arg(
+            long = "output-file-path",
+            short = 'o',
+            required = true,
+            help = "file to write, only the current user can read it"
+        )]
+        output_file_path: String,
+        #[arg(
+            long = "strict",
+            help = "fail instead of leaving placeholders empty when their key is not in the profile"
+        )]
+        strict: bool,
+        #[arg(
+            long = "on-expired",
+            value_name = "POLICY",
+            help = "override what to do with expired environment variables (warn, skip, fail)",
+            value_parser = clap::builder::PossibleValuesParser::new(["warn", "skip", "fail"])
+        )]
+        on_expired: Option<String>,
+    },
+
+    #[command(
+        name = "example",
+        about = "Write the keys of a profile without their values, as a .env.example or Markdown",
+        override_usage = "envio example <PROFILE_NAME> [OPTIONS]"
+    )]
+    Example {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(
+            long = "output-file-path",
+            short = 'o',
+            help = "output file path (default: print to stdout)"
+        )]
+        output_file_path: Option<String>,
+        #[arg(
+            long = "markdown",
+            help = "write a Markdown table instead of a .env.example"
+        )]
+        markdown: bool,
+    },
+
+    #[command(
+        name = "verify",
+        about = "Compare the keys of a profile with the ones documented in a .env.example",
+        override_usage = "envio verify <PROFILE_NAME> --against <FILE>"
+    )]
+    Verify {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(
+            long = "against",
+            required = true,
+            value_name = "FILE",
+            help = "a .env.example or a Markdown file written by `envio example --markdown`"
+        )]
+        against: String,
+    },
+
+    #[command(
+        name = "expiring",
+        about = "List environment variables that have expired or expire soon across all profiles",
+        long_about = "List environment variables that have expired or expire soon across all profiles. Per-entry profiles are checked without being decrypted, so only comments kept in cleartext with --clear-comments are shown for them",
+        override_usage = "envio expiring [PATTERN] [OPTIONS]"
+    )]
+    Expiring {
+        #[arg(help = "only check profiles matching a pattern (e.g. payments/, 'payments/*')")]
+        pattern: Option<String>,
+        #[arg(
+            long = "within",
+            short = 'w',
+            default_value = "30d",
+            help = "how far ahead to look (e.g. 7d, 2w, 3m, 1y)"
+        )]
+        within: String,
+        #[arg(long = "json", help = "print the report as JSON")]
+        json: bool,
+    },
+
+    #[command(
+        name = "on-expired",
+        about = "Set what happens to expired environment variables when a profile is used",
+        override_usage = "envio on-expired <PROFILE_NAME> <POLICY>"
+    )]
+    OnExpired {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(
+            required = true,
+            help = "warn about them, skip them or fail (warn, skip, fail)",
+            value_parser = clap::builder::PossibleValuesParser::new(["warn", "skip", "fail"])
+        )]
+        policy: String,
+    },
+
+    #[command(
+        name = "key-policy",
+        about = "Set which names new environment variables of a profile may have",
+        override_usage = "envio key-policy <PROFILE_NAME> <POLICY>"
+    )]
+    KeyPolicy {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(
+            required = true,
+            help = "letters, digits and underscores only or anything but `=` (posix, lenient)",
+            value_parser = clap::builder::PossibleValuesParser::new(["posix", "lenient"])
+        )]
+        policy: String,
+    },
+
+    #[command(
+        name = "kdf",
+        about = "Show or change how the key of a passphrase profile is derived",
+        override_usage = "envio kdf <PROFILE_NAME> [OPTIONS]"
+    )]
+    Kdf {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(
+            long = "memory",
+            value_name = "KIB",
+            help = "memory used to derive the key from the passphrase, in KiB"
+        )]
+        memory: Option<u32>,
+        #[arg(
+            long = "iterations",
+            value_name = "N",
+            help = "iterations used to derive the key from the passphrase"
+        )]
+        iterations: Option<u32>,
+        #[arg(
+            long = "parallelism",
+            value_name = "N",
+            help = "lanes used to derive the key from the passphrase"
+        )]
+        parallelism: Option<u32>,
+    },
+
+    #[command(
+        name = "lint",
+        about = "Check the names of the environment variables in a profile",
+        override_usage = "envio lint <PROFILE_NAME> [OPTIONS]"
+    )]
+    Lint {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(long = "fix", help = "rename invalid environment variables")]
+        fix: bool,
+    },
+
+    #[command(
+        name = "schema",
+        about = "Show or set the schema the environment variables of a profile are checked against",
+        override_usage = "envio schema <PROFILE_NAME> [OPTIONS]"
+    )]
+    Schema {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(
+            long = "set",
+            value_name = "FILE",
+            help = "store the schema in this json file with the profile"
+        )]
+        set: Option<String>,
+        #[arg(
+            long = "remove",
+            conflicts_with = "set",
+            help = "remove the schema stored with the profile"
+        )]
+        remove: bool,
+    },
+
+    #[command(
+        name = "check",
+        about = "Check the environment variables of a profile against its schema",
+        override_usage = "envio check <PROFILE_NAME> [OPTIONS]"
+    )]
+    Check {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(
+            long = "schema",
+            value_name = "FILE",
+            help = "schema file to check against (default: envio.schema.json or the schema of the profile)"
+        )]
+        schema: Option<String>,
+    },
+
+    #[command(
+        name = "tag",
+        about = "Show or change the tags of a profile or one of its environment variables",
+        override_usage = "envio tag <PROFILE_NAME> [TAGS]... [OPTIONS]"
+    )]
+    Tag {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(help = "tags to add or update (format: NAME=VALUE or only NAME)")]
+        tags: Option<Vec<String>>,
+        #[arg(
+            long = "env",
+            short = 'e',
+            value_name = "KEY",
+            help = "tag an environment variable instead of the profile"
+        )]
+        env: Option<String>,
+        #[arg(
+            long = "remove",
+            short = 'r',
+            value_name = "NAME",
+            action = clap::ArgAction::Append,
+            help = "remove a tag"
+        )]
+        remove: Option<Vec<String>>,
+    },
+
+    #[command(
+        name = "rotation-policy",
+        about = "Show or set how often an environment variable needs a new value",
+        override_usage = "envio rotation-policy <PROFILE_NAME> <KEY> [OPTIONS]"
+    )]
+    RotationPolicy {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(required = true, help = "name of the environment variable")]
+        key: String,
+        #[arg(
+            long = "max-age",
+            value_name = "DURATION",
+            help = "how long a value may be used before it is due for rotation (e.g. 90d, 12w, 6m, 1y), which replaces its expiration date"
+        )]
+        max_age: Option<String>,
+        #[arg(
+            long = "generator",
+            short = 'g',
+            value_name = "GENERATOR",
+            conflicts_with = "command",
+            help = "generate new values randomly (alnum, alpha, digits, ascii, words, hex, base64 or uuid, optionally followed by :LENGTH)"
+        )]
+        generator: Option<String>,
+        #[arg(
+            long = "command",
+            value_name = "COMMAND",
+            help = "shell command that prints new values, it receives the current one on stdin"
+        )]
+        command: Option<String>,
+        #[arg(
+            long = "remove",
+            conflicts_with_all = ["max_age", "generator", "command"],
+            help = "remove the rotation policy"
+        )]
+        remove: bool,
+    },
+
+    #[command(
+        name = "rotate",
+        about = "Give environment variables a new value",
+        override_usage = "envio rotate <PROFILE_NAME> <KEY> [OPTIONS]\n       envio rotate [PROFILE_NAME] --due [OPTIONS]"
+    )]
+    Rotate {
+        #[arg(
+            required_unless_present = "due",
+            help = "name of the profile, every profile is checked with --due if left out"
+        )]
+        profile_name: Option<String>,
+        #[arg(
+            required_unless_present = "due",
+            conflicts_with = "due",
+            help = "name of the environment variable"
+        )]
+        key: Option<String>,
+        #[arg(
+            long = "generator",
+            short = 'g',
+            value_name = "GENERATOR",
+            conflicts_with = "command",
+            help = "generate the new value randomly instead of how the rotation policy says (alnum, alpha, digits, ascii, words, hex, base64 or uuid, optionally followed by :LENGTH)"
+        )]
+        generator: Option<String>,
+        #[arg(
+            long = "command",
+            value_name = "COMMAND",
+            help = "shell command that prints the new value, it receives the current one on stdin"
+        )]
+        command: Option<String>,
+        #[arg(
+            long = "due",
+            help = "rotate every environment variable that is older than the max age of its rotation policy"
+        )]
+        due: bool,
+        #[arg(
+            long = "list",
+            requires = "due",
+            help = "only list the environment variables that are due for rotation"
+        )]
+        list: bool,
+    },
+
+    #[command(
+        name = "generate",
+        about = "Set an environment variable to a randomly generated value",
+        override_usage = "envio generate <PROFILE_NAME> <KEY> [OPTIONS]"
+    )]
+    Generate {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(required = true, help = "name of the environment variable")]
+        key: String,
+        #[arg(
+            long = "length",
+            short = 'l',
+            value_name = "N",
+            help = "number of characters, words with --words or bytes with --hex and --base64 (default: 32, 6 words)"
+        )]
+        length: Option<usize>,
+        #[arg(
+            long = "charset",
+            value_name = "CHARSET",
+            conflicts_with_all = ["chars", "words", "hex", "base64", "uuid"],
+            help = "characters to pick from (alnum, alpha, digits, ascii)",
+            value_parser = clap::builder::PossibleValuesParser::new(["alnum", "alpha", "digits", "ascii"])
+        )]
+        charset: Option<String>,
+        #[arg(
+            long = "chars",
+            value_name = "CHARS",
+            conflicts_with_all = ["words", "hex", "base64", "uuid"],
+            help = "custom characters to pick from"
+        )]
+        chars: Option<String>,
+        #[arg(
+            long = "words",
+            conflicts_with_all = ["hex", "base64", "uuid"],
+            help = "generate a passphrase of words from a built-in wordlist"
+        )]
+        words: bool,
+        #[arg(
+            long = "separator",
+            value_name = "SEPARATOR",
+            requires = "words",
+            help = "what to put between the words of a passphrase (default: -)"
+        )]
+        separator: Option<String>,
+        #[arg(
+            long = "hex",
+            conflicts_with_all = ["base64", "uuid"],
+            help = "generate random bytes encoded as hex"
+        )]
+        hex: bool,
+        #[arg(
+            long = "base64",
+            conflicts_with = "uuid",
+            help = "generate random bytes encoded as base64"
+        )]
+        base64: bool,
+        #[arg(
+            long = "uuid",
+            conflicts_with = "length",
+            help = "generate a random UUID"
+        )]
+        uuid: bool,
+        #[arg(
+            long = "force",
+            short = 'f',
+            help = "replace the value if the variable exists"
+        )]
+        force: bool,
+    },
+
+    #[command(
+        name = "layout",
+        about = "Change how the environment variables of a profile are stored",
+        override_usage = "envio layout <PROFILE_NAME> <LAYOUT> [OPTIONS]"
+    )]
+    Layout {
+        #[arg(required = true, help = "name of the profile")]
+        profile_name: String,
+        #[arg(
+            required = true,
+            help = "layout to convert to (blob, per-entry)",
+            value_parser = clap::builder::PossibleValuesParser::new(["blob", "per-entry"])
+        )]
+        layout: String,
+        #[arg(
+            long = "clear-comments",
+            help = "keep comments in cleartext (per-entry layout only)"
+        )]
+        clear_comments: bool,
+    },
+
+    #[command(
+        name = "store",
+        about = "Manage named profile stores",
+        override_usage = "envio store <COMMAND>"
+    )]
+    Store {
+        #[command(subcommand)]
+        command: StoreCommand,
+    },
+
+    #[command(
+        name = "sync",
+        about = "Pull and push the profiles of a git backed store",
+        override_usage = "envio sync [OPTIONS]"
+    )]
+    Sync {
+        #[arg(
+            long = "keep",
+            value_name = "SIDE",
+            help = "resolve conflicting profiles by keeping one side (local, remote)",
+            value_parser = clap::builder::PossibleValuesParser::new(["local", "remote"])
+        )]
+        keep: Option<String>,
     },
 
     #[command(
@@ -253,3 +1035,88 @@ pub enum Command {
         verbose: bool,
     },
 }
+
+#[derive(clap::Subcommand, Debug)]
+pub enum TrashCommand {
+    #[command(
+        name = "list",
+        about = "List deleted profiles",
+        visible_aliases = &["ls"],
+        override_usage = "envio trash list"
+    )]
+    List,
+
+    #[command(
+        name = "restore",
+        about = "Restore a deleted profile to where it was deleted from",
+        override_usage = "envio trash restore <ID>"
+    )]
+    Restore {
+        #[arg(
+            required = true,
+            help = "id of the deleted profile, or its name to restore the most recently deleted one"
+        )]
+        id: String,
+    },
+
+    #[command(
+        name = "empty",
+        about = "Permanently delete everything in the trash",
+        override_usage = "envio trash empty [OPTIONS]"
+    )]
+    Empty {
+        #[arg(long = "force", short = 'f', help = "do not ask for confirmation")]
+        force: bool,
+    },
+
+    #[command(
+        name = "retention",
+        about = "Show or change how many days deleted profiles are kept for (0 keeps them forever)",
+        override_usage = "envio trash retention [DAYS]"
+    )]
+    Retention { days: Option<u32> },
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum StoreCommand {
+    #[command(
+        name = "add",
+        about = "Register a directory as a named profile store",
+        override_usage = "envio store add <STORE_NAME> <PATH>"
+    )]
+    Add {
+        #[arg(required = true, help = "name of the store")]
+        store_name: String,
+        #[arg(required = true, help = "directory the store keeps its profiles in")]
+        path: String,
+        #[arg(
+            long = "git",
+            help = "keep the store in a git repository and commit every change"
+        )]
+        git: bool,
+        #[arg(
+            long = "remote",
+            help = "git remote to clone from or sync with (implies --git)"
+        )]
+        remote: Option<String>,
+    },
+
+    #[command(
+        name = "remove",
+        about = "Unregister a named profile store (its profiles are left untouched)",
+        visible_aliases = &["rm"],
+        override_usage = "envio store remove <STORE_NAME>"
+    )]
+    Remove {
+        #[arg(required = true, help = "name of the store")]
+        store_name: String,
+    },
+
+    #[command(
+        name = "list",
+        about = "List all profile stores",
+        visible_aliases = &["ls"],
+        override_usage = "envio store list"
+    )]
+    List,
+}
@@ -1,12 +1,17 @@
-use std::{io::Read, path::Path};
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
 
-use chrono::Local;
+use chrono::{Local, NaiveDate};
 use colored::Colorize;
 use envio::{
-    Env, EnvMap,
-    cipher::{CipherKind, create_cipher, gpg::get_gpg_keys},
-    get_profile,
-    profile::SerializedProfile,
+    Env, EnvMap, KeyPolicy, RotationPolicy,
+    cipher::{CipherKind, PASSPHRASE, create_cipher, gpg::get_gpg_keys, passphrase::KdfParams},
+    get_partial_profile, get_profile,
+    layout::ContentLayout,
+    profile::{ExpiredPolicy, SerializedProfile},
+    store::{ConflictResolution, GitStore, open_store},
 };
 use indexmap::IndexMap;
 use strum::IntoEnumIterator;
@@ -14,13 +19,22 @@ use url::Url;
 use zeroize::Zeroizing;
 
 use crate::{
-    clap_app::{ClapApp, Command},
+    batch,
+    clap_app::{ClapApp, Command, StoreCommand, TrashCommand},
     completions,
+    config::{Config, DEFAULT_STORE},
     diagnostic::DiagnosticReport,
+    dotenv,
     error::{AppError, AppResult},
-    error_msg, ops, prompts, success_msg,
+    error_msg, example,
+    generator::{self, Charset, Generator},
+    ops,
+    private_file::SecretFile,
+    prompts, render, schema, success_msg,
+    tags::{self, TagFilter},
+    trash,
     tui::TuiApp,
-    utils,
+    utils, warning_msg,
 };
 
 fn get_userkey() -> Zeroizing<String> {
@@ -43,7 +57,394 @@ fn get_userkey() -> Zeroizing<String> {
     }
 }
 
+/// asks for the key a new profile is encrypted with, or the GPG key to encrypt it for
+fn get_new_cipher_key(kind: CipherKind) -> AppResult<Option<Zeroizing<String>>> {
+    Ok(match kind {
+        CipherKind::GPG => {
+            let available_keys = get_gpg_keys()?;
+
+            if available_keys.is_empty() {
+                return Err(AppError::Msg("No GPG keys found".to_string()));
+            }
+
+            if let Ok(env_key) = std::env::var("ENVIO_KEY") {
+                if available_keys
+                    .iter()
+                    .any(|(_, fingerprint)| *fingerprint == env_key)
+                {
+                    Some(env_key.into())
+                } else {
+                    return Err(AppError::Msg(
+                        "ENVIO_KEY does not match any available GPG fingerprint".to_string(),
+                    ));
+                }
+            } else {
+                let labels: Vec<String> = available_keys
+                    .iter()
+                    .map(|(label, _)| label.clone())
+                    .collect();
+
+                let selected_label = prompts::select_prompt(prompts::SelectPromptOptions {
+                    title: "Select the GPG key you want to use for encryption:".to_string(),
+                    options: labels,
+                })?;
+
+                let fingerprint = available_keys
+                    .into_iter()
+                    .find(|(label, _)| *label == selected_label)
+                    .map(|(_, fingerprint)| fingerprint)
+                    .unwrap();
+
+                Some(fingerprint.into())
+            }
+        }
+
+        CipherKind::PASSPHRASE | CipherKind::AGE => {
+            Some(if let Ok(key) = std::env::var("ENVIO_KEY") {
+                Zeroizing::new(key)
+            } else {
+                prompts::password_prompt(prompts::PasswordPromptOptions {
+                    title: "Enter your encryption key:".to_string(),
+                    help_message: Some(
+                        "Remember this key, you will need it to decrypt your profile later"
+                            .to_string(),
+                    ),
+                    min_length: Some(8),
+                    with_confirmation: true,
+                    confirmation_error_message: Some("The keys don't match".to_string()),
+                })?
+                .into()
+            })
+        }
+
+        _ => None,
+    })
+}
+
+/// exit codes of `envio expiring` when it found something, so it can be used in monitoring. they
+/// are clear of the codes used for errors and by clap, so scripts can tell them apart
+const EXPIRING_EXIT_CODE: i32 = 10;
+const EXPIRED_EXIT_CODE: i32 = 11;
+
+/// asks for the key of one of many profiles, `None` if it can not be prompted for
+fn get_profile_key(profile_name: &str) -> Option<Zeroizing<String>> {
+    if let Ok(key) = std::env::var("ENVIO_KEY") {
+        return Some(Zeroizing::new(key));
+    }
+
+    prompts::password_prompt(prompts::PasswordPromptOptions {
+        title: format!("Enter the encryption key for '{}':", profile_name),
+        help_message: None,
+        min_length: None,
+        with_confirmation: false,
+        confirmation_error_message: None,
+    })
+    .ok()
+    .map(Zeroizing::new)
+}
+
+/// comments and expiration dates passed to `create` and `set`, validated before anything is
+/// decrypted or prompted for
+struct EnvAnnotations {
+    prompt_comments: bool,
+    prompt_expiration_dates: bool,
+    comments: IndexMap<String, String>,
+    expiration_dates: IndexMap<String, NaiveDate>,
+    expires_in: Option<NaiveDate>,
+    unset_comments: Vec<String>,
+    unset_expiration_dates: Vec<String>,
+}
+
+impl EnvAnnotations {
+    fn parse(
+        prompt_comments: bool,
+        prompt_expiration_dates: bool,
+        comments: &[String],
+        expires: &[String],
+        expires_in: &Option<String>,
+        unset_comments: &[String],
+        unset_expiration_dates: &[String],
+    ) -> AppResult<Self> {
+        let mut annotations = EnvAnnotations {
+            prompt_comments,
+            prompt_expiration_dates,
+            comments: IndexMap::new(),
+            expiration_dates: IndexMap::new(),
+            expires_in: expires_in
+                .as_ref()
+                .map(|duration| utils::add_duration(Local::now().date_naive(), duration))
+                .transpose()?,
+            unset_comments: unset_comments.to_vec(),
+            unset_expiration_dates: unset_expiration_dates.to_vec(),
+        };
+
+        for comment in comments {
+            let (key, text) = utils::parse_assignment(comment, "--comment")?;
+            annotations.comments.insert(key, text);
+        }
+
+        for expiration in expires {
+            let (key, date) = utils::parse_assignment(expiration, "--expire")?;
+            annotations
+                .expiration_dates
+                .insert(key, utils::parse_date(&date)?);
+        }
+
+        for key in &annotations.unset_comments {
+            if annotations.comments.contains_key(key) {
+                return Err(AppError::Msg(format!(
+                    "Can not both set and unset the comment of `{}`",
+                    key
+                )));
+            }
+        }
+
+        for key in &annotations.unset_expiration_dates {
+            if annotations.expiration_dates.contains_key(key) {
+                return Err(AppError::Msg(format!(
+                    "Can not both set and unset the expiration date of `{}`",
+                    key
+                )));
+            }
+        }
+
+        Ok(annotations)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.comments.is_empty()
+            && self.expiration_dates.is_empty()
+            && self.unset_comments.is_empty()
+            && self.unset_expiration_dates.is_empty()
+    }
+
+    /// errors for the first key that does not belong to any environment variable
+    fn check_keys<F: Fn(&str) -> bool>(&self, exists: F) -> AppResult<()> {
+        let keys = self
+            .comments
+            .keys()
+            .chain(self.expiration_dates.keys())
+            .chain(&self.unset_comments)
+            .chain(&self.unset_expiration_dates);
+
+        for key in keys {
+            if !exists(key) {
+                return Err(AppError::Msg(format!(
+                    "There is no environment variable named `{}`",
+                    key
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// annotates an environment variable that was provided on the command line
+    fn annotate_new(&self, env: &mut Env) -> AppResult<()> {
+        if let Some(comment) = self.comments.get(&env.key) {
+            env.comment = Some(comment.clone());
+        } else if self.prompt_comments {
+            env.comment = Some(prompts::text_prompt(prompts::TextPromptOptions {
+                title: format!("Enter a comment for '{}':", env.key),
+                default: None,
+            })?);
+        }
+
+        if let Some(date) = self
+            .expiration_dates
+            .get(&env.key)
+            .or(self.expires_in.as_ref())
+        {
+            env.expiration_date = Some(*date);
+        } else if self.prompt_expiration_dates {
+            env.expiration_date = Some(prompts::date_prompt(prompts::DatePromptOptions {
+                title: format!("Select an expiration date for '{}':", env.key),
+                default: Some(Local::now().date_naive()),
+            })?);
+        }
+
+        self.annotate_existing(env);
+
+        Ok(())
+    }
+
+    /// applies the explicitly given annotations to an environment variable already in a profile
+    fn annotate_existing(&self, env: &mut Env) {
+        if let Some(comment) = self.comments.get(&env.key) {
+            env.comment = Some(comment.clone());
+        }
+
+        if let Some(date) = self.expiration_dates.get(&env.key) {
+            env.expiration_date = Some(*date);
+        }
+
+        if self.unset_comments.contains(&env.key) {
+            env.comment = None;
+        }
+
+        if self.unset_expiration_dates.contains(&env.key) {
+            env.expiration_date = None;
+        }
+    }
+}
+
+fn strip_trailing_newline(mut value: String) -> String {
+    if value.ends_with('\n') {
+        value.pop();
+
+        if value.ends_with('\r') {
+            value.pop();
+        }
+    }
+
+    value
+}
+
+/// reads environment variables given as `KEY=VALUE`, `KEY=@FILE` or only `KEY`, in which case
+/// the value is read from stdin or prompted for. values can span multiple lines
+fn read_envs(
+    args: &[String],
+    from_stdin: bool,
+    hidden: bool,
+    existing: Option<&EnvMap>,
+    key_policy: KeyPolicy,
+) -> AppResult<Vec<Env>> {
+    let without_value = args.iter().filter(|arg| !arg.contains('=')).count();
+
+    if from_stdin && without_value != 1 {
+        return Err(AppError::Msg(
+            "`--stdin` needs exactly one environment variable provided without a value".to_string(),
+        ));
+    }
+
+    // files are read first so a missing one is reported before anything is prompted for
+    let mut values = Vec::with_capacity(args.len());
+    for arg in args {
+        let (key, value) = match arg.split_once('=') {
+            Some((key, value)) => (key, Some(value)),
+            None => (arg.as_str(), None),
+        };
+
+        // existing keys are kept even if they do not follow the key policy
+        if !existing.is_some_and(|envs| envs.contains_key(key)) {
+            key_policy.check(key)?;
+        }
+
+        let Some(value) = value else {
+            values.push((key, None));
+            continue;
+        };
+
+        let value = if let Some(literal) = value.strip_prefix("@@") {
+            format!("@{}", literal)
+        } else if let Some(path) = value.strip_prefix('@') {
+            strip_trailing_newline(std::fs::read_to_string(path).map_err(|e| {
+                AppError::Msg(format!(
+                    "Failed to read the value of '{}' from '{}': {}",
+                    key, path, e
+                ))
+            })?)
+        } else {
+            value.to_string()
+        };
+
+        values.push((key, Some(value)));
+    }
+
+    let mut envs = Vec::with_capacity(values.len());
+    for (key, value) in values {
+        let value = match value {
+            Some(value) => value,
+            None if from_stdin => {
+                let mut buffer = String::new();
+                std::io::stdin().read_to_string(&mut buffer)?;
+                strip_trailing_newline(buffer)
+            }
+            None => {
+                let title = format!(
+                    "Enter the {}value for {}:",
+                    if existing.is_some_and(|envs| envs.contains_key(key)) {
+                        "new "
+                    } else {
+                        ""
+                    },
+                    key
+                );
+
+                if hidden {
+                    prompts::password_prompt(prompts::PasswordPromptOptions {
+                        title,
+                        help_message: None,
+                        min_length: Some(0),
+                        with_confirmation: false,
+                        confirmation_error_message: None,
+                    })?
+                } else {
+                    prompts::text_prompt(prompts::TextPromptOptions {
+                        title,
+                        default: None,
+                    })?
+                }
+            }
+        };
+
+        envs.push(Env::from_key_value(key, value));
+    }
+
+    Ok(envs)
+}
+
+/// `base` with the parameters that were given replaced
+fn build_kdf_params(
+    base: KdfParams,
+    memory: Option<u32>,
+    iterations: Option<u32>,
+    parallelism: Option<u32>,
+) -> AppResult<KdfParams> {
+    let params = KdfParams {
+        memory: memory.unwrap_or(base.memory),
+        iterations: iterations.unwrap_or(base.iterations),
+        parallelism: parallelism.unwrap_or(base.parallelism),
+    };
+    params.to_argon2()?;
+
+    Ok(params)
+}
+
+fn parse_expired_policy(policy: &Option<String>) -> AppResult<Option<ExpiredPolicy>> {
+    policy
+        .as_ref()
+        .map(|policy| {
+            policy
+                .parse::<ExpiredPolicy>()
+                .map_err(|e| AppError::Msg(e.to_string()))
+        })
+        .transpose()
+}
+
 impl ClapApp {
+    pub fn select_profile_dir(&self) -> AppResult<()> {
+        if let Some(dir) = &self.profile_dir {
+            utils::set_profile_dir(dir.into());
+        } else if let Some(store) = &self.store {
+            utils::set_profile_dir(Config::load()?.get_store_dir(store)?);
+        }
+
+        Ok(())
+    }
+
+    /// the store selected with `--profile-dir` or `--store`, otherwise every store
+    fn get_selected_stores(&self) -> AppResult<Vec<(String, PathBuf)>> {
+        if self.profile_dir.is_some() || self.store.is_some() {
+            return Ok(vec![(
+                self.store.clone().unwrap_or(DEFAULT_STORE.to_string()),
+                utils::get_profile_dir(),
+            )]);
+        }
+
+        Ok(Config::load()?.get_stores())
+    }
+
     pub fn run(&self) -> AppResult<()> {
         if self.diagnostic {
             DiagnosticReport::generate()?.print()?;
@@ -56,10 +457,54 @@ impl ClapApp {
                 description,
                 envs,
                 envs_file,
+                stdin,
+                hidden,
                 cipher_kind,
-                comments: add_comments,
-                expires: add_expires,
+                comments,
+                expires,
+                comment,
+                expire,
+                expires_in,
+                layout,
+                clear_comments,
+                kdf_memory,
+                kdf_iterations,
+                kdf_parallelism,
+                on_expired,
+                key_policy,
+                tags,
             } => {
+                let on_expired = parse_expired_policy(on_expired)?.unwrap_or_default();
+                let tags = tags::parse_tags(tags.as_deref().unwrap_or_default())?;
+                let key_policy = match key_policy {
+                    Some(policy) => policy
+                        .parse::<KeyPolicy>()
+                        .map_err(|e| AppError::Msg(e.to_string()))?,
+                    None => KeyPolicy::default(),
+                };
+                let annotations = EnvAnnotations::parse(
+                    *comments,
+                    *expires,
+                    comment,
+                    expire,
+                    expires_in,
+                    &[],
+                    &[],
+                )?;
+
+                if let Some(envs) = envs {
+                    annotations.check_keys(|key| {
+                        envs.iter().any(|env| env.split('=').next() == Some(key))
+                    })?;
+                }
+
+                let layout = match layout {
+                    Some(layout) => layout
+                        .parse::<ContentLayout>()
+                        .map_err(|e| AppError::Msg(e.to_string()))?,
+                    None => ContentLayout::default(),
+                };
+
                 let selected_cipher_kind = if let Some(kind) = cipher_kind {
                     kind.parse::<CipherKind>()
                         .map_err(|e| AppError::Msg(e.to_string()))?
@@ -72,71 +517,44 @@ impl ClapApp {
                     })?
                 };
 
-                let key = match selected_cipher_kind {
-                    CipherKind::GPG => {
-                        let available_keys = get_gpg_keys()?;
-
-                        if available_keys.is_empty() {
-                            return Err(AppError::Msg("No GPG keys found".to_string()));
-                        }
-
-                        if let Ok(env_key) = std::env::var("ENVIO_KEY") {
-                            if available_keys
-                                .iter()
-                                .any(|(_, fingerprint)| *fingerprint == env_key)
-                            {
-                                Some(env_key.into())
-                            } else {
-                                return Err(AppError::Msg(
-                                    "ENVIO_KEY does not match any available GPG fingerprint"
-                                        .to_string(),
-                                ));
-                            }
-                        } else {
-                            let labels: Vec<String> = available_keys
-                                .iter()
-                                .map(|(label, _)| label.clone())
-                                .collect();
-
-                            let selected_label =
-                                prompts::select_prompt(prompts::SelectPromptOptions {
-                                    title: "Select the GPG key you want to use for encryption:"
-                                        .to_string(),
-                                    options: labels,
-                                })?;
-
-                            let fingerprint = available_keys
-                                .into_iter()
-                                .find(|(label, _)| *label == selected_label)
-                                .map(|(_, fingerprint)| fingerprint)
-                                .unwrap();
-
-                            Some(fingerprint.into())
-                        }
-                    }
+                if layout == ContentLayout::PerEntry && selected_cipher_kind == CipherKind::NONE {
+                    return Err(AppError::Msg(
+                        "The per-entry layout requires an encrypting cipher".to_string(),
+                    ));
+                }
 
-                    CipherKind::PASSPHRASE | CipherKind::AGE => {
-                        Some(if let Ok(key) = std::env::var("ENVIO_KEY") {
-                            Zeroizing::new(key)
-                        } else {
-                            prompts::password_prompt(prompts::PasswordPromptOptions {
-                                title: "Enter your encryption key:".to_string(),
-                                help_message: Some(
-                                    "Remember this key, you will need it to decrypt your profile later"
-                                        .to_string(),
-                                ),
-                                min_length: Some(8),
-                                with_confirmation: true,
-                                confirmation_error_message: Some("The keys don't match".to_string()),
-                            })?
-                            .into()
-                        })
+                let kdf_params = if kdf_memory.is_some()
+                    || kdf_iterations.is_some()
+                    || kdf_parallelism.is_some()
+                {
+                    if selected_cipher_kind != CipherKind::PASSPHRASE {
+                        return Err(AppError::Msg(
+                            "Key derivation parameters can only be used with the passphrase cipher"
+                                .to_string(),
+                        ));
                     }
 
-                    _ => None,
+                    Some(build_kdf_params(
+                        KdfParams::default(),
+                        *kdf_memory,
+                        *kdf_iterations,
+                        *kdf_parallelism,
+                    )?)
+                } else {
+                    None
                 };
 
-                let cipher = create_cipher(selected_cipher_kind, key)?;
+                let key = get_new_cipher_key(selected_cipher_kind)?;
+
+                let mut cipher = create_cipher(selected_cipher_kind, key)?;
+
+                if let Some(params) = kdf_params {
+                    cipher
+                        .as_any_mut()
+                        .downcast_mut::<PASSPHRASE>()
+                        .expect("Failed to cast to PASSPHRASE")
+                        .set_params(params)?;
+                }
 
                 let mut envs_map;
 
@@ -152,7 +570,7 @@ impl ClapApp {
                     let mut buffer = String::new();
                     file.read_to_string(&mut buffer)?;
 
-                    envs_map = utils::parse_envs_from_string(&buffer)?;
+                    envs_map = utils::parse_envs_from_string(&buffer, key_policy)?;
 
                     let default_options = (0..envs_map.len()).collect::<Vec<usize>>();
                     let selected_keys = prompts::multi_select_prompt(prompts::MultiSelectPromptOptions {
@@ -164,58 +582,18 @@ impl ClapApp {
                     })?;
 
                     envs_map.retain(|env| selected_keys.contains(&env.key));
-                } else if envs.is_some() {
-                    envs_map = EnvMap::default();
-
-                    for env in envs.as_ref().unwrap() {
-                        if (*env).contains('=') {
-                            let mut parts = env.splitn(2, '=');
-
-                            if let Some(key) = parts.next() {
-                                if let Some(value) = parts.next() {
-                                    envs_map
-                                        .insert_from_key_value(key.to_string(), value.to_string());
-                                } else {
-                                    return Err(AppError::Msg(format!(
-                                        "Unable to parse value for key '{}'",
-                                        key
-                                    )));
-                                }
-                            } else {
-                                return Err(AppError::Msg(format!(
-                                    "Unable to parse key-value pair from '{}'",
-                                    env
-                                )));
-                            }
-
-                            continue;
-                        }
-
-                        let value = prompts::text_prompt(prompts::TextPromptOptions {
-                            title: format!("Enter the value for {}:", env),
-                            default: None,
-                        })?;
-                        envs_map.insert_from_key_value(env.to_string(), value);
-                    }
+                } else if let Some(envs) = envs {
+                    envs_map = read_envs(envs, *stdin, *hidden, None, key_policy)?.into();
                 } else {
                     envs_map = EnvMap::default(); // the user created a profile without any envs
                 }
 
-                for env in envs_map.iter_mut() {
-                    if *add_comments {
-                        env.comment = Some(prompts::text_prompt(prompts::TextPromptOptions {
-                            title: format!("Enter a comment for '{}':", env.key),
-                            default: None,
-                        })?);
-                    }
+                envs_map.set_key_policy(key_policy);
 
-                    if *add_expires {
-                        env.expiration_date =
-                            Some(prompts::date_prompt(prompts::DatePromptOptions {
-                                title: format!("Select an expiration date for '{}':", env.key),
-                                default: Some(Local::now().date_naive()),
-                            })?);
-                    }
+                annotations.check_keys(|key| envs_map.contains_key(key))?;
+
+                for env in envs_map.iter_mut() {
+                    annotations.annotate_new(env)?;
                 }
 
                 ops::create_profile(
@@ -223,6 +601,12 @@ impl ClapApp {
                     description.clone(),
                     envs_map,
                     cipher,
+                    ops::ProfileOptions {
+                        layout,
+                        clear_comments: *clear_comments,
+                        on_expired,
+                        tags,
+                    },
                 )?;
 
                 success_msg!("Profile created");
@@ -231,73 +615,63 @@ impl ClapApp {
             Command::Set {
                 profile_name,
                 envs,
-                comments: add_comments,
-                expires: add_expires,
+                stdin,
+                hidden,
+                comments,
+                expires,
+                comment,
+                expire,
+                expires_in,
+                unset_comment,
+                unset_expiration,
             } => {
+                let annotations = EnvAnnotations::parse(
+                    *comments,
+                    *expires,
+                    comment,
+                    expire,
+                    expires_in,
+                    unset_comment,
+                    unset_expiration,
+                )?;
+
+                if envs.is_empty() && annotations.is_empty() {
+                    return Err(AppError::Msg(
+                        "No environment variables were provided".to_string(),
+                    ));
+                }
+
                 let mut profile =
                     get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
 
                 ops::check_expired_envs(&profile);
 
-                let mut set_envs = Vec::new();
-
-                for env in envs {
-                    if env.contains('=') {
-                        let mut parts = env.splitn(2, '=');
-
-                        if let Some(key) = parts.next() {
-                            if let Some(value) = parts.next() {
-                                set_envs
-                                    .push(Env::from_key_value(key.to_string(), value.to_string()));
-                            } else {
-                                return Err(AppError::Msg(format!(
-                                    "Unable to parse value for key '{}'",
-                                    key
-                                )));
-                            }
-                        } else {
-                            return Err(AppError::Msg(format!(
-                                "Unable to parse key-value pair from '{}'",
-                                env
-                            )));
-                        }
-
-                        continue;
-                    }
-
-                    let prompt = prompts::text_prompt(prompts::TextPromptOptions {
-                        title: format!(
-                            "Enter the {}value for {}:",
-                            if profile.envs.contains_key(env) {
-                                "new "
-                            } else {
-                                ""
-                            },
-                            env
-                        ),
-                        default: None,
-                    })?;
+                annotations.check_keys(|key| {
+                    profile.envs.contains_key(key)
+                        || envs.iter().any(|env| env.split('=').next() == Some(key))
+                })?;
 
-                    set_envs.push(Env::from_key_value(env.to_string(), prompt));
+                for env in profile.envs.iter_mut() {
+                    annotations.annotate_existing(env);
                 }
 
+                let set_envs = read_envs(
+                    envs,
+                    *stdin,
+                    *hidden,
+                    Some(&profile.envs),
+                    profile.metadata.key_policy,
+                )?;
+
                 for mut env in set_envs {
-                    if *add_comments {
-                        env.comment = Some(prompts::text_prompt(prompts::TextPromptOptions {
-                            title: format!("Enter a comment for '{}':", env.key),
-                            default: None,
-                        })?);
-                    }
+                    annotations.annotate_new(&mut env)?;
 
-                    if *add_expires {
-                        env.expiration_date =
-                            Some(prompts::date_prompt(prompts::DatePromptOptions {
-                                title: format!("Select an expiration date for '{}':", env.key),
-                                default: Some(Local::now().date_naive()),
-                            })?);
+                    if let Some(existing) = profile.envs.get(&env.key) {
+                        env.tags = existing.tags.clone();
+                        env.rotation = existing.rotation.clone();
                     }
 
-                    profile.envs.insert(env);
+                    profile.envs.insert(env)?;
                 }
 
                 profile.save()?;
@@ -318,10 +692,127 @@ impl ClapApp {
                 success_msg!("Changes applied");
             }
 
-            Command::Load { profile_name } => {
+            Command::Edit { profile_name } => {
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                let Some(envs) = ops::edit_envs(&profile)? else {
+                    warning_msg!("Discarded changes");
+                    return Ok(());
+                };
+
+                if !ops::print_env_changes(&profile.envs, &envs) {
+                    success_msg!("No changes");
+                    return Ok(());
+                }
+
+                profile.envs = envs;
+                profile.save()?;
+                success_msg!("Changes applied");
+            }
+
+            Command::Merge {
+                profile_name,
+                from,
+                strategy,
+                dry_run,
+            } => {
+                let strategy = strategy
+                    .parse::<ops::MergeStrategy>()
+                    .map_err(|e| AppError::Msg(e.to_string()))?;
+
+                // a file takes precedence over a profile of the same name
+                // keys from a file only have to be valid for the target, which is checked on merge
+                let source = if Path::new(from).is_file() {
+                    utils::parse_envs_from_string(
+                        &std::fs::read_to_string(from)?,
+                        KeyPolicy::Lenient,
+                    )?
+                } else if utils::get_profile_path(from).is_ok() {
+                    get_profile(
+                        utils::get_profile_path(from)?,
+                        Some(|| get_profile_key(from).unwrap_or_default()),
+                    )?
+                    .envs
+                } else {
+                    return Err(AppError::Msg(format!(
+                        "`{}` is neither a file nor a profile",
+                        from
+                    )));
+                };
+
+                if source.is_empty() {
+                    return Err(AppError::Msg(format!(
+                        "`{}` has no environment variables to merge",
+                        from
+                    )));
+                }
+
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                let plan = ops::plan_merge(&profile.envs, &source, strategy, *dry_run)?;
+
+                if *dry_run {
+                    ops::print_merge_plan(&plan);
+                    return Ok(());
+                }
+
+                ops::apply_merge(&mut profile.envs, &source, &plan)?;
+
+                let merged = plan
+                    .iter()
+                    .filter(|(_, action)| {
+                        matches!(action, ops::MergeAction::Add | ops::MergeAction::Overwrite)
+                    })
+                    .count();
+
+                if merged == 0 {
+                    success_msg!("No changes");
+                    return Ok(());
+                }
+
+                profile.save()?;
+                success_msg!("Merged {} environment variables", merged);
+            }
+
+            Command::Apply { profile_name, file } => {
+                let input = match file {
+                    Some(file) => std::fs::read_to_string(file)?,
+                    None => {
+                        let mut input = String::new();
+                        std::io::stdin().read_to_string(&mut input)?;
+                        input
+                    }
+                };
+
+                // the input is parsed before the profile is decrypted
+                let operations = batch::parse_operations(&input)?;
+
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                let envs = batch::apply_operations(&profile.envs, &operations)?;
+
+                if !ops::print_env_changes(&profile.envs, &envs) {
+                    success_msg!("No changes");
+                    return Ok(());
+                }
+
+                profile.envs = envs;
+                profile.save()?;
+                success_msg!("Applied {} operations", operations.len());
+            }
+
+            Command::Load {
+                profile_name,
+                on_expired,
+            } => {
+                let on_expired = parse_expired_policy(on_expired)?;
+
                 #[cfg(target_family = "unix")]
                 {
-                    ops::load_profile(profile_name)?;
+                    ops::load_profile(profile_name, on_expired)?;
 
                     let shell_config = utils::get_shell_config_path()?;
 
@@ -337,8 +828,9 @@ impl ClapApp {
 
                 #[cfg(target_family = "windows")]
                 {
-                    let profile =
+                    let mut profile =
                         get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+                    ops::apply_expired_policy(&mut profile, on_expired)?;
 
                     ops::load_profile(profile)?;
 
@@ -365,6 +857,12 @@ impl ClapApp {
 
             Command::Run {
                 profile_name,
+                keys,
+                on_expired,
+                schema,
+                no_check,
+                file_vars,
+                memfd,
                 command,
             } => {
                 if command.is_empty() {
@@ -374,24 +872,92 @@ impl ClapApp {
                 let program = &command[0];
                 let args = &command[1..];
 
-                let profile =
-                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
-                ops::check_expired_envs(&profile);
+                let file_vars = file_vars
+                    .as_deref()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|file_var| match file_var.split_once('=') {
+                        Some((var, key)) if !var.trim().is_empty() && !key.trim().is_empty() => {
+                            Ok((var.trim().to_string(), key.trim().to_string()))
+                        }
+                        _ => Err(AppError::Msg(format!(
+                            "Invalid file variable `{}`, expected VAR=KEY",
+                            file_var
+                        ))),
+                    })
+                    .collect::<AppResult<Vec<_>>>()?;
+
+                let profile_path = utils::get_profile_path(profile_name)?;
+
+                let keys = keys.as_ref().map(|keys| {
+                    let mut keys = keys.clone();
+                    keys.extend(file_vars.iter().map(|(_, key)| key.clone()));
+                    keys
+                });
+
+                // per-entry profiles only decrypt the values the command actually needs
+                let mut profile = match &keys {
+                    Some(keys) => get_partial_profile(profile_path, Some(get_userkey), keys)?,
+                    None => get_profile(profile_path, Some(get_userkey))?,
+                };
+                ops::apply_expired_policy(&mut profile, parse_expired_policy(on_expired)?)?;
+
+                if !*no_check
+                    && let Some((mut schema, source)) =
+                        schema::find_schema(schema.as_deref(), &profile.metadata.schema)?
+                {
+                    // keys that were not asked for are not loaded, so their rules do not apply
+                    if let Some(keys) = &keys {
+                        schema.retain(|key, _| keys.contains(key));
+                    }
+
+                    let violations = schema::validate(&schema, &profile.envs)?;
+
+                    if !violations.is_empty() {
+                        schema::print_violations(&violations);
+                        return Err(AppError::Msg(format!(
+                            "Refusing to run `{}`, the environment variables do not match the schema of {}. Pass --no-check to run it anyway",
+                            program, source
+                        )));
+                    }
+                }
+
+                let mut files = Vec::with_capacity(file_vars.len());
+                for (var, key) in &file_vars {
+                    let env = profile
+                        .envs
+                        .get(key)
+                        .ok_or_else(|| envio::error::Error::EnvDoesNotExist(key.clone()))?;
+
+                    files.push((var, SecretFile::create(key, env.value.as_bytes(), *memfd)?));
+                }
+
+                // values handed over as files are not passed in the environment as well
+                for (_, key) in &file_vars {
+                    if profile.envs.contains_key(key) {
+                        profile.envs.remove(key)?;
+                    }
+                }
+
+                let mut envs: IndexMap<String, String> = profile.envs.into();
+                for (var, file) in &files {
+                    envs.insert(var.to_string(), file.path().to_string_lossy().into_owned());
+                }
 
                 let mut cmd = std::process::Command::new(program)
-                    .envs::<IndexMap<String, String>, _, _>(profile.envs.into())
+                    .envs(envs)
                     .args(args)
                     .stdout(std::process::Stdio::inherit())
                     .stderr(std::process::Stdio::inherit())
                     .spawn()
                     .expect("Failed to execute command");
 
-                let status = match cmd.wait() {
-                    Ok(s) => s,
-                    Err(e) => {
-                        return Err(AppError::Msg(format!("Failed to execute command: {}", e)));
-                    }
-                };
+                let status = ops::wait_child(&mut cmd)
+                    .map_err(|e| AppError::Msg(format!("Failed to execute command: {}", e)));
+
+                // exiting below skips destructors, so the files are removed first
+                drop(files);
+                let status = status?;
 
                 match status.code() {
                     Some(code) => std::process::exit(code),
@@ -403,31 +969,736 @@ impl ClapApp {
                 }
             }
 
-            Command::Delete { profile_name } => {
-                ops::delete_profile(profile_name)?;
-                success_msg!("Deleted profile");
-            }
+            Command::OnExpired {
+                profile_name,
+                policy,
+            } => {
+                let policy = policy
+                    .parse::<ExpiredPolicy>()
+                    .map_err(|e| AppError::Msg(e.to_string()))?;
 
-            Command::List { no_pretty_print } => {
-                ops::list_profiles(*no_pretty_print)?;
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                profile.metadata.on_expired = policy;
+                profile.save()?;
+
+                success_msg!(
+                    "Expired environment variables will now {}",
+                    match policy {
+                        ExpiredPolicy::Warn => "only be warned about",
+                        ExpiredPolicy::Skip => "be skipped",
+                        ExpiredPolicy::Fail => "cause the profile to fail to load",
+                    }
+                );
             }
 
-            Command::Show {
+            Command::KeyPolicy {
                 profile_name,
-                no_pretty_print,
+                policy,
+            } => {
+                let policy = policy
+                    .parse::<KeyPolicy>()
+                    .map_err(|e| AppError::Msg(e.to_string()))?;
+
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                profile.set_key_policy(policy)?;
+
+                success_msg!("Profile now uses the {} key policy", policy);
+
+                let invalid = profile
+                    .envs
+                    .keys()
+                    .filter(|key| policy.check(key).is_err())
+                    .count();
+
+                if invalid > 0 {
+                    warning_msg!(
+                        "{} existing environment variables do not follow it, run `envio lint {} --fix` to rename them",
+                        invalid,
+                        profile_name
+                    );
+                }
+            }
+
+            Command::Kdf {
+                profile_name,
+                memory,
+                iterations,
+                parallelism,
+            } => {
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                let cipher = profile
+                    .cipher
+                    .as_any_mut()
+                    .downcast_mut::<PASSPHRASE>()
+                    .ok_or_else(|| {
+                        AppError::Msg(
+                            "Key derivation parameters can only be used with the passphrase cipher"
+                                .to_string(),
+                        )
+                    })?;
+
+                let current = cipher.params();
+
+                if memory.is_none() && iterations.is_none() && parallelism.is_none() {
+                    println!("Memory: {} KiB", current.memory);
+                    println!("Iterations: {}", current.iterations);
+                    println!("Parallelism: {}", current.parallelism);
+                    return Ok(());
+                }
+
+                cipher.set_params(build_kdf_params(
+                    current,
+                    *memory,
+                    *iterations,
+                    *parallelism,
+                )?)?;
+
+                profile.rewrap_data_key()?;
+                profile.save()?;
+
+                success_msg!("Updated the key derivation parameters");
+            }
+
+            Command::Lint { profile_name, fix } => {
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                let issues = ops::lint_keys(&profile.envs);
+
+                if issues.is_empty() {
+                    success_msg!("All environment variable names are valid");
+                    return Ok(());
+                }
+
+                ops::print_lint_issues(&issues);
+
+                if !*fix {
+                    return Err(AppError::Msg(format!(
+                        "Found {} invalid environment variable names, run `envio lint {} --fix` to rename them",
+                        issues.len(),
+                        profile_name
+                    )));
+                }
+
+                ops::fix_keys(&mut profile.envs, &issues)?;
+                profile.save()?;
+
+                success_msg!("Renamed {} environment variables", issues.len());
+            }
+
+            Command::Tag {
+                profile_name,
+                tags,
+                env,
+                remove,
+            } => {
+                let added = tags::parse_tags(tags.as_deref().unwrap_or_default())?;
+                let removed = remove.as_deref().unwrap_or_default();
+
+                // the tags of a profile are stored in the clear and can be shown without the key
+                if env.is_none() && added.is_empty() && removed.is_empty() {
+                    ops::print_tags(&utils::get_profile_metadata(profile_name)?.tags);
+                    return Ok(());
+                }
+
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                let current = match env {
+                    Some(key) => {
+                        &mut profile
+                            .envs
+                            .get_mut(key)
+                            .ok_or_else(|| envio::error::Error::EnvDoesNotExist(key.clone()))?
+                            .tags
+                    }
+                    None => &mut profile.metadata.tags,
+                };
+
+                if added.is_empty() && removed.is_empty() {
+                    ops::print_tags(current);
+                    return Ok(());
+                }
+
+                for name in removed {
+                    if current.remove(name).is_none() {
+                        warning_msg!("Tag '{}' is not set", name);
+                    }
+                }
+
+                current.extend(added);
+                profile.save()?;
+
+                success_msg!("Tags updated");
+            }
+
+            Command::Schema {
+                profile_name,
+                set,
+                remove,
+            } => {
+                if set.is_none() && !*remove {
+                    let profile = utils::get_profile_metadata(profile_name)?;
+
+                    if profile.schema.is_empty() {
+                        println!("Profile `{}` has no schema", profile_name);
+                    } else {
+                        println!("{}", serde_json::to_string_pretty(&profile.schema)?);
+                    }
+
+                    return Ok(());
+                }
+
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                match set {
+                    Some(path) => {
+                        profile.metadata.schema = schema::read_schema_file(Path::new(path))?;
+                        profile.save()?;
+
+                        let violations = schema::validate(&profile.metadata.schema, &profile.envs)?;
+
+                        success_msg!(
+                            "Stored the schema with {} environment variable{}",
+                            profile.metadata.schema.len(),
+                            if profile.metadata.schema.len() == 1 {
+                                ""
+                            } else {
+                                "s"
+                            }
+                        );
+
+                        if !violations.is_empty() {
+                            warning_msg!("The profile does not match the schema yet:");
+                            schema::print_violations(&violations);
+                        }
+                    }
+                    None => {
+                        profile.metadata.schema.clear();
+                        profile.save()?;
+
+                        success_msg!("Removed the schema");
+                    }
+                }
+            }
+
+            Command::Check {
+                profile_name,
+                schema,
+            } => {
+                let profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                let Some((schema, source)) =
+                    schema::find_schema(schema.as_deref(), &profile.metadata.schema)?
+                else {
+                    return Err(AppError::Msg(format!(
+                        "Profile `{}` has no schema, store one with `envio schema {} --set <FILE>` or add {}",
+                        profile_name,
+                        profile_name,
+                        schema::PROJECT_SCHEMA_FILE
+                    )));
+                };
+
+                let violations = schema::validate(&schema, &profile.envs)?;
+
+                if !violations.is_empty() {
+                    schema::print_violations(&violations);
+                    return Err(AppError::Msg(format!(
+                        "Found {} problem{} checking against the schema of {}",
+                        violations.len(),
+                        if violations.len() == 1 { "" } else { "s" },
+                        source
+                    )));
+                }
+
+                success_msg!(
+                    "Profile `{}` matches the schema of {}",
+                    profile_name,
+                    source
+                );
+            }
+
+            Command::Generate {
+                profile_name,
+                key,
+                length,
+                charset,
+                chars,
+                words,
+                separator,
+                hex,
+                base64,
+                uuid,
+                force,
+            } => {
+                let generator = if *uuid {
+                    Generator::Uuid
+                } else if *hex {
+                    Generator::Hex(length.unwrap_or(generator::DEFAULT_LENGTH))
+                } else if *base64 {
+                    Generator::Base64(length.unwrap_or(generator::DEFAULT_LENGTH))
+                } else if *words {
+                    Generator::Words {
+                        count: length.unwrap_or(generator::DEFAULT_WORDS),
+                        separator: separator
+                            .clone()
+                            .unwrap_or_else(|| generator::DEFAULT_SEPARATOR.to_string()),
+                    }
+                } else {
+                    let charset = match (chars, charset) {
+                        (Some(chars), _) => chars.clone(),
+                        (None, Some(charset)) => charset
+                            .parse::<Charset>()
+                            .map_err(|e| AppError::Msg(e.to_string()))?
+                            .chars(),
+                        (None, None) => Charset::Alnum.chars(),
+                    };
+
+                    Generator::Chars {
+                        charset,
+                        length: length.unwrap_or(generator::DEFAULT_LENGTH),
+                    }
+                };
+
+                generator.check()?;
+
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                match profile.envs.get_mut(key) {
+                    Some(_) if !*force => {
+                        return Err(AppError::Msg(format!(
+                            "'{}' already exists, pass --force to replace its value",
+                            key
+                        )));
+                    }
+                    Some(env) => {
+                        ops::rotate_env(env, &ops::RotationSource::Generator(generator.clone()))?
+                    }
+                    None => profile
+                        .envs
+                        .insert(Env::from_key_value(key, generator.generate().as_str()))?,
+                }
+
+                profile.save()?;
+
+                success_msg!(
+                    "Generated a value for '{}' with {:.0} bits of entropy",
+                    key,
+                    generator.entropy()
+                );
+            }
+
+            Command::RotationPolicy {
+                profile_name,
+                key,
+                max_age,
+                generator,
+                command,
+                remove,
+            } => {
+                if let Some(max_age) = max_age {
+                    utils::add_duration(Local::now().date_naive(), max_age)?;
+                }
+
+                if let Some(generator) = generator {
+                    generator.parse::<Generator>()?;
+                }
+
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                let env = profile
+                    .envs
+                    .get_mut(key)
+                    .ok_or_else(|| envio::error::Error::EnvDoesNotExist(key.clone()))?;
+
+                if *remove {
+                    if env.rotation.take().is_none() {
+                        warning_msg!("'{}' has no rotation policy", key);
+                        return Ok(());
+                    }
+
+                    profile.save()?;
+                    success_msg!("Removed the rotation policy of '{}'", key);
+                    return Ok(());
+                }
+
+                if max_age.is_none() && generator.is_none() && command.is_none() {
+                    return ops::print_rotation_policy(env);
+                }
+
+                let mut policy = match (env.rotation.take(), max_age) {
+                    (Some(policy), _) => policy,
+                    (None, Some(max_age)) => RotationPolicy {
+                        max_age: max_age.clone(),
+                        generator: None,
+                        command: None,
+                    },
+                    (None, None) => {
+                        return Err(AppError::Msg(format!(
+                            "'{}' has no rotation policy yet, pass --max-age to create one",
+                            key
+                        )));
+                    }
+                };
+
+                if let Some(max_age) = max_age {
+                    policy.max_age = max_age.clone();
+                }
+
+                if generator.is_some() || command.is_some() {
+                    policy.generator = generator.clone();
+                    policy.command = command.clone();
+                }
+
+                env.rotation = Some(policy);
+
+                // the expiration date follows the policy so `expiring` reports upcoming rotations
+                let due = ops::rotation_due_date(env)?;
+                let replaced = env.expiration_date.filter(|date| Some(*date) != due);
+                env.expiration_date = due;
+
+                profile.save()?;
+
+                if let Some(replaced) = replaced {
+                    warning_msg!(
+                        "The expiration date of '{}' was {} and now follows the rotation policy",
+                        key,
+                        replaced
+                    );
+                }
+
+                if let Some(due) = due {
+                    success_msg!(
+                        "Rotation policy set, '{}' is due for rotation on {}",
+                        key,
+                        due
+                    );
+                }
+            }
+
+            Command::Rotate {
+                profile_name,
+                key,
+                generator,
+                command,
+                due,
+                list,
+            } => {
+                let source =
+                    ops::RotationSource::from_flags(generator.as_deref(), command.as_deref())?;
+
+                if *due {
+                    let profiles = match profile_name {
+                        Some(name) => vec![(name.clone(), utils::get_profile_path(name)?)],
+                        None => {
+                            let stores = self.get_selected_stores()?;
+                            let show_store = stores.len() > 1;
+                            let mut profiles = Vec::new();
+
+                            for (store, dir) in &stores {
+                                for name in utils::get_profile_names(dir)? {
+                                    let label = if show_store {
+                                        format!("{}:{}", store, name)
+                                    } else {
+                                        name.to_string()
+                                    };
+
+                                    profiles.push((label, name.file_path(dir)));
+                                }
+                            }
+
+                            profiles
+                        }
+                    };
+
+                    return ops::rotate_due(&profiles, source.as_ref(), *list, get_profile_key);
+                }
+
+                let (Some(profile_name), Some(key)) = (profile_name, key) else {
+                    return Err(AppError::Msg(
+                        "A profile and environment variable are required".to_string(),
+                    ));
+                };
+
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                let env = profile
+                    .envs
+                    .get_mut(key)
+                    .ok_or_else(|| envio::error::Error::EnvDoesNotExist(key.clone()))?;
+
+                let policy_source = match &env.rotation {
+                    Some(policy) => ops::RotationSource::from_policy(policy)?,
+                    None => None,
+                };
+
+                let source = source
+                    .or(policy_source)
+                    .unwrap_or_else(|| ops::RotationSource::Generator(Generator::default()));
+
+                ops::rotate_env(env, &source)?;
+                let expiration_date = env.expiration_date.filter(|_| env.rotation.is_some());
+
+                profile.save()?;
+
+                success_msg!("Rotated '{}'", key);
+
+                if let Some(date) = expiration_date {
+                    println!("The next rotation is due on {}", date);
+                }
+            }
+
+            Command::Layout {
+                profile_name,
+                layout,
+                clear_comments,
+            } => {
+                let layout = layout
+                    .parse::<ContentLayout>()
+                    .map_err(|e| AppError::Msg(e.to_string()))?;
+
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                profile.metadata.layout = layout;
+                profile.metadata.clear_comments =
+                    layout == ContentLayout::PerEntry && *clear_comments;
+                profile.save()?;
+
+                success_msg!("Profile now uses the {} layout", layout);
+            }
+
+            Command::Delete {
+                profile_name,
+                force,
+            } => {
+                utils::get_profile_path(profile_name)?;
+
+                if !*force
+                    && !prompts::confirm_prompt(prompts::ConfirmPromptOptions {
+                        title: format!("Delete profile '{}'?", profile_name),
+                        default: false,
+                    })?
+                {
+                    return Ok(());
+                }
+
+                ops::delete_profile(profile_name)?;
+                success_msg!(
+                    "Moved profile to the trash, run `envio trash restore {}` to restore it",
+                    profile_name
+                );
+            }
+
+            Command::Trash { command } => {
+                let mut config = Config::load()?;
+                trash::purge(config.get_trash_retention_days())?;
+
+                match command {
+                    TrashCommand::List => ops::list_trash(&trash::list()?),
+
+                    TrashCommand::Restore { id } => {
+                        let entry = trash::find(id)?;
+                        trash::restore(&entry)?;
+
+                        success_msg!("Restored profile '{}'", entry.name);
+                    }
+
+                    TrashCommand::Empty { force } => {
+                        let entries = trash::list()?;
+
+                        if entries.is_empty() {
+                            println!("The trash is empty");
+                            return Ok(());
+                        }
+
+                        if !*force
+                            && !prompts::confirm_prompt(prompts::ConfirmPromptOptions {
+                                title: format!(
+                                    "Permanently delete {} profiles from the trash?",
+                                    entries.len()
+                                ),
+                                default: false,
+                            })?
+                        {
+                            return Ok(());
+                        }
+
+                        for entry in &entries {
+                            trash::remove(entry)?;
+                        }
+
+                        success_msg!("Emptied the trash");
+                    }
+
+                    TrashCommand::Retention { days } => match days {
+                        Some(days) => {
+                            config.trash_retention_days = Some(*days);
+                            config.save()?;
+
+                            success_msg!("Deleted profiles are now kept for {} days", days);
+                        }
+                        None => println!("{}", config.get_trash_retention_days()),
+                    },
+                }
+            }
+
+            Command::Rename {
+                profile_name,
+                new_name,
+            } => {
+                let file_path = utils::build_profile_path(new_name)?;
+                if file_path.exists() {
+                    return Err(AppError::ProfileExists(new_name.clone()));
+                }
+
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                profile.rename(new_name.clone(), file_path)?;
+                success_msg!("Renamed profile to `{}`", new_name);
+            }
+
+            Command::Describe {
+                profile_name,
+                description,
+                remove,
+            } => {
+                let description = description
+                    .as_ref()
+                    .map(|d| d.trim().to_string())
+                    .filter(|d| !d.is_empty() && !*remove);
+
+                let mut profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                profile.set_description(description)?;
+                success_msg!("Updated description");
+            }
+
+            Command::Copy {
+                profile_name,
+                new_name,
+                cipher_kind,
+            } => {
+                let file_path = utils::build_profile_path(new_name)?;
+                if file_path.exists() {
+                    return Err(AppError::ProfileExists(new_name.clone()));
+                }
+
+                let profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                let cipher = match cipher_kind {
+                    Some(kind) => {
+                        let kind = kind
+                            .parse::<CipherKind>()
+                            .map_err(|e| AppError::Msg(e.to_string()))?;
+
+                        Some(create_cipher(kind, get_new_cipher_key(kind)?)?)
+                    }
+                    None => None,
+                };
+
+                profile.copy(new_name.clone(), file_path, cipher)?;
+                success_msg!("Copied profile to `{}`", new_name);
+            }
+
+            Command::List {
+                pattern,
+                tags,
+                no_pretty_print,
+                tree,
+            } => {
+                let tags = tags.as_deref().map(TagFilter::new).transpose()?;
+
+                ops::list_profiles(
+                    &self.get_selected_stores()?,
+                    pattern.as_deref(),
+                    tags.as_ref(),
+                    *no_pretty_print,
+                    *tree || pattern.as_ref().is_some_and(|p| p.ends_with('/')),
+                )?;
+            }
+
+            Command::Expiring {
+                pattern,
+                within,
+                json,
+            } => {
+                let cutoff = utils::add_duration(Local::now().date_naive(), within)?;
+                let stores = self.get_selected_stores()?;
+
+                let report =
+                    ops::find_expiring_envs(&stores, pattern.as_deref(), cutoff, get_profile_key)?;
+
+                if *json {
+                    println!("{}", serde_json::to_string_pretty(&report)?);
+                } else {
+                    ops::print_expiration_report(&report, stores.len() > 1, within);
+                }
+
+                if report.envs.iter().any(|env| env.is_expired()) {
+                    std::process::exit(EXPIRED_EXIT_CODE);
+                } else if !report.envs.is_empty() {
+                    std::process::exit(EXPIRING_EXIT_CODE);
+                }
+            }
+
+            Command::Show {
+                profile_name,
+                no_pretty_print,
+                quote,
                 show_comments,
                 show_expiration,
+                show_tags,
+                show_history_columns,
+                tags,
+                on_expired,
             } => {
-                let profile =
+                let tags = tags.as_deref().map(TagFilter::new).transpose()?;
+
+                let mut profile =
                     get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
-                ops::check_expired_envs(&profile);
+
+                if let Some(tags) = tags {
+                    profile.envs.retain(|env| tags.matches(&env.tags));
+                }
+
+                // only viewing the profile does not enforce its policy unless asked to
+                match parse_expired_policy(on_expired)? {
+                    Some(policy) => ops::apply_expired_policy(&mut profile, Some(policy))?,
+                    None => ops::check_expired_envs(&profile),
+                }
 
                 if *no_pretty_print {
+                    // one line per variable, which is what the script written by `load` reads
                     for env in profile.envs {
-                        println!("{}={}", env.key, env.value);
+                        if *quote && dotenv::needs_quotes(&env.value) {
+                            println!("{}={}", env.key, dotenv::quote_value(&env.value));
+                        } else {
+                            println!("{}={}", env.key, env.value);
+                        }
                     }
                 } else {
-                    ops::list_envs(&profile, *show_comments, *show_expiration);
+                    ops::list_envs(
+                        &profile,
+                        *show_comments,
+                        *show_expiration,
+                        *show_tags,
+                        *show_history_columns,
+                    );
                 }
             }
 
@@ -435,11 +1706,12 @@ impl ClapApp {
                 profile_name,
                 output_file_path,
                 keys,
+                on_expired,
             } => {
-                let profile =
+                let mut profile =
                     get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
 
-                ops::check_expired_envs(&profile);
+                ops::apply_expired_policy(&mut profile, parse_expired_policy(on_expired)?)?;
 
                 let envs_selected = if keys.is_some() {
                     let keys_vec = keys.as_ref().unwrap();
@@ -467,6 +1739,120 @@ impl ClapApp {
                 success_msg!("Exported envs to {}", output_file_path);
             }
 
+            Command::Render {
+                profile_name,
+                template,
+                output_file_path,
+                strict,
+                on_expired,
+            } => {
+                let source = std::fs::read_to_string(template)
+                    .map_err(|e| AppError::Msg(format!("Failed to read `{}`: {}", template, e)))?;
+                let parsed = render::Template::parse(&source)
+                    .map_err(|e| AppError::Msg(format!("{}: {}", template, e)))?;
+
+                // per-entry profiles only decrypt the values the template uses
+                let keys: Vec<String> = parsed.keys().into_iter().collect();
+                let mut profile = get_partial_profile(
+                    utils::get_profile_path(profile_name)?,
+                    Some(get_userkey),
+                    &keys,
+                )?;
+                ops::apply_expired_policy(&mut profile, parse_expired_policy(on_expired)?)?;
+
+                let (output, missing) = parsed.render(&profile.envs, *strict)?;
+
+                for key in &missing {
+                    warning_msg!(
+                        "`{}` is not in the profile, its placeholder was left empty",
+                        key
+                    );
+                }
+
+                render::write_private(Path::new(output_file_path), &output)?;
+
+                success_msg!("Rendered {} to {}", template, output_file_path);
+            }
+
+            Command::Example {
+                profile_name,
+                output_file_path,
+                markdown,
+            } => {
+                let profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                let schema =
+                    schema::find_schema(None, &profile.metadata.schema)?.map(|(schema, _)| schema);
+
+                let content = if *markdown {
+                    example::write_markdown(&profile, schema.as_ref())
+                } else {
+                    example::write_dotenv(&profile, schema.as_ref())
+                };
+
+                match output_file_path {
+                    Some(path) => {
+                        std::fs::write(path, content)?;
+                        success_msg!("Wrote the keys of `{}` to {}", profile_name, path);
+                    }
+                    None => print!("{}", content),
+                }
+            }
+
+            Command::Verify {
+                profile_name,
+                against,
+            } => {
+                let profile =
+                    get_profile(utils::get_profile_path(profile_name)?, Some(get_userkey))?;
+
+                let documented = example::read_documented_keys(Path::new(against))?;
+
+                let missing: Vec<&String> = documented
+                    .iter()
+                    .filter(|key| !profile.envs.contains_key(key))
+                    .collect();
+                let undocumented: Vec<&String> = profile
+                    .envs
+                    .keys()
+                    .filter(|key| !documented.contains(*key))
+                    .collect();
+
+                for key in &missing {
+                    error_msg!(
+                        "`{}` is documented in {} but missing from the profile",
+                        key,
+                        against
+                    );
+                }
+
+                for key in &undocumented {
+                    error_msg!(
+                        "`{}` is in the profile but not documented in {}",
+                        key,
+                        against
+                    );
+                }
+
+                let differences = missing.len() + undocumented.len();
+                if differences > 0 {
+                    return Err(AppError::Msg(format!(
+                        "Profile `{}` and {} differ by {} key{}",
+                        profile_name,
+                        against,
+                        differences,
+                        if differences == 1 { "" } else { "s" }
+                    )));
+                }
+
+                success_msg!(
+                    "Profile `{}` has exactly the keys documented in {}",
+                    profile_name,
+                    against
+                );
+            }
+
             Command::Import {
                 source,
                 profile_name,
@@ -491,7 +1877,7 @@ impl ClapApp {
 
                 success_msg!("Imported profile");
 
-                let location = utils::build_profile_path(&profile_name);
+                let location = utils::build_profile_path(&profile_name)?;
 
                 let mut serialized_profile: SerializedProfile =
                     envio::utils::get_serialized_profile(&location)?;
@@ -499,7 +1885,61 @@ impl ClapApp {
                 serialized_profile.metadata.name = profile_name;
                 serialized_profile.metadata.file_path = location.clone();
 
-                envio::utils::save_serialized_profile(&location, serialized_profile)?;
+                open_store(utils::get_profile_dir()).save_profile(&location, serialized_profile)?;
+            }
+
+            Command::Store { command } => match command {
+                StoreCommand::Add {
+                    store_name,
+                    path,
+                    git,
+                    remote,
+                } => {
+                    let path = std::path::absolute(path)?;
+
+                    let mut config = Config::load()?;
+                    config.add_store(store_name, path.clone())?;
+
+                    if *git || remote.is_some() {
+                        GitStore::init(path, remote.as_deref())?;
+                    } else {
+                        std::fs::create_dir_all(&path)?;
+                    }
+
+                    config.save()?;
+
+                    success_msg!("Added store '{}'", store_name);
+                }
+
+                StoreCommand::Remove { store_name } => {
+                    let mut config = Config::load()?;
+                    config.remove_store(store_name)?;
+                    config.save()?;
+
+                    success_msg!("Removed store '{}'", store_name);
+                }
+
+                StoreCommand::List => ops::list_stores(&Config::load()?.get_stores())?,
+            },
+
+            Command::Sync { keep } => {
+                let resolution = match keep.as_deref() {
+                    Some("local") => Some(ConflictResolution::Local),
+                    Some("remote") => Some(ConflictResolution::Remote),
+                    _ => None,
+                };
+
+                match open_store(utils::get_profile_dir()).sync(resolution) {
+                    Err(envio::error::Error::SyncConflict(profiles)) => {
+                        return Err(AppError::Msg(format!(
+                            "Profiles changed both locally and remotely: {}\nNothing was merged, rerun with `--keep local` or `--keep remote` to choose which version to keep",
+                            profiles.join(", ")
+                        )));
+                    }
+                    result => result?,
+                }
+
+                success_msg!("Store synced");
             }
 
             Command::Tui => {
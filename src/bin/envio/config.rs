@@ -0,0 +1,96 @@
+use std::path::PathBuf;
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{AppError, AppResult},
+    utils::{get_configdir, get_default_profile_dir},
+};
+
+pub const DEFAULT_STORE: &str = "default";
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StoreConfig {
+    pub path: PathBuf,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Config {
+    #[serde(default)]
+    pub stores: IndexMap<String, StoreConfig>,
+    /// days deleted profiles are kept in the trash for, 0 keeps them until the trash is emptied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trash_retention_days: Option<u32>,
+}
+
+impl Config {
+    pub fn path() -> PathBuf {
+        get_configdir().join("config.json")
+    }
+
+    pub fn load() -> AppResult<Config> {
+        let path = Self::path();
+
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    pub fn save(&self) -> AppResult<()> {
+        std::fs::create_dir_all(get_configdir())?;
+        std::fs::write(Self::path(), serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+
+    pub fn get_trash_retention_days(&self) -> u32 {
+        self.trash_retention_days
+            .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS)
+    }
+
+    pub fn get_store_dir(&self, name: &str) -> AppResult<PathBuf> {
+        if name == DEFAULT_STORE {
+            return Ok(get_default_profile_dir());
+        }
+
+        self.stores
+            .get(name)
+            .map(|store| store.path.clone())
+            .ok_or_else(|| AppError::StoreDoesNotExist(name.to_string()))
+    }
+
+    /// returns every store including the default one, in the order they were added
+    pub fn get_stores(&self) -> Vec<(String, PathBuf)> {
+        let mut stores = vec![(DEFAULT_STORE.to_string(), get_default_profile_dir())];
+
+        stores.extend(
+            self.stores
+                .iter()
+                .map(|(name, store)| (name.clone(), store.path.clone())),
+        );
+
+        stores
+    }
+
+    pub fn add_store(&mut self, name: &str, path: PathBuf) -> AppResult<()> {
+        if name == DEFAULT_STORE || self.stores.contains_key(name) {
+            return Err(AppError::StoreExists(name.to_string()));
+        }
+
+        self.stores.insert(name.to_string(), StoreConfig { path });
+
+        Ok(())
+    }
+
+    pub fn remove_store(&mut self, name: &str) -> AppResult<()> {
+        if self.stores.shift_remove(name).is_none() {
+            return Err(AppError::StoreDoesNotExist(name.to_string()));
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,8 @@
 pub use git_version::git_version;
 
+use crate::config::Config;
 use crate::error::AppResult;
-use crate::utils::{get_configdir, get_profile_dir};
+use crate::utils::{get_configdir, get_profile_dir, get_profile_names};
 
 struct Code {
     language: Option<String>,
@@ -168,11 +169,22 @@ fn get_config_info() -> Vec<DiagnosticEntry> {
         }
     )));
 
+    let config_file = Config::path();
+    info.push(DiagnosticEntry::Text(format!(
+        "Config file: {} ({})",
+        config_file.display(),
+        if config_file.exists() {
+            "exists"
+        } else {
+            "does not exist"
+        }
+    )));
+
     let profile_dir = get_profile_dir();
     if profile_dir.exists() {
-        match std::fs::read_dir(&profile_dir) {
-            Ok(entries) => {
-                let profile_count = entries.count();
+        match get_profile_names(&profile_dir) {
+            Ok(profiles) => {
+                let profile_count = profiles.len();
                 info.push(DiagnosticEntry::Text(format!(
                     "Profile directory: {} ({} profiles)",
                     profile_dir.display(),
@@ -0,0 +1,314 @@
+use chrono::NaiveDate;
+use envio::{Env, EnvMap, KeyPolicy};
+
+use crate::{
+    error::{AppError, AppResult},
+    tags::{format_tags, parse_tag_list},
+};
+
+const COMMENT_ANNOTATION: &str = "#@ comment:";
+const EXPIRES_ANNOTATION: &str = "#@ expires:";
+const TAGS_ANNOTATION: &str = "#@ tags:";
+
+/// wraps a value in double quotes, escaping everything that would break a single line
+pub fn quote_value(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\r', "\\r")
+            .replace('\n', "\\n")
+    )
+}
+
+/// whether a value has to be written with `quote_value` to survive being read back line by line.
+/// values starting with a double quote would otherwise be taken for quoted ones. this is the one
+/// rule for everything `envio` writes in this format, from `show --quote` to `export` and `edit`
+pub fn needs_quotes(value: &str) -> bool {
+    value.contains(['\n', '\r']) || value.starts_with('"')
+}
+
+fn unquote_value(quoted: &str, line_number: usize) -> AppResult<String> {
+    let invalid = |reason: &str| AppError::Msg(format!("Line {}: {}", line_number, reason));
+
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+
+    loop {
+        match chars.next() {
+            Some('"') => break,
+            Some('\\') => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('r') => value.push('\r'),
+                Some('t') => value.push('\t'),
+                Some(c @ ('\\' | '"')) => value.push(c),
+                _ => return Err(invalid("invalid escape sequence in quoted value")),
+            },
+            Some(c) => value.push(c),
+            None => return Err(invalid("quoted value is missing its closing quote")),
+        }
+    }
+
+    if !chars.as_str().trim().is_empty() {
+        return Err(invalid("unexpected characters after quoted value"));
+    }
+
+    Ok(value)
+}
+
+/// a value as written after `KEY=`, either verbatim or in double quotes
+pub fn parse_value(value: &str, line_number: usize) -> AppResult<String> {
+    match value.strip_prefix('"') {
+        Some(quoted) => unquote_value(quoted, line_number),
+        None => Ok(value.to_string()),
+    }
+}
+
+/// renders environment variables with their comments, expiration dates and tags as annotations
+pub fn write_annotated(envs: &EnvMap, header: &str) -> String {
+    let mut buffer = String::new();
+
+    for line in header.lines() {
+        buffer.push_str(&format!("# {}\n", line));
+    }
+
+    for env in envs {
+        buffer.push('\n');
+
+        // comments are quoted by the same rule as values so they stay on their line
+        if let Some(comment) = &env.comment {
+            if needs_quotes(comment) {
+                buffer.push_str(&format!(
+                    "{} {}\n",
+                    COMMENT_ANNOTATION,
+                    quote_value(comment)
+                ));
+            } else {
+                buffer.push_str(&format!("{} {}\n", COMMENT_ANNOTATION, comment));
+            }
+        }
+
+        if let Some(date) = env.expiration_date {
+            buffer.push_str(&format!("{} {}\n", EXPIRES_ANNOTATION, date));
+        }
+
+        if !env.tags.is_empty() {
+            buffer.push_str(&format!("{} {}\n", TAGS_ANNOTATION, format_tags(&env.tags)));
+        }
+
+        if needs_quotes(&env.value) {
+            buffer.push_str(&format!("{}={}\n", env.key, quote_value(&env.value)));
+        } else {
+            buffer.push_str(&format!("{}={}\n", env.key, env.value));
+        }
+    }
+
+    buffer
+}
+
+/// parses a buffer written by `write_annotated`, annotations apply to the next variable. keys
+/// that are not in `existing` have to be valid under its key policy
+pub fn parse_annotated(buffer: &str, existing: &EnvMap) -> AppResult<EnvMap> {
+    let mut envs = EnvMap::default();
+    envs.set_key_policy(KeyPolicy::Lenient);
+    let mut comment = None;
+    let mut expiration_date = None;
+    let mut tags = None;
+
+    for (index, line) in buffer.lines().enumerate() {
+        let line_number = index + 1;
+        let trimmed = line.trim();
+
+        if let Some(text) = trimmed.strip_prefix(COMMENT_ANNOTATION) {
+            comment = Some(parse_value(text.trim(), line_number)?).filter(|c| !c.is_empty());
+            continue;
+        }
+
+        if let Some(date) = trimmed.strip_prefix(EXPIRES_ANNOTATION) {
+            expiration_date = Some(NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").map_err(
+                |_| {
+                    AppError::Msg(format!(
+                        "Line {}: invalid expiration date `{}`, expected YYYY-MM-DD",
+                        line_number,
+                        date.trim()
+                    ))
+                },
+            )?);
+            continue;
+        }
+
+        if let Some(list) = trimmed.strip_prefix(TAGS_ANNOTATION) {
+            tags = Some(
+                parse_tag_list(list)
+                    .map_err(|e| AppError::Msg(format!("Line {}: {}", line_number, e)))?,
+            );
+            continue;
+        }
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(AppError::Msg(format!(
+                "Line {}: expected KEY=VALUE",
+                line_number
+            )));
+        };
+
+        let key = key.trim();
+        if envs.contains_key(key) {
+            return Err(AppError::Msg(format!(
+                "Line {}: `{}` is defined more than once",
+                line_number, key
+            )));
+        }
+
+        let value = parse_value(value, line_number)?;
+
+        let mut env = Env::new(key, value, comment.take(), expiration_date.take());
+        env.tags = tags.take().unwrap_or_default();
+
+        if !existing.contains_key(key) {
+            existing
+                .key_policy()
+                .check(key)
+                .map_err(|e| AppError::Msg(format!("Line {}: {}", line_number, e)))?;
+        }
+
+        envs.insert(env)
+            .map_err(|e| AppError::Msg(format!("Line {}: {}", line_number, e)))?;
+    }
+
+    envs.set_key_policy(existing.key_policy());
+
+    Ok(envs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn envs() -> EnvMap {
+        let mut plain = Env::new(
+            "PLAIN",
+            "a=b c",
+            Some("with a comment".to_string()),
+            NaiveDate::from_ymd_opt(2030, 1, 31),
+        );
+        plain.tags = parse_tag_list("owner=platform, critical").unwrap();
+
+        EnvMap::from(vec![
+            plain,
+            Env::new(
+                "PEM",
+                "-----BEGIN KEY-----\r\nabc\n-----END KEY-----\n",
+                Some("first line\nsecond line".to_string()),
+                None,
+            ),
+            Env::from_key_value("PADDED", "  spaces  "),
+            Env::new(
+                "QUOTED",
+                "\"quoted\" \\ back\\slash",
+                Some("\"quoted\" comment".to_string()),
+                None,
+            ),
+            Env::from_key_value("EMPTY", ""),
+        ])
+    }
+
+    #[test]
+    fn quotes_values() {
+        assert_eq!(quote_value("a\"b\\c\r\nd"), r#""a\"b\\c\r\nd""#);
+    }
+
+    #[test]
+    fn parses_what_it_wrote() {
+        let buffer = write_annotated(&envs(), "header\nsecond line");
+        let parsed = parse_annotated(&buffer, &envs()).unwrap();
+
+        assert!(buffer.starts_with("# header\n# second line\n"));
+        assert_eq!(parsed.len(), envs().len());
+
+        for env in &envs() {
+            let parsed = parsed.get(&env.key).unwrap();
+            assert_eq!(parsed.value, env.value, "{}", env.key);
+            assert_eq!(parsed.comment, env.comment, "{}", env.key);
+            assert_eq!(parsed.expiration_date, env.expiration_date, "{}", env.key);
+            assert_eq!(parsed.tags, env.tags, "{}", env.key);
+        }
+    }
+
+    #[test]
+    fn keeps_every_value_on_one_line() {
+        let buffer = write_annotated(&envs(), "");
+
+        assert!(buffer.contains(r#"PEM="-----BEGIN KEY-----\r\nabc\n-----END KEY-----\n""#));
+        assert!(buffer.contains("PLAIN=a=b c\n"));
+        assert!(buffer.contains("PADDED=  spaces  \n"));
+        assert!(buffer.contains("#@ comment: \"first line\\nsecond line\"\n"));
+        assert!(buffer.contains(r#"#@ comment: "\"quoted\" comment""#));
+        assert!(buffer.contains("#@ comment: with a comment\n"));
+        assert!(buffer.contains(r#"QUOTED="\"quoted\" \\ back\\slash""#));
+    }
+
+    #[test]
+    fn annotations_apply_to_the_next_variable_only() {
+        let parsed = parse_annotated("#@ comment: first\nA=1\nB=2\n", &EnvMap::default()).unwrap();
+
+        assert_eq!(parsed.get("A").unwrap().comment.as_deref(), Some("first"));
+        assert_eq!(parsed.get("B").unwrap().comment, None);
+    }
+
+    #[test]
+    fn accepts_keys_allowed_by_the_key_policy() {
+        let mut existing = EnvMap::default();
+        existing.set_key_policy(KeyPolicy::Lenient);
+        let parsed = parse_annotated("my-key=1\n", &existing).unwrap();
+
+        assert_eq!(parsed.get("my-key").unwrap().value, "1");
+        assert_eq!(parsed.key_policy(), KeyPolicy::Lenient);
+    }
+
+    #[test]
+    fn keeps_existing_keys_the_key_policy_does_not_allow() {
+        let existing = EnvMap::from(vec![Env::from_key_value("old-key", "1")]);
+        let parsed = parse_annotated("old-key=2\n", &existing).unwrap();
+
+        assert_eq!(parsed.get("old-key").unwrap().value, "2");
+        assert_eq!(parsed.key_policy(), KeyPolicy::Posix);
+    }
+
+    #[test]
+    fn reports_invalid_lines() {
+        for (buffer, error) in [
+            ("A=1\nnot a variable\n", "Line 2: expected KEY=VALUE"),
+            ("A=1\nA=2\n", "Line 2: `A` is defined more than once"),
+            (
+                "A=\"open\n",
+                "Line 1: quoted value is missing its closing quote",
+            ),
+            (
+                "A=\"\\x\"\n",
+                "Line 1: invalid escape sequence in quoted value",
+            ),
+            (
+                "A=\"a\" b\n",
+                "Line 1: unexpected characters after quoted value",
+            ),
+            ("#@ expires: soon\nA=1\n", "Line 1: invalid expiration date"),
+            (
+                "#@ comment: \"open\nA=1\n",
+                "Line 1: quoted value is missing its closing quote",
+            ),
+            ("A=1\nmy-key=2\n", "Line 2: `my-key` is not a valid"),
+        ] {
+            let error_message = parse_annotated(buffer, &EnvMap::default())
+                .err()
+                .unwrap()
+                .to_string();
+            assert!(error_message.contains(error), "{}", error_message);
+        }
+    }
+}
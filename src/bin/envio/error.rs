@@ -12,9 +12,18 @@ pub enum AppError {
     #[error("Profile `{0}` already exists")]
     ProfileExists(String),
 
+    #[error("`{0}` is not a valid profile name, {1}")]
+    InvalidProfileName(String, String),
+
     #[error("Profile `{0}` is empty")]
     EmptyProfile(String),
 
+    #[error("Store `{0}` does not exist")]
+    StoreDoesNotExist(String),
+
+    #[error("Store `{0}` already exists")]
+    StoreExists(String),
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
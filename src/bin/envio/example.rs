@@ -0,0 +1,257 @@
+use std::path::Path;
+
+use envio::{Env, Profile, Schema, SchemaRule};
+use indexmap::IndexSet;
+
+use crate::error::{AppError, AppResult};
+
+/// what is known about an environment variable apart from its value
+struct Hints<'a> {
+    description: Vec<&'a str>,
+    constraints: Vec<String>,
+    expires: Option<String>,
+}
+
+impl<'a> Hints<'a> {
+    fn new(env: &'a Env, rule: Option<&'a SchemaRule>) -> Hints<'a> {
+        let mut description = Vec::new();
+        let mut constraints = Vec::new();
+
+        if let Some(comment) = &env.comment {
+            description.push(comment.as_str());
+        }
+
+        if let Some(rule) = rule {
+            if let Some(text) = &rule.description
+                && env.comment.as_ref() != Some(text)
+            {
+                description.push(text.as_str());
+            }
+
+            if !rule.required {
+                constraints.push("optional".to_string());
+            }
+
+            if !rule.value_type.is_string() {
+                constraints.push(rule.value_type.to_string());
+            }
+
+            if !rule.allowed.is_empty() {
+                constraints.push(format!("one of {}", rule.allowed.join(", ")));
+            }
+
+            if let Some(pattern) = &rule.pattern {
+                constraints.push(format!("matches {}", pattern));
+            }
+        }
+
+        Hints {
+            description,
+            constraints,
+            expires: env.expiration_date.map(|date| date.to_string()),
+        }
+    }
+}
+
+/// a `.env.example` with the keys of a profile and everything known about them, but no values
+pub fn write_dotenv(profile: &Profile, schema: Option<&Schema>) -> String {
+    let mut buffer = format!("# Environment variables of `{}`\n", profile.metadata.name);
+
+    if let Some(description) = &profile.metadata.description {
+        for line in description.lines() {
+            buffer.push_str(&format!("# {}\n", line));
+        }
+    }
+
+    for env in &profile.envs {
+        let hints = Hints::new(env, schema.and_then(|schema| schema.get(&env.key)));
+
+        buffer.push('\n');
+
+        for line in hints.description.iter().flat_map(|text| text.lines()) {
+            buffer.push_str(&format!("# {}\n", line));
+        }
+
+        if !hints.constraints.is_empty() {
+            buffer.push_str(&format!("# {}\n", hints.constraints.join(", ")));
+        }
+
+        if let Some(expires) = hints.expires {
+            buffer.push_str(&format!("# expires {}\n", expires));
+        }
+
+        buffer.push_str(&format!("{}=\n", env.key));
+    }
+
+    buffer
+}
+
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// a Markdown table documenting the keys of a profile
+pub fn write_markdown(profile: &Profile, schema: Option<&Schema>) -> String {
+    let mut buffer = format!("# {}\n\n", profile.metadata.name);
+
+    if let Some(description) = &profile.metadata.description {
+        buffer.push_str(&format!("{}\n\n", description));
+    }
+
+    buffer.push_str("| Variable | Description | Constraints | Expires |\n");
+    buffer.push_str("| --- | --- | --- | --- |\n");
+
+    for env in &profile.envs {
+        let hints = Hints::new(env, schema.and_then(|schema| schema.get(&env.key)));
+
+        buffer.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            env.key,
+            escape_cell(&hints.description.join(". ")),
+            escape_cell(&hints.constraints.join(", ")),
+            hints.expires.unwrap_or_default()
+        ));
+    }
+
+    buffer
+}
+
+/// the keys documented in a `.env.example` or in the table of a Markdown file written by
+/// `write_markdown`
+pub fn read_documented_keys(path: &Path) -> AppResult<IndexSet<String>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AppError::Msg(format!("Failed to read `{}`: {}", path.display(), e)))?;
+
+    let markdown = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("md"));
+
+    let keys = content
+        .lines()
+        .map(str::trim)
+        .filter_map(|line| {
+            if markdown {
+                // the first cell of a table row, the key is written as code
+                line.strip_prefix('|')?
+                    .split('|')
+                    .next()?
+                    .trim()
+                    .strip_prefix('`')?
+                    .strip_suffix('`')
+            } else if line.is_empty() || line.starts_with('#') {
+                None
+            } else {
+                let line = line.strip_prefix("export ").unwrap_or(line);
+                Some(line.split_once('=').map_or(line, |(key, _)| key).trim())
+            }
+        })
+        .filter(|key| !key.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use envio::{EnvMap, cipher::NONE};
+
+    use super::*;
+
+    /// writes `content` to a file named `name` that is removed again when the test is done
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, content: &str) -> TempFile {
+            let path =
+                std::env::temp_dir().join(format!("envio-test-{}-{}", std::process::id(), name));
+            std::fs::write(&path, content).unwrap();
+            TempFile(path)
+        }
+
+        fn read_keys(&self) -> Vec<String> {
+            read_documented_keys(&self.0).unwrap().into_iter().collect()
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn profile() -> Profile {
+        Profile::new(
+            "test".to_string(),
+            Some("first line\nsecond | line".to_string()),
+            PathBuf::from("/profiles/test.env"),
+            EnvMap::from(vec![
+                Env::new("A", "secret", Some("a | b".to_string()), None),
+                Env::from_key_value("B", "secret"),
+            ]),
+            Box::new(NONE),
+        )
+    }
+
+    #[test]
+    fn reads_the_keys_of_a_dotenv_file() {
+        let file = TempFile::new(
+            "example.env",
+            "# comment\n\nA=\nexport B=value\n  C = spaced\nD\n#E=\n",
+        );
+
+        assert_eq!(file.read_keys(), ["A", "B", "C", "D"]);
+    }
+
+    #[test]
+    fn reads_the_keys_of_a_markdown_table() {
+        let file = TempFile::new(
+            "example.md",
+            "# Title\n\nA=text\n\n| Variable | Description |\n| --- | --- |\n| `A` | x |\n| `B` |  |\n| C | not code |\n",
+        );
+
+        assert_eq!(file.read_keys(), ["A", "B"]);
+    }
+
+    #[test]
+    fn reads_the_keys_it_wrote() {
+        let profile = profile();
+
+        let dotenv = TempFile::new("written.env", &write_dotenv(&profile, None));
+        let markdown = TempFile::new("written.md", &write_markdown(&profile, None));
+
+        assert_eq!(dotenv.read_keys(), ["A", "B"]);
+        assert_eq!(markdown.read_keys(), ["A", "B"]);
+    }
+
+    #[test]
+    fn never_writes_values() {
+        let schema: Schema = serde_json::from_str(
+            r#"{"A": {"description": "the a", "type": "int", "allowed": ["1", "2"]}}"#,
+        )
+        .unwrap();
+
+        for content in [
+            write_dotenv(&profile(), Some(&schema)),
+            write_markdown(&profile(), Some(&schema)),
+        ] {
+            assert!(!content.contains("secret"));
+            assert!(content.contains("the a"));
+            assert!(content.contains("int, one of 1, 2"));
+        }
+    }
+
+    #[test]
+    fn escapes_table_cells() {
+        let markdown = write_markdown(&profile(), None);
+
+        assert!(markdown.contains("| `A` | a \\| b |  |  |\n"));
+    }
+
+    #[test]
+    fn reports_a_missing_file() {
+        assert!(read_documented_keys(Path::new("/nonexistent/.env.example")).is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use std::{collections::HashSet, str::FromStr};
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{Engine, prelude::BASE64_STANDARD};
+use strum_macros::{AsRefStr, EnumString};
+use zeroize::Zeroizing;
+
+use crate::error::{AppError, AppResult};
+
+const ALPHA: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const DIGITS: &str = "0123456789";
+/// printable characters without quotes, backslashes and backticks so values can be pasted into
+/// shells and config files as they are
+const SYMBOLS: &str = "!#$%&()*+,-./:;<=>?@[]^_{|}~";
+/// the English wordlist of BIP 39, taken unchanged from
+/// https://github.com/bitcoin/bips/blob/master/bip-0039/english.txt, see the BIP for its licence
+/// sha256: 2f5eed53a4727b4bf8880d8f3f199efc90e58503646d9ff8eff3a2ed3b24dbda
+const WORDLIST: &str = include_str!("wordlist.txt");
+
+pub const DEFAULT_LENGTH: usize = 32;
+pub const DEFAULT_WORDS: usize = 6;
+pub const DEFAULT_SEPARATOR: &str = "-";
+const MAX_LENGTH: usize = 4096;
+
+/// produces random values, written as `KIND[:LENGTH]`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Generator {
+    /// `length` characters picked from `charset`
+    Chars {
+        charset: String,
+        length: usize,
+    },
+    /// `count` words of the built-in wordlist joined by `separator`
+    Words {
+        count: usize,
+        separator: String,
+    },
+    /// `LENGTH` random bytes encoded as hex
+    Hex(usize),
+    /// `LENGTH` random bytes encoded as base64
+    Base64(usize),
+    Uuid,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator::Chars {
+            charset: Charset::Alnum.chars(),
+            length: DEFAULT_LENGTH,
+        }
+    }
+}
+
+/// the named sets of characters a generator can pick from
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, AsRefStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum Charset {
+    Alnum,
+    Alpha,
+    Digits,
+    Ascii,
+}
+
+impl Charset {
+    pub fn chars(&self) -> String {
+        match self {
+            Charset::Alnum => format!("{}{}", ALPHA, DIGITS),
+            Charset::Alpha => ALPHA.to_string(),
+            Charset::Digits => DIGITS.to_string(),
+            Charset::Ascii => format!("{}{}{}", ALPHA, DIGITS, SYMBOLS),
+        }
+    }
+}
+
+impl FromStr for Generator {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> AppResult<Generator> {
+        let invalid =
+            |reason: &str| AppError::Msg(format!("Invalid generator `{}`, {}", s, reason));
+
+        let (kind, length) = match s.split_once(':') {
+            Some((kind, length)) => (
+                kind,
+                Some(
+                    length
+                        .parse::<usize>()
+                        .map_err(|_| invalid("the length must be a number"))?,
+                ),
+            ),
+            None => (s, None),
+        };
+
+        let generator = match kind {
+            "hex" => Generator::Hex(length.unwrap_or(DEFAULT_LENGTH)),
+            "base64" => Generator::Base64(length.unwrap_or(DEFAULT_LENGTH)),
+            "words" => Generator::Words {
+                count: length.unwrap_or(DEFAULT_WORDS),
+                separator: DEFAULT_SEPARATOR.to_string(),
+            },
+            "uuid" if length.is_some() => return Err(invalid("uuids do not take a length")),
+            "uuid" => Generator::Uuid,
+            charset => Generator::Chars {
+                charset: charset
+                    .parse::<Charset>()
+                    .map_err(|_| {
+                        invalid(
+                            "expected alnum, alpha, digits, ascii, hex, base64, words or uuid optionally followed by `:LENGTH`",
+                        )
+                    })?
+                    .chars(),
+                length: length.unwrap_or(DEFAULT_LENGTH),
+            },
+        };
+
+        generator.check()?;
+        Ok(generator)
+    }
+}
+
+impl Generator {
+    /// makes sure the generator produces a value that is not empty
+    pub fn check(&self) -> AppResult<()> {
+        let length = match self {
+            Generator::Chars { charset, length } => {
+                if charset.is_empty() {
+                    return Err(AppError::Msg("The character set is empty".to_string()));
+                }
+
+                *length
+            }
+            Generator::Words { count, .. } => *count,
+            Generator::Hex(length) | Generator::Base64(length) => *length,
+            Generator::Uuid => return Ok(()),
+        };
+
+        if !(1..=MAX_LENGTH).contains(&length) {
+            return Err(AppError::Msg(format!(
+                "The length must be between 1 and {}",
+                MAX_LENGTH
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub fn generate(&self) -> Zeroizing<String> {
+        Zeroizing::new(match self {
+            Generator::Chars { charset, length } => random_chars(charset, *length),
+            Generator::Words { count, separator } => {
+                let words: Vec<&str> = WORDLIST.lines().collect();
+                (0..*count)
+                    .map(|_| words[random_index(words.len())])
+                    .collect::<Vec<_>>()
+                    .join(separator)
+            }
+            Generator::Hex(length) => random_bytes(*length)
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect(),
+            Generator::Base64(length) => BASE64_STANDARD.encode(random_bytes(*length)),
+            Generator::Uuid => {
+                let mut bytes = random_bytes(16);
+                // version 4, variant 1
+                bytes[6] = (bytes[6] & 0x0f) | 0x40;
+                bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+                let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+                format!(
+                    "{}-{}-{}-{}-{}",
+                    &hex[0..8],
+                    &hex[8..12],
+                    &hex[12..16],
+                    &hex[16..20],
+                    &hex[20..32]
+                )
+            }
+        })
+    }
+
+    /// how many bits of randomness a generated value has
+    pub fn entropy(&self) -> f64 {
+        match self {
+            Generator::Chars { charset, length } => {
+                let unique: HashSet<char> = charset.chars().collect();
+                *length as f64 * (unique.len() as f64).log2()
+            }
+            Generator::Words { count, .. } => {
+                *count as f64 * (WORDLIST.lines().count() as f64).log2()
+            }
+            Generator::Hex(length) | Generator::Base64(length) => *length as f64 * 8.0,
+            Generator::Uuid => 122.0,
+        }
+    }
+}
+
+fn random_bytes(length: usize) -> Zeroizing<Vec<u8>> {
+    let mut bytes = Zeroizing::new(vec![0u8; length]);
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+/// picks `length` characters of `charset` uniformly at random, characters listed more than once
+/// are not more likely to be picked
+fn random_chars(charset: &str, length: usize) -> String {
+    let mut seen = HashSet::new();
+    let charset: Vec<char> = charset.chars().filter(|c| seen.insert(*c)).collect();
+    (0..length)
+        .map(|_| charset[random_index(charset.len())])
+        .collect()
+}
+
+/// a random number below `n`, numbers that would favour the lower indices are drawn again
+fn random_index(n: usize) -> usize {
+    let n = n as u32;
+    let limit = u32::MAX - u32::MAX % n;
+
+    loop {
+        let number = OsRng.next_u32();
+        if number < limit {
+            return (number % n) as usize;
+        }
+    }
+}
@@ -1,11 +1,22 @@
+mod batch;
 mod clap_app;
 mod commands;
 mod completions;
+mod config;
 mod diagnostic;
+mod dotenv;
 mod error;
+mod example;
+mod generator;
 mod log_macros;
 mod ops;
+mod private_file;
+mod profile_name;
 mod prompts;
+mod render;
+mod schema;
+mod tags;
+mod trash;
 mod tui;
 mod utils;
 #[cfg(not(debug_assertions))]
@@ -25,19 +36,18 @@ pub fn initialize_config() -> AppResult<()> {
     use std::io::Write;
     use std::path::PathBuf;
 
-    use crate::utils::{get_configdir, get_shell_config_path};
+    use crate::utils::{get_configdir, get_profile_dir, get_shell_config_path};
 
     let configdir = get_configdir();
     if !configdir.exists() {
         println!("{}", "Creating config directory".bold());
-        fs::create_dir(&configdir)?;
-        fs::create_dir(configdir.join("profiles"))?;
+        fs::create_dir_all(&configdir)?;
     }
 
-    let profile_dir = configdir.join("profiles");
+    let profile_dir = get_profile_dir();
     if !profile_dir.exists() {
         println!("{}", "Creating profile directory".bold());
-        fs::create_dir(&profile_dir)?;
+        fs::create_dir_all(&profile_dir)?;
     }
 
     let shellscript_path = configdir.join("setenv.sh");
@@ -102,10 +112,13 @@ fn run() -> AppResult<()> {
     #[cfg(not(debug_assertions))]
     check_for_updates()?;
 
+    let app = ClapApp::parse();
+    app.select_profile_dir()?;
+
     #[cfg(target_family = "unix")]
     initialize_config()?;
 
-    ClapApp::parse().run()
+    app.run()
 }
 
 fn main() {
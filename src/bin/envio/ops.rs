@@ -1,40 +1,73 @@
-#[cfg(target_family = "windows")]
-use std::process::Command;
 use std::{
+    collections::BTreeMap,
     io::{Read, Write},
     path::{Path, PathBuf},
+    process::{Command, Stdio},
 };
 
-use chrono::Local;
+use chrono::{Local, NaiveDate};
 use colored::Colorize;
-use comfy_table::{Attribute, Cell, ContentArrangement, Table};
-use envio::{EnvMap, Profile, cipher::Cipher};
+use comfy_table::{Attribute, Cell, Color, ContentArrangement, Table};
+use envio::{
+    Env, EnvMap, KeyPolicy, Profile, RotationPolicy, Tags,
+    cipher::{Cipher, CipherKind, EncryptedContent},
+    layout::ContentLayout,
+    profile::ExpiredPolicy,
+    store::{detect_store_kind, open_store},
+};
+use serde::Serialize;
+use strum_macros::EnumString;
+use zeroize::Zeroizing;
 
 #[cfg(target_family = "unix")]
-use crate::utils::get_shellscript_path;
+use crate::utils::{get_default_profile_dir, get_shellscript_path};
 
 use crate::{
+    config::Config,
+    dotenv,
     error::{AppError, AppResult},
+    error_msg,
+    generator::Generator,
+    private_file::PrivateFile,
+    prompts::{SelectPromptOptions, select_prompt},
+    success_msg,
+    tags::{TagFilter, format_tags},
+    trash::{self, TrashEntry},
     utils::{
-        build_profile_path, contains_path_separator, download_file, get_cwd, get_profile_dir,
-        get_profile_metadata, get_profile_path,
+        add_duration, build_profile_path, contains_path_separator, download_file, get_cwd,
+        get_profile_dir, get_profile_metadata, get_profile_names, get_profile_path,
     },
     warning_msg,
 };
 
+/// the settings of a new profile that can be left at their defaults
+#[derive(Default)]
+pub struct ProfileOptions {
+    pub layout: ContentLayout,
+    pub clear_comments: bool,
+    pub on_expired: ExpiredPolicy,
+    pub tags: Tags,
+}
+
 pub fn create_profile(
     name: String,
     description: Option<String>,
     envs: EnvMap,
     cipher: Box<dyn Cipher>,
+    options: ProfileOptions,
 ) -> AppResult<()> {
-    let profile_file_path = build_profile_path(&name);
+    let profile_file_path = build_profile_path(&name)?;
 
     if profile_file_path.exists() {
         return Err(AppError::ProfileExists(name));
     }
 
-    Profile::new(name, description, profile_file_path, envs, cipher).save()?;
+    let mut profile = Profile::new(name, description, profile_file_path, envs, cipher);
+    profile.metadata.layout = options.layout;
+    profile.metadata.clear_comments = options.clear_comments;
+    profile.metadata.on_expired = options.on_expired;
+    profile.metadata.tags = options.tags;
+    profile.save()?;
 
     Ok(())
 }
@@ -49,6 +82,42 @@ pub fn check_expired_envs(profile: &Profile) {
     }
 }
 
+/// handles the expired environment variables of a profile that is about to be used, `policy`
+/// overrides the one stored in the profile
+pub fn apply_expired_policy(profile: &mut Profile, policy: Option<ExpiredPolicy>) -> AppResult<()> {
+    let today = Local::now().date_naive();
+    let expired: Vec<String> = profile
+        .envs
+        .iter()
+        .filter(|env| env.expiration_date.is_some_and(|date| date <= today))
+        .map(|env| env.key.clone())
+        .collect();
+
+    if expired.is_empty() {
+        return Ok(());
+    }
+
+    match policy.unwrap_or(profile.metadata.on_expired) {
+        ExpiredPolicy::Warn => check_expired_envs(profile),
+        ExpiredPolicy::Skip => {
+            for key in &expired {
+                warning_msg!("skipping expired environment variable '{}'", key);
+            }
+
+            profile.envs.retain(|env| !expired.contains(&env.key));
+        }
+        ExpiredPolicy::Fail => {
+            return Err(AppError::Msg(format!(
+                "Profile `{}` contains expired environment variables: {}",
+                profile.metadata.name,
+                expired.join(", ")
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 pub fn export_envs(
     profile: &Profile,
     output_file_path: &str,
@@ -83,13 +152,390 @@ pub fn export_envs(
     }
 
     for env in envs_to_export {
-        writeln!(file, "{}={}", env.key, env.value)?;
+        // multi-line values such as PEM keys are quoted the way dotenv parsers expect them
+        if dotenv::needs_quotes(&env.value) {
+            writeln!(file, "{}={}", env.key, dotenv::quote_value(&env.value))?;
+        } else {
+            writeln!(file, "{}={}", env.key, env.value)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn get_editor() -> Vec<String> {
+    let editor = std::env::var("VISUAL")
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .or_else(|| {
+            std::env::var("EDITOR")
+                .ok()
+                .filter(|e| !e.trim().is_empty())
+        })
+        .unwrap_or_else(|| {
+            if cfg!(target_family = "windows") {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        });
+
+    editor.split_whitespace().map(String::from).collect()
+}
+
+/// runs the editor on `path`, returns false if envio was asked to terminate in the meantime
+fn run_editor(path: &Path) -> AppResult<bool> {
+    use signal_hook::consts::{SIGINT, SIGTERM};
+    use std::sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    };
+
+    let editor = get_editor();
+
+    // handlers instead of ignoring the signals, so the editor still gets the default behaviour
+    // while envio stays alive long enough to wipe the decrypted buffer. interrupts are sent to
+    // the editor as well, which decides on its own what to do with them
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let terminated = Arc::new(AtomicBool::new(false));
+
+    let mut signals = vec![
+        signal_hook::flag::register(SIGINT, Arc::clone(&interrupted))?,
+        signal_hook::flag::register(SIGTERM, Arc::clone(&terminated))?,
+    ];
+
+    #[cfg(target_family = "unix")]
+    signals.push(signal_hook::flag::register(
+        signal_hook::consts::SIGHUP,
+        Arc::clone(&terminated),
+    )?);
+
+    let status = std::process::Command::new(&editor[0])
+        .args(&editor[1..])
+        .arg(path)
+        .status();
+
+    for signal in signals {
+        signal_hook::low_level::unregister(signal);
+    }
+
+    let status =
+        status.map_err(|e| AppError::Msg(format!("Failed to open `{}`: {}", editor[0], e)))?;
+
+    if terminated.load(Ordering::Relaxed) {
+        return Ok(false);
+    }
+
+    if !status.success() {
+        return Err(AppError::Msg(format!(
+            "`{}` exited with {}, discarding changes",
+            editor[0], status
+        )));
+    }
+
+    Ok(true)
+}
+
+/// waits for `child` while keeping envio alive, so whatever was handed to the child can be cleaned
+/// up after it exits. termination and hangup signals are passed on to the child, interrupts
+/// from the terminal reach it on their own
+pub fn wait_child(child: &mut std::process::Child) -> AppResult<std::process::ExitStatus> {
+    #[cfg(target_family = "unix")]
+    {
+        use signal_hook::{
+            consts::{SIGHUP, SIGINT, SIGTERM},
+            iterator::Signals,
+        };
+
+        let mut signals = Signals::new([SIGINT, SIGTERM, SIGHUP])?;
+        let handle = signals.handle();
+        let pid = child.id() as libc::pid_t;
+
+        let forwarder = std::thread::spawn(move || {
+            for signal in signals.forever() {
+                if signal != SIGINT {
+                    unsafe { libc::kill(pid, signal) };
+                }
+            }
+        });
+
+        let status = child.wait();
+
+        handle.close();
+        let _ = forwarder.join();
+
+        Ok(status?)
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    {
+        use std::sync::{Arc, atomic::AtomicBool};
+
+        let signal = signal_hook::flag::register(
+            signal_hook::consts::SIGINT,
+            Arc::new(AtomicBool::new(false)),
+        )?;
+        let status = child.wait();
+        signal_hook::low_level::unregister(signal);
+
+        Ok(status?)
+    }
+}
+
+/// lets the user edit the environment variables of a profile in their editor, returns `None` if
+/// the edit was abandoned
+pub fn edit_envs(profile: &Profile) -> AppResult<Option<EnvMap>> {
+    let header = format!(
+        "Editing profile `{}`, lines starting with `#` are ignored\n\
+         Annotate a variable by placing `#@ comment: <TEXT>`, `#@ expires: <YYYY-MM-DD>` or\n\
+         `#@ tags: <NAME=VALUE>, ...` above it\n\
+         Values that span multiple lines are written in double quotes using \\n",
+        profile.metadata.name
+    );
+
+    let buffer = Zeroizing::new(dotenv::write_annotated(&profile.envs, &header));
+    let file = PrivateFile::create("edit.env", buffer.as_bytes())?;
+
+    loop {
+        if !run_editor(file.path())? {
+            return Err(AppError::Msg(
+                "envio was terminated, discarding changes".to_string(),
+            ));
+        }
+
+        let edited = Zeroizing::new(std::fs::read_to_string(file.path())?);
+
+        match dotenv::parse_annotated(&edited, &profile.envs) {
+            Ok(mut envs) => {
+                // rotation policies are not part of the buffer
+                for env in envs.iter_mut() {
+                    env.rotation = profile.envs.get(&env.key).and_then(|e| e.rotation.clone());
+                }
+
+                return Ok(Some(envs));
+            }
+            Err(e) => {
+                error_msg!(e);
+
+                let choice = select_prompt(SelectPromptOptions {
+                    title: "What do you want to do?".to_string(),
+                    options: vec!["Edit again", "Discard changes"],
+                })?;
+
+                if choice != "Edit again" {
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
+
+/// prints the keys that differ between two sets of environment variables, values are left out
+/// since they are secrets. returns whether anything changed
+pub fn print_env_changes(old: &EnvMap, new: &EnvMap) -> bool {
+    let mut changed = false;
+
+    for env in new {
+        match old.get(&env.key) {
+            None => {
+                println!("{} {}", "+".green().bold(), env.key);
+                changed = true;
+            }
+            Some(previous) => {
+                let mut fields = vec![];
+
+                if previous.value != env.value {
+                    fields.push("value");
+                }
+                if previous.comment != env.comment {
+                    fields.push("comment");
+                }
+                if previous.expiration_date != env.expiration_date {
+                    fields.push("expiration date");
+                }
+                if previous.tags != env.tags {
+                    fields.push("tags");
+                }
+
+                if !fields.is_empty() {
+                    println!(
+                        "{} {} ({})",
+                        "~".yellow().bold(),
+                        env.key,
+                        fields.join(", ")
+                    );
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    for env in old {
+        if !new.contains_key(&env.key) {
+            println!("{} {}", "-".red().bold(), env.key);
+            changed = true;
+        }
+    }
+
+    changed || !old.keys().eq(new.keys())
+}
+
+/// what to do with keys that exist in both profiles with different values
+#[derive(Clone, Copy, PartialEq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum MergeStrategy {
+    Keep,
+    Overwrite,
+    Prompt,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum MergeAction {
+    Add,
+    Overwrite,
+    Keep,
+    Unchanged,
+    /// only used for dry runs, where the user is not asked about conflicts
+    Conflict,
+}
+
+impl std::fmt::Display for MergeAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let action = match self {
+            MergeAction::Add => "add",
+            MergeAction::Overwrite => "overwrite",
+            MergeAction::Keep => "keep",
+            MergeAction::Unchanged => "unchanged",
+            MergeAction::Conflict => "ask",
+        };
+
+        write!(f, "{}", action)
+    }
+}
+
+/// decides for every key in `source` what merging it into `target` does, conflicts are prompted
+/// for unless this is a dry run
+pub fn plan_merge(
+    target: &EnvMap,
+    source: &EnvMap,
+    strategy: MergeStrategy,
+    dry_run: bool,
+) -> AppResult<Vec<(String, MergeAction)>> {
+    let mut plan = Vec::with_capacity(source.len());
+
+    for env in source {
+        let action = match target.get(&env.key) {
+            None => MergeAction::Add,
+            Some(existing) if existing.value == env.value => MergeAction::Unchanged,
+            Some(_) => match strategy {
+                MergeStrategy::Keep => MergeAction::Keep,
+                MergeStrategy::Overwrite => MergeAction::Overwrite,
+                MergeStrategy::Prompt if dry_run => MergeAction::Conflict,
+                MergeStrategy::Prompt => {
+                    let choice = select_prompt(SelectPromptOptions {
+                        title: format!("`{}` already exists with a different value:", env.key),
+                        options: vec!["Keep the current value", "Overwrite it"],
+                    })?;
+
+                    if choice == "Overwrite it" {
+                        MergeAction::Overwrite
+                    } else {
+                        MergeAction::Keep
+                    }
+                }
+            },
+        };
+
+        plan.push((env.key.clone(), action));
+    }
+
+    Ok(plan)
+}
+
+pub fn print_merge_plan(plan: &[(String, MergeAction)]) {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec![
+        Cell::new("Environment Variable").add_attribute(Attribute::Bold),
+        Cell::new("Action").add_attribute(Attribute::Bold),
+    ]);
+
+    for (key, action) in plan {
+        let color = match action {
+            MergeAction::Add => Color::Green,
+            MergeAction::Overwrite => Color::Yellow,
+            MergeAction::Conflict => Color::Red,
+            MergeAction::Keep | MergeAction::Unchanged => Color::Reset,
+        };
+
+        table.add_row(vec![
+            Cell::new(key),
+            Cell::new(action.to_string()).fg(color),
+        ]);
+    }
+
+    println!("{table}");
+}
+
+/// applies a merge plan, overwritten variables keep their comment, expiration date, tags and
+/// rotation policy unless they did not have any
+pub fn apply_merge(
+    target: &mut EnvMap,
+    source: &EnvMap,
+    plan: &[(String, MergeAction)],
+) -> AppResult<()> {
+    for (key, action) in plan {
+        let Some(env) = source.get(key) else {
+            continue;
+        };
+
+        match action {
+            MergeAction::Add => target.insert(env.clone())?,
+            MergeAction::Overwrite => {
+                let mut merged = env.clone();
+
+                if let Some(existing) = target.get(key) {
+                    merged.comment = existing.comment.clone().or(merged.comment);
+                    merged.expiration_date = existing.expiration_date.or(merged.expiration_date);
+
+                    if !existing.tags.is_empty() {
+                        merged.tags = existing.tags.clone();
+                    }
+
+                    merged.rotation = existing.rotation.clone().or(merged.rotation);
+                }
+
+                target.insert(merged)?;
+            }
+            MergeAction::Keep | MergeAction::Unchanged | MergeAction::Conflict => {}
+        }
     }
 
     Ok(())
 }
 
-pub fn list_envs(profile: &Profile, show_comments: bool, show_expiration: bool) {
+pub fn print_tags(tags: &Tags) {
+    if tags.is_empty() {
+        println!("{}", "No tags".bold());
+        return;
+    }
+
+    for (name, value) in tags {
+        if value.is_empty() {
+            println!("{}", name);
+        } else {
+            println!("{}={}", name, value);
+        }
+    }
+}
+
+pub fn list_envs(
+    profile: &Profile,
+    show_comments: bool,
+    show_expiration: bool,
+    show_tags: bool,
+    show_history: bool,
+) {
     let mut table = Table::new();
     table.set_content_arrangement(ContentArrangement::Dynamic);
 
@@ -106,6 +552,16 @@ pub fn list_envs(profile: &Profile, show_comments: bool, show_expiration: bool)
         header.push(Cell::new("Expiration Date").add_attribute(Attribute::Bold));
     }
 
+    if show_tags {
+        header.push(Cell::new("Tags").add_attribute(Attribute::Bold));
+    }
+
+    if show_history {
+        header.push(Cell::new("Created At").add_attribute(Attribute::Bold));
+        header.push(Cell::new("Updated At").add_attribute(Attribute::Bold));
+        header.push(Cell::new("Updated By").add_attribute(Attribute::Bold));
+    }
+
     table.set_header(header);
 
     let mut row;
@@ -125,38 +581,653 @@ pub fn list_envs(profile: &Profile, show_comments: bool, show_expiration: bool)
             );
         }
 
+        if show_tags {
+            row.push(format_tags(&env.tags));
+        }
+
+        if show_history {
+            for timestamp in [env.created_at, env.updated_at] {
+                row.push(
+                    timestamp
+                        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                        .unwrap_or_else(|| "".to_string()),
+                );
+            }
+
+            row.push(env.updated_by.clone().unwrap_or_else(|| "".to_string()));
+        }
+
         table.add_row(row);
     }
 
     println!("{table}");
 }
 
-pub fn delete_profile(profile_name: &str) -> AppResult<()> {
-    std::fs::remove_file(get_profile_path(profile_name)?)?;
+#[derive(Serialize)]
+pub struct ExpiringEnv {
+    pub store: String,
+    pub profile: String,
+    pub key: String,
+    pub expiration_date: NaiveDate,
+    pub days_left: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// the comment is encrypted with the value of a per-entry profile, which is not decrypted
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub comment_encrypted: bool,
+}
 
-    Ok(())
+impl ExpiringEnv {
+    pub fn is_expired(&self) -> bool {
+        self.days_left <= 0
+    }
 }
 
-pub fn list_profiles(no_pretty_print: bool) -> AppResult<()> {
-    let profile_dir = get_profile_dir();
+/// a profile that could not be decrypted while looking for expiring environment variables
+#[derive(Serialize)]
+pub struct UncheckedProfile {
+    pub store: String,
+    pub profile: String,
+    pub reason: String,
+}
 
-    let mut profiles = Vec::new();
-    for entry in std::fs::read_dir(profile_dir).unwrap() {
-        let entry = entry.unwrap();
-        let path = entry.path();
-        match path.extension() {
-            None => continue,
-            Some(ext) => {
-                if ext != "env" {
+#[derive(Serialize, Default)]
+pub struct ExpirationReport {
+    pub envs: Vec<ExpiringEnv>,
+    pub unchecked: Vec<UncheckedProfile>,
+}
+
+/// collects the environment variables of every profile in `stores` matching `pattern` that
+/// expire on or before `cutoff`. per-entry profiles are not decrypted since their expiration
+/// dates are in cleartext, so only their comments kept in cleartext are available
+pub fn find_expiring_envs<F>(
+    stores: &[(String, PathBuf)],
+    pattern: Option<&str>,
+    cutoff: NaiveDate,
+    get_key: F,
+) -> AppResult<ExpirationReport>
+where
+    F: Fn(&str) -> Option<Zeroizing<String>>,
+{
+    let today = Local::now().date_naive();
+    let mut report = ExpirationReport::default();
+
+    for (store, dir) in stores {
+        for name in get_profile_names(dir)? {
+            if pattern.is_some_and(|pattern| !name.matches(pattern)) {
+                continue;
+            }
+
+            let path = name.file_path(dir);
+            let profile_name = name.to_string();
+            let serialized_profile = match envio::utils::get_serialized_profile(&path) {
+                Ok(serialized_profile) => serialized_profile,
+                Err(e) => {
+                    report.unchecked.push(UncheckedProfile {
+                        store: store.clone(),
+                        profile: profile_name,
+                        reason: e.to_string(),
+                    });
                     continue;
                 }
+            };
+
+            let comment_encrypted = serialized_profile.metadata.layout == ContentLayout::PerEntry
+                && !serialized_profile.metadata.clear_comments;
+
+            let envs: Vec<(String, Option<NaiveDate>, Option<String>)> =
+                if let EncryptedContent::Entries(entries) = serialized_profile.content {
+                    entries
+                        .entries
+                        .into_iter()
+                        .map(|entry| (entry.key, entry.expiration_date, entry.comment))
+                        .collect()
+                } else {
+                    let cipher_kind = serialized_profile.metadata.cipher_kind;
+
+                    match open_profile(&path, &profile_name, cipher_kind, &get_key) {
+                        Ok(profile) => profile
+                            .envs
+                            .into_iter()
+                            .map(|env| (env.key, env.expiration_date, env.comment))
+                            .collect(),
+                        Err(e) => {
+                            report.unchecked.push(UncheckedProfile {
+                                store: store.clone(),
+                                profile: profile_name,
+                                reason: e.to_string(),
+                            });
+                            continue;
+                        }
+                    }
+                };
+
+            for (key, expiration_date, comment) in envs {
+                if let Some(date) = expiration_date
+                    && date <= cutoff
+                {
+                    report.envs.push(ExpiringEnv {
+                        store: store.clone(),
+                        profile: profile_name.clone(),
+                        key,
+                        expiration_date: date,
+                        days_left: (date - today).num_days(),
+                        comment,
+                        comment_encrypted,
+                    });
+                }
             }
         }
-        let profile_name = path.file_stem().unwrap().to_str().unwrap().to_owned();
-        if profile_name.starts_with('.') {
-            continue;
+    }
+
+    report.envs.sort_by_key(|env| env.expiration_date);
+
+    Ok(report)
+}
+
+pub fn print_expiration_report(report: &ExpirationReport, show_store: bool, within: &str) {
+    for unchecked in &report.unchecked {
+        warning_msg!(
+            "could not check profile '{}': {}",
+            unchecked.profile,
+            unchecked.reason
+        );
+    }
+
+    if report.envs.is_empty() {
+        println!(
+            "{}",
+            format!("No environment variables expire within {}", within).bold()
+        );
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new("Profile").add_attribute(Attribute::Bold),
+        Cell::new("Environment Variable").add_attribute(Attribute::Bold),
+        Cell::new("Expiration Date").add_attribute(Attribute::Bold),
+        Cell::new("Status").add_attribute(Attribute::Bold),
+        Cell::new("Comment").add_attribute(Attribute::Bold),
+    ]);
+
+    for env in &report.envs {
+        let profile = if show_store {
+            format!("{}:{}", env.store, env.profile)
+        } else {
+            env.profile.clone()
+        };
+
+        let status = if env.is_expired() {
+            Cell::new("expired").fg(Color::Red)
+        } else {
+            Cell::new(format!(
+                "in {} day{}",
+                env.days_left,
+                if env.days_left == 1 { "" } else { "s" }
+            ))
+            .fg(Color::Yellow)
+        };
+
+        table.add_row(vec![
+            Cell::new(profile),
+            Cell::new(&env.key),
+            Cell::new(env.expiration_date),
+            status,
+            if env.comment_encrypted {
+                Cell::new("(encrypted)").add_attribute(Attribute::Dim)
+            } else {
+                Cell::new(env.comment.clone().unwrap_or_default())
+            },
+        ]);
+    }
+
+    println!("{table}");
+}
+
+/// where the new value of a rotated environment variable comes from
+#[derive(Clone)]
+pub enum RotationSource {
+    Generator(Generator),
+    /// a shell command printing the new value, it gets the current one on stdin
+    Command(String),
+}
+
+impl RotationSource {
+    pub fn from_flags(generator: Option<&str>, command: Option<&str>) -> AppResult<Option<Self>> {
+        match (generator, command) {
+            (_, Some(command)) => Ok(Some(RotationSource::Command(command.to_string()))),
+            (Some(generator), None) => Ok(Some(RotationSource::Generator(generator.parse()?))),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// the source configured in a rotation policy, if it has one
+    pub fn from_policy(policy: &RotationPolicy) -> AppResult<Option<Self>> {
+        Self::from_flags(policy.generator.as_deref(), policy.command.as_deref())
+    }
+
+    fn produce(&self, env: &Env) -> AppResult<Zeroizing<String>> {
+        match self {
+            RotationSource::Generator(generator) => Ok(generator.generate()),
+            RotationSource::Command(command) => run_rotation_command(command, env),
+        }
+    }
+}
+
+fn run_rotation_command(command: &str, env: &Env) -> AppResult<Zeroizing<String>> {
+    #[cfg(target_family = "unix")]
+    let mut process = {
+        let mut process = Command::new("sh");
+        process.arg("-c").arg(command);
+        process
+    };
+
+    #[cfg(target_family = "windows")]
+    let mut process = {
+        let mut process = Command::new("cmd");
+        process.arg("/C").arg(command);
+        process
+    };
+
+    let mut child = process
+        .env("ENVIO_ROTATE_KEY", &env.key)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::Msg(format!("Failed to run `{}`: {}", command, e)))?;
+
+    // the current value is passed on stdin so it does not show up in the process list, the
+    // command does not have to read it
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(env.value.as_bytes());
+    }
+
+    let output = child.wait_with_output()?;
+    let stdout = Zeroizing::new(output.stdout);
+
+    if !output.status.success() {
+        return Err(AppError::Msg(format!(
+            "`{}` exited with {}",
+            command, output.status
+        )));
+    }
+
+    let value = std::str::from_utf8(&stdout)
+        .map_err(|_| AppError::Msg(format!("`{}` did not print valid UTF-8", command)))?;
+
+    Ok(Zeroizing::new(
+        value.trim_end_matches(['\n', '\r']).to_string(),
+    ))
+}
+
+/// the day the value of `env` has to be rotated by according to its rotation policy
+pub fn rotation_due_date(env: &Env) -> AppResult<Option<NaiveDate>> {
+    let Some(policy) = &env.rotation else {
+        return Ok(None);
+    };
+
+    let changed = env
+        .updated_at
+        .map(|updated_at| updated_at.date_naive())
+        .unwrap_or_else(|| Local::now().date_naive());
+
+    Ok(Some(add_duration(changed, &policy.max_age)?))
+}
+
+/// gives `env` a new value from `source` and moves its expiration date to when the next rotation
+/// is due, the time it was rotated is recorded when the profile is saved
+pub fn rotate_env(env: &mut Env, source: &RotationSource) -> AppResult<()> {
+    let value = source.produce(env)?;
+
+    if value.is_empty() {
+        return Err(AppError::Msg(format!(
+            "The new value of '{}' is empty",
+            env.key
+        )));
+    }
+
+    if *value == env.value {
+        return Err(AppError::Msg(format!(
+            "The new value of '{}' is the same as the current one",
+            env.key
+        )));
+    }
+
+    env.value = value.to_string();
+
+    if let Some(policy) = &env.rotation {
+        env.expiration_date = Some(add_duration(Local::now().date_naive(), &policy.max_age)?);
+    }
+
+    Ok(())
+}
+
+pub fn print_rotation_policy(env: &Env) -> AppResult<()> {
+    let Some(policy) = &env.rotation else {
+        println!("{}", format!("'{}' has no rotation policy", env.key).bold());
+        return Ok(());
+    };
+
+    println!("{} {}", "Max age:".bold(), policy.max_age);
+
+    if let Some(generator) = &policy.generator {
+        println!("{} {}", "Generator:".bold(), generator);
+    }
+
+    if let Some(command) = &policy.command {
+        println!("{} {}", "Command:".bold(), command);
+    }
+
+    if let Some(updated_at) = env.updated_at {
+        println!(
+            "{} {}",
+            "Last changed:".bold(),
+            updated_at.format("%Y-%m-%d %H:%M:%S")
+        );
+    }
+
+    if let Some(date) = rotation_due_date(env)? {
+        println!("{} {}", "Due:".bold(), date);
+    }
+
+    Ok(())
+}
+
+/// rotates the environment variables of `profiles` that are due for rotation with the source
+/// in their policy, or `source` if given. variables without either are skipped
+pub fn rotate_due<F>(
+    profiles: &[(String, PathBuf)],
+    source: Option<&RotationSource>,
+    list_only: bool,
+    get_key: F,
+) -> AppResult<()>
+where
+    F: Fn(&str) -> Option<Zeroizing<String>>,
+{
+    let today = Local::now().date_naive();
+    let mut due = Vec::new();
+    let mut rotated = 0;
+    let mut skipped = 0;
+
+    for (profile_name, path) in profiles {
+        let cipher_kind = match envio::utils::get_serialized_profile(path) {
+            Ok(serialized_profile) => serialized_profile.metadata.cipher_kind,
+            Err(e) => {
+                warning_msg!("could not read profile '{}': {}", profile_name, e);
+                continue;
+            }
+        };
+
+        let mut profile = match open_profile(path, profile_name, cipher_kind, &get_key) {
+            Ok(profile) => profile,
+            Err(e) => {
+                warning_msg!("could not check profile '{}': {}", profile_name, e);
+                continue;
+            }
+        };
+
+        let mut changed = false;
+
+        for env in profile.envs.iter_mut() {
+            let Some(date) = rotation_due_date(env)?.filter(|date| *date <= today) else {
+                continue;
+            };
+
+            if list_only {
+                due.push((profile_name.clone(), env.key.clone(), date));
+                continue;
+            }
+
+            let policy_source = match &env.rotation {
+                Some(policy) => RotationSource::from_policy(policy)?,
+                None => None,
+            };
+
+            let Some(source) = source.cloned().or(policy_source) else {
+                warning_msg!(
+                    "skipping '{}' in '{}', its rotation policy has no generator or command",
+                    env.key,
+                    profile_name
+                );
+                skipped += 1;
+                continue;
+            };
+
+            match rotate_env(env, &source) {
+                Ok(()) => {
+                    println!("{} {}: {}", "Rotated".green(), profile_name, env.key);
+                    rotated += 1;
+                    changed = true;
+                }
+                Err(e) => {
+                    error_msg!("{}: {}", profile_name, e);
+                    skipped += 1;
+                }
+            }
+        }
+
+        if changed {
+            profile.save()?;
+        }
+    }
+
+    if list_only {
+        if due.is_empty() {
+            println!("{}", "No environment variables are due for rotation".bold());
+            return Ok(());
+        }
+
+        let mut table = Table::new();
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+
+        table.set_header(vec![
+            Cell::new("Profile").add_attribute(Attribute::Bold),
+            Cell::new("Environment Variable").add_attribute(Attribute::Bold),
+            Cell::new("Due").add_attribute(Attribute::Bold),
+        ]);
+
+        for (profile, key, date) in due {
+            table.add_row(vec![
+                Cell::new(profile),
+                Cell::new(key),
+                Cell::new(date).fg(Color::Red),
+            ]);
+        }
+
+        println!("{table}");
+        return Ok(());
+    }
+
+    if rotated == 0 && skipped == 0 {
+        println!("{}", "No environment variables are due for rotation".bold());
+    } else if skipped > 0 {
+        return Err(AppError::Msg(format!(
+            "Rotated {} environment variables, {} could not be rotated",
+            rotated, skipped
+        )));
+    } else {
+        success_msg!("Rotated {} environment variables", rotated);
+    }
+
+    Ok(())
+}
+
+/// opens one of many profiles, the key is only asked for if its cipher needs one
+fn open_profile<F>(
+    path: &Path,
+    profile_name: &str,
+    cipher_kind: CipherKind,
+    get_key: &F,
+) -> AppResult<Profile>
+where
+    F: Fn(&str) -> Option<Zeroizing<String>>,
+{
+    let key = match cipher_kind {
+        CipherKind::PASSPHRASE | CipherKind::AGE => get_key(profile_name),
+        _ => Some(Zeroizing::default()),
+    };
+
+    key.ok_or_else(|| AppError::Msg("no key was provided".to_string()))
+        .and_then(|key| Ok(envio::get_profile(path, Some(|| key))?))
+}
+
+/// moves a profile to the trash, from where it can be restored until it is purged
+pub fn delete_profile(profile_name: &str) -> AppResult<()> {
+    let profile_path = get_profile_path(profile_name)?;
+
+    trash::add(profile_name, &profile_path)?;
+    open_store(get_profile_dir()).delete_profile(&profile_path)?;
+
+    trash::purge(Config::load()?.get_trash_retention_days())?;
+
+    Ok(())
+}
+
+/// an environment variable whose name does not follow the key policy of its profile
+pub struct KeyIssue {
+    pub key: String,
+    pub problem: String,
+    pub suggestion: Option<String>,
+}
+
+pub fn lint_keys(envs: &EnvMap) -> Vec<KeyIssue> {
+    envs.keys()
+        .filter_map(|key| match envs.key_policy().check(key) {
+            Ok(()) => None,
+            Err(e) => Some(KeyIssue {
+                key: key.clone(),
+                problem: match e {
+                    envio::error::Error::InvalidKey(_, reason) => reason,
+                    e => e.to_string(),
+                },
+                suggestion: KeyPolicy::normalize(key),
+            }),
+        })
+        .collect()
+}
+
+pub fn print_lint_issues(issues: &[KeyIssue]) {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec![
+        Cell::new("Key").add_attribute(Attribute::Bold),
+        Cell::new("Problem").add_attribute(Attribute::Bold),
+        Cell::new("Suggestion").add_attribute(Attribute::Bold),
+    ]);
+
+    for issue in issues {
+        table.add_row(vec![
+            Cell::new(format!("`{}`", issue.key)).fg(Color::Red),
+            Cell::new(&issue.problem),
+            match &issue.suggestion {
+                Some(suggestion) => Cell::new(suggestion).fg(Color::Green),
+                None => Cell::new("remove it with `envio unset`").fg(Color::DarkGrey),
+            },
+        ]);
+    }
+
+    println!("{table}");
+}
+
+/// renames every environment variable with an issue to its suggestion. nothing is renamed if
+/// any of them can not be renamed or two of them would end up with the same name
+pub fn fix_keys(envs: &mut EnvMap, issues: &[KeyIssue]) -> AppResult<()> {
+    let mut taken: Vec<&str> = envs.keys().map(String::as_str).collect();
+
+    for issue in issues {
+        let Some(suggestion) = &issue.suggestion else {
+            return Err(AppError::Msg(format!(
+                "`{}` can not be renamed automatically, remove it with `envio unset` first",
+                issue.key
+            )));
+        };
+
+        if taken.contains(&suggestion.as_str()) {
+            return Err(AppError::Msg(format!(
+                "Can not rename `{}` to `{}`, an environment variable with that name already exists",
+                issue.key, suggestion
+            )));
+        }
+
+        taken.push(suggestion);
+    }
+
+    for issue in issues {
+        envs.rename(&issue.key, issue.suggestion.as_deref().unwrap())?;
+    }
+
+    Ok(())
+}
+
+pub fn list_trash(entries: &[TrashEntry]) {
+    if entries.is_empty() {
+        println!("The trash is empty");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec![
+        Cell::new("ID").add_attribute(Attribute::Bold),
+        Cell::new("Profile").add_attribute(Attribute::Bold),
+        Cell::new("Deleted At").add_attribute(Attribute::Bold),
+        Cell::new("Location").add_attribute(Attribute::Bold),
+    ]);
+
+    for entry in entries {
+        table.add_row(vec![
+            Cell::new(&entry.id),
+            Cell::new(&entry.name),
+            Cell::new(entry.deleted_at.format("%Y-%m-%d %H:%M:%S").to_string()),
+            Cell::new(
+                entry
+                    .original_path
+                    .parent()
+                    .map(|dir| dir.display().to_string())
+                    .unwrap_or_default(),
+            ),
+        ]);
+    }
+
+    println!("{table}");
+}
+
+/// lists the profiles matching `pattern` and `tags`, as a tree of their namespaces if `tree` is
+/// set
+pub fn list_profiles(
+    stores: &[(String, PathBuf)],
+    pattern: Option<&str>,
+    tags: Option<&TagFilter>,
+    no_pretty_print: bool,
+    tree: bool,
+) -> AppResult<()> {
+    let show_store = stores.len() > 1;
+
+    let mut profiles = Vec::new();
+    for (store, dir) in stores {
+        for profile in get_profile_names(dir)? {
+            if pattern.is_some_and(|pattern| !profile.matches(pattern)) {
+                continue;
+            }
+
+            let metadata = envio::utils::get_serialized_profile(profile.file_path(dir))?.metadata;
+
+            if tags.is_some_and(|tags| !tags.matches(&metadata.tags)) {
+                continue;
+            }
+
+            if profile.is_legacy() && !no_pretty_print {
+                warning_msg!(
+                    "the name of profile '{}' is no longer valid, rename it with `envio rename`",
+                    profile
+                );
+            }
+
+            profiles.push((store, profile.to_string(), metadata));
         }
-        profiles.push(profile_name);
     }
 
     if no_pretty_print {
@@ -165,37 +1236,167 @@ pub fn list_profiles(no_pretty_print: bool) -> AppResult<()> {
             return Ok(());
         }
 
-        for profile in profiles {
-            println!(
-                "{} - {}",
-                profile,
-                get_profile_metadata(&profile)?
-                    .description
-                    .unwrap_or("".to_string())
-            );
+        for (store, profile, metadata) in profiles {
+            let description = metadata.description.unwrap_or("".to_string());
+
+            if show_store {
+                println!("{}:{} - {}", store, profile, description);
+            } else {
+                println!("{} - {}", profile, description);
+            }
+        }
+        return Ok(());
+    }
+
+    if tree {
+        if profiles.is_empty() {
+            println!("{}", "No profiles found".bold());
+            return Ok(());
         }
+
+        for (store, _) in stores {
+            let mut root = ProfileTree::default();
+
+            for (_, profile, metadata) in profiles.iter().filter(|(s, _, _)| *s == store) {
+                root.insert(profile, metadata.description.as_deref());
+            }
+
+            if !root.children.is_empty() {
+                println!("{}", store.bold());
+                root.print("");
+            }
+        }
+
         return Ok(());
     }
 
     let mut table = Table::new();
     table.set_content_arrangement(ContentArrangement::Dynamic);
 
-    table.set_header(vec![
+    let mut header = vec![
         Cell::new("Name").add_attribute(Attribute::Bold),
         Cell::new("Description").add_attribute(Attribute::Bold),
         Cell::new("Cipher Kind").add_attribute(Attribute::Bold),
         Cell::new("Created At").add_attribute(Attribute::Bold),
         Cell::new("Updated At").add_attribute(Attribute::Bold),
+    ];
+
+    let show_tags = profiles
+        .iter()
+        .any(|(_, _, metadata)| !metadata.tags.is_empty());
+
+    if show_tags {
+        header.insert(2, Cell::new("Tags").add_attribute(Attribute::Bold));
+    }
+
+    if show_store {
+        header.insert(0, Cell::new("Store").add_attribute(Attribute::Bold));
+    }
+
+    table.set_header(header);
+
+    for (store, profile, metadata) in profiles {
+        let tags = format_tags(&metadata.tags);
+
+        let mut row = vec![
+            profile,
+            metadata.description.unwrap_or("".to_string()),
+            metadata.cipher_kind.as_ref().to_string(),
+            metadata.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            metadata.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        ];
+
+        if show_tags {
+            row.insert(2, tags);
+        }
+
+        if show_store {
+            row.insert(0, store.clone());
+        }
+
+        table.add_row(row);
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+/// the profiles of a store grouped by their namespaces
+#[derive(Default)]
+struct ProfileTree<'a> {
+    /// set if there is a profile with the name of this node, along with its description
+    profile: Option<Option<&'a str>>,
+    children: BTreeMap<&'a str, ProfileTree<'a>>,
+}
+
+impl<'a> ProfileTree<'a> {
+    fn insert(&mut self, name: &'a str, description: Option<&'a str>) {
+        let node = name.split('/').fold(self, |node, segment| {
+            node.children.entry(segment).or_default()
+        });
+
+        node.profile = Some(description);
+    }
+
+    fn print(&self, prefix: &str) {
+        // a name can be a profile and a namespace at the same time, which are listed separately
+        let mut lines = Vec::new();
+        for (segment, node) in &self.children {
+            if node.profile.is_some() {
+                lines.push((*segment, node, false));
+            }
+
+            if !node.children.is_empty() {
+                lines.push((*segment, node, true));
+            }
+        }
+
+        for (index, (segment, node, is_namespace)) in lines.iter().enumerate() {
+            let last = index == lines.len() - 1;
+            let connector = if last { "└── " } else { "├── " };
+
+            if *is_namespace {
+                println!(
+                    "{}{}{}",
+                    prefix,
+                    connector,
+                    format!("{}/", segment).blue().bold()
+                );
+                node.print(&format!("{}{}", prefix, if last { "    " } else { "│   " }));
+                continue;
+            }
+
+            match node.profile.flatten().filter(|d| !d.is_empty()) {
+                Some(description) => println!(
+                    "{}{}{} {}",
+                    prefix,
+                    connector,
+                    segment,
+                    format!("- {}", description).dimmed()
+                ),
+                None => println!("{}{}{}", prefix, connector, segment),
+            }
+        }
+    }
+}
+
+pub fn list_stores(stores: &[(String, PathBuf)]) -> AppResult<()> {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new("Name").add_attribute(Attribute::Bold),
+        Cell::new("Kind").add_attribute(Attribute::Bold),
+        Cell::new("Path").add_attribute(Attribute::Bold),
+        Cell::new("Profiles").add_attribute(Attribute::Bold),
     ]);
 
-    for profile in profiles {
-        let metadata = get_profile_metadata(&profile)?;
+    for (store, dir) in stores {
         table.add_row(vec![
-            &profile,
-            &metadata.description.unwrap_or("".to_string()),
-            metadata.cipher_kind.as_ref(),
-            &metadata.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-            &metadata.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            store.clone(),
+            detect_store_kind(dir).to_string(),
+            dir.display().to_string(),
+            get_profile_names(dir)?.len().to_string(),
         ]);
     }
 
@@ -204,12 +1405,16 @@ pub fn list_profiles(no_pretty_print: bool) -> AppResult<()> {
 }
 
 pub fn download_profile(url: String, profile_name: &str) -> AppResult<()> {
-    let location = build_profile_path(profile_name);
+    let location = build_profile_path(profile_name)?;
 
     if location.exists() {
         return Err(AppError::ProfileExists(profile_name.to_owned()));
     }
 
+    if let Some(dir) = location.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
     let runtime = tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()?;
@@ -235,19 +1440,23 @@ pub fn import_profile(file_path: String, profile_name: &str) -> AppResult<()> {
     let mut contents = String::new();
     file.read_to_string(&mut contents).unwrap();
 
-    let location = build_profile_path(profile_name);
+    let location = build_profile_path(profile_name)?;
 
     if location.exists() {
         return Err(AppError::ProfileExists(profile_name.to_owned()));
     }
 
+    if let Some(dir) = location.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
     std::fs::write(location, contents)?;
 
     Ok(())
 }
 
 #[cfg(target_family = "unix")]
-pub fn create_shellscript(profile: &str) -> AppResult<()> {
+pub fn create_shellscript(profile: &str, on_expired: ExpiredPolicy) -> AppResult<()> {
     let mut file = std::fs::OpenOptions::new()
         .write(true)
         .open(get_shellscript_path())?;
@@ -258,26 +1467,46 @@ pub fn create_shellscript(profile: &str) -> AppResult<()> {
         ));
     }
 
+    // names of profiles created before names were validated may contain spaces and quotes
+    let escaped_profile = shell_escape::escape(profile.into());
+
+    // profiles outside the default store have to be looked up in the same directory later on
+    let profile_dir = get_profile_dir();
+    let show_args = if profile_dir != get_default_profile_dir() {
+        format!(
+            "--profile-dir {} show {}",
+            shell_escape::escape(profile_dir.to_string_lossy()),
+            escaped_profile
+        )
+    } else {
+        format!("show {}", escaped_profile)
+    };
+
     let shellscript = format!(
         r#"#!/bin/bash
 # This script was generated by envio and should not be modified!
 
-raw_output=$(envio show {p} --no-pretty-print)
+raw_output=$(envio {show_args} --no-pretty-print --quote --on-expired {on_expired})
 
 if ! echo "$raw_output" | grep -q "="; then
-    echo -e "\e[31mError: \e[0mFailed to load environment variables from profile '{p}'" >&2
+    printf "\e[31mError: \e[0mFailed to load environment variables from profile '%s'\n" {p} >&2
     return 1 2>/dev/null || exit 1
 fi
 
-ENV_VARS=$(echo "$raw_output" | awk -F "=" '/^[^=]+=.+/ {{print}}')
+ENV_VARS=$(printf '%s\n' "$raw_output" | awk -F "=" '/^[^=]+=.+/ {{print}}')
 
 while IFS= read -r line; do
     var="${{line%%=*}}"
     val="${{line#*=}}"
+    # values spanning several lines are printed in double quotes with escape sequences
+    if [[ "$val" == \"*\" ]]; then
+        val="${{val:1:${{#val}}-2}}"
+        printf -v val '%b' "${{val//\\\"/\"}}"
+    fi
     export "$var"="$val"
 done <<< "$ENV_VARS"
 "#,
-        p = profile,
+        p = escaped_profile,
     );
 
     file.write_all(shellscript.as_bytes())?;
@@ -288,10 +1517,16 @@ done <<< "$ENV_VARS"
 }
 
 #[cfg(target_family = "unix")]
-pub fn load_profile(profile_name: &str) -> AppResult<()> {
+pub fn load_profile(profile_name: &str, on_expired: Option<ExpiredPolicy>) -> AppResult<()> {
     get_profile_path(profile_name)?; // will error if the profile does not exist
 
-    create_shellscript(profile_name)?;
+    // the profile is only decrypted once the shell starts, so its policy is resolved right away
+    let on_expired = match on_expired {
+        Some(policy) => policy,
+        None => get_profile_metadata(profile_name)?.on_expired,
+    };
+
+    create_shellscript(profile_name, on_expired)?;
 
     Ok(())
 }
@@ -0,0 +1,161 @@
+use std::{
+    fs::OpenOptions,
+    io::{Read, Seek, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::error::AppResult;
+
+/// a temporary file only the current user can read, its content is overwritten before it is
+/// removed again
+pub struct PrivateFile {
+    path: PathBuf,
+}
+
+/// prefers memory backed directories so decrypted content never reaches the disk
+fn get_private_dir() -> PathBuf {
+    #[cfg(target_os = "linux")]
+    {
+        let shm = Path::new("/dev/shm");
+        if shm.is_dir() {
+            return shm.to_path_buf();
+        }
+    }
+
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) if Path::new(&dir).is_dir() => PathBuf::from(dir),
+        _ => std::env::temp_dir(),
+    }
+}
+
+impl PrivateFile {
+    pub fn create(name: &str, content: &[u8]) -> AppResult<PrivateFile> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or_default();
+
+        // the name may come from a key, which can contain path separators
+        let name: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        let path =
+            get_private_dir().join(format!("envio-{}-{}-{}", std::process::id(), nanos, name));
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+
+        #[cfg(target_family = "unix")]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = options.open(&path)?;
+        let private_file = PrivateFile { path };
+
+        file.write_all(content)?;
+        file.sync_all()?;
+
+        Ok(private_file)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn wipe(&self) -> std::io::Result<()> {
+        let mut file = OpenOptions::new().write(true).open(&self.path)?;
+        let len = file.metadata()?.len();
+
+        file.rewind()?;
+        std::io::copy(&mut std::io::repeat(0).take(len), &mut file)?;
+        file.sync_all()?;
+
+        Ok(())
+    }
+}
+
+impl Drop for PrivateFile {
+    fn drop(&mut self) {
+        // editors may have replaced the file, in which case there is nothing left to overwrite
+        let _ = self.wipe();
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// a secret handed to a child process as a path. by default it is a `PrivateFile`. on linux it
+/// can instead only live in memory, it is then gone once envio and the child have exited, however
+/// they are stopped, but the child has to be able to open `/dev/fd` paths of descriptors it
+/// inherited
+pub enum SecretFile {
+    #[cfg(target_os = "linux")]
+    Memory(std::fs::File),
+    File(PrivateFile),
+}
+
+impl SecretFile {
+    /// `in_memory` is ignored where memory backed files are not supported
+    pub fn create(name: &str, content: &[u8], in_memory: bool) -> AppResult<SecretFile> {
+        #[cfg(target_os = "linux")]
+        if in_memory && let Some(file) = create_memfd(name, content) {
+            return Ok(SecretFile::Memory(file));
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        let _ = in_memory;
+
+        Ok(SecretFile::File(PrivateFile::create(name, content)?))
+    }
+
+    pub fn path(&self) -> PathBuf {
+        match self {
+            // the child inherits the descriptor under the same number
+            #[cfg(target_os = "linux")]
+            SecretFile::Memory(file) => {
+                use std::os::fd::AsRawFd;
+                PathBuf::from(format!("/dev/fd/{}", file.as_raw_fd()))
+            }
+            SecretFile::File(file) => file.path().to_path_buf(),
+        }
+    }
+}
+
+/// `None` if the kernel does not support memory backed files
+#[cfg(target_os = "linux")]
+fn create_memfd(name: &str, content: &[u8]) -> Option<std::fs::File> {
+    use std::os::{fd::FromRawFd, unix::fs::PermissionsExt};
+
+    let name = std::ffi::CString::new(format!("envio-{}", name)).ok()?;
+
+    // without MFD_CLOEXEC so the descriptor is passed on to the child
+    let fd = unsafe { libc::memfd_create(name.as_ptr(), 0) };
+    if fd < 0 {
+        return None;
+    }
+
+    let mut file = unsafe { std::fs::File::from_raw_fd(fd) };
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))
+        .ok()?;
+    file.write_all(content).ok()?;
+
+    Some(file)
+}
+
+impl Drop for SecretFile {
+    fn drop(&mut self) {
+        #[cfg(target_os = "linux")]
+        if let SecretFile::Memory(file) = self {
+            let _ = file.set_len(0);
+        }
+    }
+}
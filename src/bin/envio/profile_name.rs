@@ -0,0 +1,261 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::error::{AppError, AppResult};
+
+const MAX_SEGMENT_LEN: usize = 255;
+
+/// the name of a profile, which can not point outside of the directory it is stored in.
+/// segments separated by `/` such as `team/service/prod` are stored in subdirectories
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ProfileName(String);
+
+impl ProfileName {
+    pub fn new(name: &str) -> AppResult<ProfileName> {
+        let invalid = |reason: &str| AppError::InvalidProfileName(name.to_string(), reason.into());
+
+        if name.is_empty() {
+            return Err(invalid("it must not be empty"));
+        }
+
+        for segment in name.split('/') {
+            if segment.is_empty() {
+                return Err(invalid("it must not start or end with `/` or contain `//`"));
+            }
+
+            if segment.starts_with('.') {
+                return Err(invalid("its segments must not start with `.`"));
+            }
+
+            if segment.ends_with('.') {
+                return Err(invalid("its segments must not end with `.`"));
+            }
+
+            if segment.len() > MAX_SEGMENT_LEN {
+                return Err(invalid("its segments must not be longer than 255 bytes"));
+            }
+
+            if let Some(c) = segment
+                .chars()
+                .find(|c| !c.is_alphanumeric() && !"-_.@+".contains(*c))
+            {
+                return Err(invalid(&format!(
+                    "it may only contain letters, digits, `-`, `_`, `.`, `@`, `+` and `/` but contains `{}`",
+                    c.escape_default()
+                )));
+            }
+        }
+
+        Ok(ProfileName(name.to_string()))
+    }
+
+    /// the name of a profile that already exists. besides valid names this accepts the names of
+    /// profiles created before names were validated, such as `my profile`, so they can still be
+    /// read, renamed and deleted. they still can not point outside of the store
+    pub fn existing(name: &str) -> AppResult<ProfileName> {
+        ProfileName::new(name).or_else(|e| {
+            if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\', '\0']) {
+                return Err(e);
+            }
+
+            Ok(ProfileName(name.to_string()))
+        })
+    }
+
+    /// whether this is the name of a profile created before names were validated, which can not
+    /// be used for new profiles
+    pub fn is_legacy(&self) -> bool {
+        ProfileName::new(&self.0).is_err()
+    }
+
+    /// the name of the profile stored at `path` within `dir`, `None` if it is not a profile or
+    /// its name would not be valid
+    pub fn from_file_path(dir: &Path, path: &Path) -> Option<ProfileName> {
+        if path.extension().and_then(|s| s.to_str()) != Some("env") {
+            return None;
+        }
+
+        let relative = path.strip_prefix(dir).ok()?.with_extension("");
+        let segments = relative
+            .components()
+            .map(|component| component.as_os_str().to_str())
+            .collect::<Option<Vec<_>>>()?;
+
+        ProfileName::existing(&segments.join("/")).ok()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// whether the profile is selected by `pattern`. `*` and `?` match within a segment and `**`
+    /// any number of segments. `payments/` selects everything in the `payments` namespace, as
+    /// does `payments` along with the profile of that name
+    pub fn matches(&self, pattern: &str) -> bool {
+        let pattern = match pattern.strip_suffix('/') {
+            Some(namespace) => format!("{}/**", namespace),
+            None if !pattern.contains(['*', '?']) => format!("{}/**", pattern),
+            None => pattern.to_string(),
+        };
+
+        let pattern: Vec<&str> = pattern.split('/').collect();
+        let name: Vec<&str> = self.0.split('/').collect();
+
+        match_segments(&pattern, &name)
+    }
+
+    pub fn file_path(&self, dir: &Path) -> PathBuf {
+        // the extension is appended since names may contain `.` themselves
+        let mut path = dir.to_path_buf();
+        path.extend(self.0.split('/'));
+        path.as_mut_os_string().push(".env");
+        path
+    }
+}
+
+fn match_segments(pattern: &[&str], name: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((&"**", rest)) => (0..=name.len()).any(|skip| match_segments(rest, &name[skip..])),
+        Some((segment, rest)) => name.split_first().is_some_and(|(first, name_rest)| {
+            match_glob(segment, first) && match_segments(rest, name_rest)
+        }),
+    }
+}
+
+fn match_glob(pattern: &str, s: &str) -> bool {
+    let mut chars = pattern.chars();
+
+    match chars.next() {
+        None => s.is_empty(),
+        Some('*') => s
+            .char_indices()
+            .map(|(index, _)| index)
+            .chain(std::iter::once(s.len()))
+            .any(|index| match_glob(chars.as_str(), &s[index..])),
+        Some('?') => {
+            let mut s_chars = s.chars();
+            s_chars.next().is_some() && match_glob(chars.as_str(), s_chars.as_str())
+        }
+        Some(c) => s
+            .strip_prefix(c)
+            .is_some_and(|rest| match_glob(chars.as_str(), rest)),
+    }
+}
+
+impl FromStr for ProfileName {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> AppResult<ProfileName> {
+        ProfileName::new(s)
+    }
+}
+
+impl AsRef<str> for ProfileName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for ProfileName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_valid_names() {
+        for name in [
+            "prod",
+            "team/service/prod",
+            "a.b",
+            "user@host",
+            "v1+2",
+            "my-app_2",
+        ] {
+            assert!(ProfileName::new(name).is_ok(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn rejects_names_pointing_outside_the_store() {
+        for name in [
+            "../x",
+            "x/../y",
+            "..",
+            ".",
+            "./x",
+            "/x",
+            "x/",
+            "a//b",
+            "/etc/passwd",
+        ] {
+            assert!(ProfileName::new(name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_segments() {
+        for name in ["", ".hidden", "x.", "a\\b", "a b", "a\0b", &"a".repeat(256)] {
+            assert!(ProfileName::new(name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn accepts_legacy_names_of_existing_profiles() {
+        for name in ["my profile", "a\tb", "x.", "prod!"] {
+            assert!(ProfileName::new(name).is_err(), "{}", name);
+            assert!(ProfileName::existing(name).unwrap().is_legacy(), "{}", name);
+        }
+
+        assert!(!ProfileName::existing("team/prod").unwrap().is_legacy());
+
+        for name in ["", ".", "..", ".hidden", "../x", "a/b c", "a\\b", "a\0b"] {
+            assert!(ProfileName::existing(name).is_err(), "{}", name);
+        }
+
+        let dir = Path::new("/profiles");
+        assert_eq!(
+            ProfileName::from_file_path(dir, Path::new("/profiles/my profile.env")),
+            Some(ProfileName::existing("my profile").unwrap())
+        );
+    }
+
+    #[test]
+    fn maps_names_to_file_paths_and_back() {
+        let dir = Path::new("/profiles");
+        let name = ProfileName::new("team/v1.2").unwrap();
+        let path = name.file_path(dir);
+
+        assert_eq!(path, Path::new("/profiles/team/v1.2.env"));
+        assert_eq!(ProfileName::from_file_path(dir, &path), Some(name));
+        assert_eq!(
+            ProfileName::from_file_path(dir, Path::new("/profiles/.git/x.env")),
+            None
+        );
+        assert_eq!(
+            ProfileName::from_file_path(dir, Path::new("/profiles/notes.txt")),
+            None
+        );
+    }
+
+    #[test]
+    fn matches_patterns() {
+        let name = ProfileName::new("payments/api/prod").unwrap();
+
+        assert!(name.matches("payments/"));
+        assert!(name.matches("payments"));
+        assert!(name.matches("payments/*/prod"));
+        assert!(name.matches("**/prod"));
+        assert!(name.matches("payments/api/pr?d"));
+        assert!(!name.matches("pay"));
+        assert!(!name.matches("*/prod"));
+        assert!(!name.matches("billing/"));
+    }
+}
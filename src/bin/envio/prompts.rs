@@ -1,6 +1,8 @@
 use std::{env, path::Path};
 
-use inquire::{DateSelect, MultiSelect, Password, PasswordDisplayMode, Select, Text, min_length};
+use inquire::{
+    Confirm, DateSelect, MultiSelect, Password, PasswordDisplayMode, Select, Text, min_length,
+};
 use regex::Regex;
 
 use crate::error::AppResult;
@@ -49,6 +51,17 @@ where
         .prompt()?)
 }
 
+pub struct ConfirmPromptOptions {
+    pub title: String,
+    pub default: bool,
+}
+
+pub fn confirm_prompt(options: ConfirmPromptOptions) -> AppResult<bool> {
+    Ok(Confirm::new(&options.title)
+        .with_default(options.default)
+        .prompt()?)
+}
+
 pub struct TextPromptOptions {
     pub title: String,
     pub default: Option<String>,
@@ -0,0 +1,386 @@
+use std::{fs::OpenOptions, io::Write, path::Path};
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+use envio::EnvMap;
+use indexmap::IndexSet;
+use zeroize::Zeroizing;
+
+use crate::error::{AppError, AppResult};
+
+/// transforms the value of a placeholder, applied from left to right
+enum Filter {
+    Base64,
+    JsonEscape,
+    /// used when the key is not in the profile or its value is empty
+    Default(String),
+}
+
+struct Placeholder {
+    key: String,
+    filters: Vec<Filter>,
+}
+
+enum Part<'a> {
+    Text(&'a str),
+    Placeholder(Placeholder),
+}
+
+/// a config file with `{{ KEY }}` placeholders, optionally followed by filters such as
+/// `{{ KEY | default("value") | base64 }}`. `\{{` is written as a literal `{{`
+pub struct Template<'a> {
+    parts: Vec<Part<'a>>,
+}
+
+fn line_number(source: &str, offset: usize) -> usize {
+    source[..offset].matches('\n').count() + 1
+}
+
+/// splits `s` at every `|` that is not inside a double quoted string
+fn split_filters(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (index, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '|' if !quoted => {
+                parts.push(&s[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(&s[start..]);
+    parts
+}
+
+fn parse_string(s: &str) -> Option<String> {
+    let mut chars = s.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut string = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                'n' => string.push('\n'),
+                't' => string.push('\t'),
+                c @ ('\\' | '"') => string.push(c),
+                _ => return None,
+            },
+            '"' => return None,
+            c => string.push(c),
+        }
+    }
+
+    Some(string)
+}
+
+fn parse_filter(filter: &str) -> Result<Filter, String> {
+    match filter {
+        "base64" => Ok(Filter::Base64),
+        "json-escape" => Ok(Filter::JsonEscape),
+        _ => {
+            let argument = filter
+                .strip_prefix("default")
+                .map(str::trim_start)
+                .and_then(|rest| rest.strip_prefix('('))
+                .and_then(|rest| rest.strip_suffix(')'))
+                .ok_or_else(|| {
+                    format!(
+                        "unknown filter `{}`, expected base64, json-escape or default(\"VALUE\")",
+                        filter
+                    )
+                })?;
+
+            parse_string(argument.trim())
+                .map(Filter::Default)
+                .ok_or_else(|| "the value of default must be a double quoted string".to_string())
+        }
+    }
+}
+
+fn parse_placeholder(expression: &str) -> Result<Placeholder, String> {
+    let mut parts = split_filters(expression).into_iter().map(str::trim);
+    let key = parts.next().unwrap_or_default();
+
+    if key.is_empty() {
+        return Err("the placeholder has no key".to_string());
+    }
+
+    if key.contains(char::is_whitespace) {
+        return Err(format!("`{}` is not a valid key", key));
+    }
+
+    Ok(Placeholder {
+        key: key.to_string(),
+        filters: parts.map(parse_filter).collect::<Result<_, _>>()?,
+    })
+}
+
+impl<'a> Template<'a> {
+    pub fn parse(source: &'a str) -> AppResult<Template<'a>> {
+        let mut parts = Vec::new();
+        let mut offset = 0;
+
+        while let Some(start) = source[offset..].find("{{").map(|index| offset + index) {
+            if source[..start].ends_with('\\') {
+                parts.push(Part::Text(&source[offset..start - 1]));
+                parts.push(Part::Text("{{"));
+                offset = start + 2;
+                continue;
+            }
+
+            let invalid = |reason: String| {
+                AppError::Msg(format!("Line {}: {}", line_number(source, start), reason))
+            };
+
+            let end = source[start..]
+                .find("}}")
+                .map(|index| start + index)
+                .ok_or_else(|| invalid("`{{` is missing its closing `}}`".to_string()))?;
+
+            parts.push(Part::Text(&source[offset..start]));
+            parts.push(Part::Placeholder(
+                parse_placeholder(&source[start + 2..end]).map_err(invalid)?,
+            ));
+
+            offset = end + 2;
+        }
+
+        parts.push(Part::Text(&source[offset..]));
+
+        Ok(Template { parts })
+    }
+
+    /// the keys used by the template, so only their values have to be decrypted
+    pub fn keys(&self) -> IndexSet<String> {
+        self.parts
+            .iter()
+            .filter_map(|part| match part {
+                Part::Placeholder(placeholder) => Some(placeholder.key.clone()),
+                Part::Text(_) => None,
+            })
+            .collect()
+    }
+
+    /// fills in the placeholders, along with the keys that are not in `envs` and have no default.
+    /// they are left empty unless `strict` is set, in which case rendering fails
+    pub fn render(
+        &self,
+        envs: &EnvMap,
+        strict: bool,
+    ) -> AppResult<(Zeroizing<String>, IndexSet<String>)> {
+        let mut output = Zeroizing::new(String::new());
+        let mut missing = IndexSet::new();
+
+        for part in &self.parts {
+            let placeholder = match part {
+                Part::Text(text) => {
+                    output.push_str(text);
+                    continue;
+                }
+                Part::Placeholder(placeholder) => placeholder,
+            };
+
+            let mut value = envs
+                .get(&placeholder.key)
+                .map(|env| Zeroizing::new(env.value.clone()));
+
+            for filter in &placeholder.filters {
+                value = match (filter, value) {
+                    (Filter::Default(default), value)
+                        if value.as_ref().is_none_or(|value| value.is_empty()) =>
+                    {
+                        Some(Zeroizing::new(default.clone()))
+                    }
+                    (Filter::Base64, Some(value)) => {
+                        Some(Zeroizing::new(BASE64_STANDARD.encode(value.as_bytes())))
+                    }
+                    (Filter::JsonEscape, Some(value)) => {
+                        let quoted = Zeroizing::new(serde_json::to_string(value.as_str())?);
+                        Some(Zeroizing::new(quoted[1..quoted.len() - 1].to_string()))
+                    }
+                    (_, value) => value,
+                };
+            }
+
+            match value {
+                Some(value) => output.push_str(&value),
+                None => {
+                    missing.insert(placeholder.key.clone());
+                }
+            }
+        }
+
+        if strict && !missing.is_empty() {
+            return Err(AppError::Msg(format!(
+                "The profile does not have {}",
+                missing
+                    .iter()
+                    .map(|key| format!("`{}`", key))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+
+        Ok((output, missing))
+    }
+}
+
+/// writes `content` to `path` so that only the current user can read it. the content goes to a
+/// new file next to `path` first, which then replaces it, so an existing file or a symlink at
+/// `path` is never written through
+pub fn write_private(path: &Path, content: &str) -> AppResult<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| AppError::Msg(format!("`{}` is not a file path", path.display())))?;
+
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+    let temp_path = path.with_file_name(temp_name);
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(target_family = "unix")]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(&temp_path)?;
+
+    let written = file
+        .write_all(content.as_bytes())
+        .and_then(|_| file.sync_all())
+        .and_then(|_| std::fs::rename(&temp_path, path));
+
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use envio::Env;
+
+    use super::*;
+
+    fn envs() -> EnvMap {
+        EnvMap::from(vec![
+            Env::from_key_value("HOST", "db.internal"),
+            Env::from_key_value("PASSWORD", "p\"a\\ss\nword"),
+            Env::from_key_value("EMPTY", ""),
+        ])
+    }
+
+    fn render(source: &str) -> (String, Vec<String>) {
+        let (output, missing) = Template::parse(source)
+            .unwrap()
+            .render(&envs(), false)
+            .unwrap();
+
+        (output.to_string(), missing.into_iter().collect())
+    }
+
+    fn parse_error(source: &str) -> String {
+        Template::parse(source).err().unwrap().to_string()
+    }
+
+    #[test]
+    fn fills_in_placeholders() {
+        assert_eq!(
+            render("host: {{HOST}}\nurl: https://{{ HOST }}:5432\n").0,
+            "host: db.internal\nurl: https://db.internal:5432\n"
+        );
+    }
+
+    #[test]
+    fn applies_filters_from_left_to_right() {
+        assert_eq!(render("{{ HOST | base64 }}").0, "ZGIuaW50ZXJuYWw=");
+        assert_eq!(
+            render(r#""{{ PASSWORD | json-escape }}""#).0,
+            r#""p\"a\\ss\nword""#
+        );
+        assert_eq!(render(r#"{{ EMPTY | default("x") | base64 }}"#).0, "eA==");
+        assert_eq!(render(r#"{{ EMPTY | base64 | default("x") }}"#).0, "x");
+    }
+
+    #[test]
+    fn uses_defaults_for_missing_and_empty_values() {
+        assert_eq!(
+            render(
+                r#"{{ MISSING | default("a | \"b\"") }}/{{ EMPTY | default("c") }}/{{ HOST | default("d") }}"#
+            ),
+            ("a | \"b\"/c/db.internal".to_string(), vec![])
+        );
+    }
+
+    #[test]
+    fn leaves_missing_keys_empty_unless_strict() {
+        assert_eq!(
+            render("[{{ MISSING }}][{{ EMPTY }}][{{ OTHER }}]"),
+            (
+                "[][][]".to_string(),
+                vec!["MISSING".to_string(), "OTHER".to_string()]
+            )
+        );
+
+        let error = Template::parse("{{ MISSING }}{{ OTHER }}")
+            .unwrap()
+            .render(&envs(), true)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "The profile does not have `MISSING`, `OTHER`"
+        );
+    }
+
+    #[test]
+    fn lists_the_keys_it_uses() {
+        let template = Template::parse("{{ A }} {{ B | base64 }} {{ A }}").unwrap();
+
+        assert_eq!(template.keys().into_iter().collect::<Vec<_>>(), ["A", "B"]);
+    }
+
+    #[test]
+    fn writes_escaped_braces_as_they_are() {
+        assert_eq!(
+            render("\\{{ HOST }} {{ HOST }} \\{{ }}").0,
+            "{{ HOST }} db.internal {{ }}"
+        );
+        assert_eq!(render("a \\{{ HOST").0, "a {{ HOST");
+        assert!(
+            Template::parse("\\{{ A }} {{ B }}")
+                .unwrap()
+                .keys()
+                .into_iter()
+                .eq(["B"])
+        );
+    }
+
+    #[test]
+    fn reports_invalid_placeholders() {
+        assert_eq!(
+            parse_error("ok\n{{ HOST"),
+            "Line 2: `{{` is missing its closing `}}`"
+        );
+        assert_eq!(parse_error("{{ }}"), "Line 1: the placeholder has no key");
+        assert_eq!(parse_error("{{ A B }}"), "Line 1: `A B` is not a valid key");
+        assert!(parse_error("{{ A | upper }}").starts_with("Line 1: unknown filter `upper`"));
+        assert_eq!(
+            parse_error("{{ A | default(x) }}"),
+            "Line 1: the value of default must be a double quoted string"
+        );
+    }
+}
@@ -0,0 +1,286 @@
+use std::path::Path;
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+use envio::{EnvMap, Schema, SchemaRule, ValueType};
+use indexmap::IndexMap;
+use regex::Regex;
+use url::Url;
+
+use crate::{
+    error::{AppError, AppResult},
+    error_msg,
+};
+
+/// the schema of a project, picked up from the current directory
+pub const PROJECT_SCHEMA_FILE: &str = "envio.schema.json";
+
+/// the fields a rule may have in a schema file
+const RULE_FIELDS: [&str; 5] = ["required", "type", "pattern", "allowed", "description"];
+
+/// an environment variable that does not meet its rule. the problem never includes the value
+pub struct Violation {
+    pub key: String,
+    pub problem: String,
+}
+
+pub fn read_schema_file(path: &Path) -> AppResult<Schema> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AppError::Msg(format!("Failed to read `{}`: {}", path.display(), e)))?;
+
+    let invalid = |e: &dyn std::fmt::Display| {
+        AppError::Msg(format!("Invalid schema `{}`: {}", path.display(), e))
+    };
+
+    // stored rules ignore fields they do not know, a schema file is written by hand so a typo
+    // in one of its fields should not go unnoticed
+    let rules: IndexMap<String, serde_json::Map<String, serde_json::Value>> =
+        serde_json::from_str(&content).map_err(|e| invalid(&e))?;
+
+    for (key, rule) in &rules {
+        if let Some(field) = rule
+            .keys()
+            .find(|field| !RULE_FIELDS.contains(&field.as_str()))
+        {
+            return Err(invalid(&format!(
+                "unknown field `{}` in the rule for `{}`, expected one of {}",
+                field,
+                key,
+                RULE_FIELDS.join(", ")
+            )));
+        }
+    }
+
+    let schema: Schema = serde_json::from_str(&content).map_err(|e| invalid(&e))?;
+
+    check_schema(&schema)?;
+
+    Ok(schema)
+}
+
+/// makes sure the patterns of a schema are valid regular expressions
+pub fn check_schema(schema: &Schema) -> AppResult<()> {
+    for (key, rule) in schema {
+        compile_pattern(key, rule)?;
+    }
+
+    Ok(())
+}
+
+fn compile_pattern(key: &str, rule: &SchemaRule) -> AppResult<Option<Regex>> {
+    rule.pattern
+        .as_ref()
+        .map(|pattern| {
+            Regex::new(pattern)
+                .map_err(|e| AppError::Msg(format!("Invalid pattern for `{}`: {}", key, e)))?;
+
+            // the pattern has to match the whole value, not only a part of it
+            Ok(Regex::new(&format!("^(?:{})$", pattern)).expect("the pattern is valid"))
+        })
+        .transpose()
+}
+
+/// the schema that applies to a profile along with where it comes from. a schema file given on
+/// the command line comes first, then the project schema in the current directory and last the
+/// schema stored with the profile
+pub fn find_schema(path: Option<&str>, stored: &Schema) -> AppResult<Option<(Schema, String)>> {
+    if let Some(path) = path {
+        return Ok(Some((read_schema_file(Path::new(path))?, path.to_string())));
+    }
+
+    let project = Path::new(PROJECT_SCHEMA_FILE);
+    if project.is_file() {
+        return Ok(Some((
+            read_schema_file(project)?,
+            PROJECT_SCHEMA_FILE.to_string(),
+        )));
+    }
+
+    if stored.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some((stored.clone(), "the profile".to_string())))
+}
+
+pub fn validate(schema: &Schema, envs: &EnvMap) -> AppResult<Vec<Violation>> {
+    let mut violations = Vec::new();
+
+    for (key, rule) in schema {
+        let pattern = compile_pattern(key, rule)?;
+        let mut violation = |problem: String| {
+            violations.push(Violation {
+                key: key.clone(),
+                problem,
+            })
+        };
+
+        let value = match envs.get(key) {
+            Some(env) if !env.value.is_empty() => &env.value,
+            Some(_) if rule.required => {
+                violation("is required but empty".to_string());
+                continue;
+            }
+            None if rule.required => {
+                violation("is required but missing".to_string());
+                continue;
+            }
+            _ => continue,
+        };
+
+        if !is_valid(rule.value_type, value) {
+            violation(format!("is not a valid {}", rule.value_type));
+        }
+
+        if pattern.is_some_and(|pattern| !pattern.is_match(value)) {
+            violation(format!(
+                "does not match the pattern `{}`",
+                rule.pattern.as_deref().unwrap_or_default()
+            ));
+        }
+
+        if !rule.allowed.is_empty() && !rule.allowed.contains(value) {
+            violation(format!("must be one of {}", rule.allowed.join(", ")));
+        }
+    }
+
+    Ok(violations)
+}
+
+fn is_valid(value_type: ValueType, value: &str) -> bool {
+    match value_type {
+        ValueType::String => true,
+        ValueType::Url => Url::parse(value).is_ok(),
+        ValueType::Int => value.parse::<i64>().is_ok(),
+        ValueType::Port => value.parse::<u16>().is_ok_and(|port| port != 0),
+        ValueType::Bool => matches!(
+            value.to_ascii_lowercase().as_str(),
+            "true" | "false" | "1" | "0" | "yes" | "no" | "on" | "off"
+        ),
+        ValueType::Base64 => BASE64_STANDARD.decode(value).is_ok(),
+    }
+}
+
+pub fn print_violations(violations: &[Violation]) {
+    for violation in violations {
+        error_msg!("`{}` {}", violation.key, violation.problem);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use envio::Env;
+
+    use super::*;
+
+    fn schema(json: &str) -> Schema {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn problems(schema: &Schema, envs: &[(&str, &str)]) -> Vec<String> {
+        let envs = EnvMap::from(
+            envs.iter()
+                .map(|(key, value)| Env::from_key_value(*key, *value))
+                .collect::<Vec<_>>(),
+        );
+
+        validate(schema, &envs)
+            .unwrap()
+            .into_iter()
+            .map(|violation| format!("{} {}", violation.key, violation.problem))
+            .collect()
+    }
+
+    #[test]
+    fn reports_missing_and_empty_variables() {
+        let schema = schema(r#"{"A": {}, "B": {}, "C": {"required": false}}"#);
+
+        assert_eq!(
+            problems(&schema, &[("B", "")]),
+            ["A is required but missing", "B is required but empty"]
+        );
+        assert!(problems(&schema, &[("A", "1"), ("B", "2"), ("C", "")]).is_empty());
+    }
+
+    #[test]
+    fn checks_value_types() {
+        for (value_type, valid, invalid) in [
+            ("url", "https://example.com/path", "example.com"),
+            ("int", "-42", "4.2"),
+            ("port", "8080", "0"),
+            ("port", "65535", "65536"),
+            ("bool", "Yes", "maybe"),
+            ("base64", "aGVsbG8=", "aGVsbG8"),
+        ] {
+            let schema = schema(&format!(r#"{{"A": {{"type": "{}"}}}}"#, value_type));
+
+            assert!(problems(&schema, &[("A", valid)]).is_empty(), "{}", valid);
+            assert_eq!(
+                problems(&schema, &[("A", invalid)]),
+                [format!("A is not a valid {}", value_type)]
+            );
+        }
+    }
+
+    #[test]
+    fn matches_the_whole_value_against_the_pattern() {
+        let schema = schema(r#"{"A": {"pattern": "[a-z]+|[0-9]+"}}"#);
+
+        assert!(problems(&schema, &[("A", "abc")]).is_empty());
+        assert!(problems(&schema, &[("A", "123")]).is_empty());
+        assert_eq!(
+            problems(&schema, &[("A", "abc123")]),
+            ["A does not match the pattern `[a-z]+|[0-9]+`"]
+        );
+    }
+
+    #[test]
+    fn checks_allowed_values() {
+        let schema = schema(r#"{"A": {"allowed": ["dev", "prod"]}}"#);
+
+        assert!(problems(&schema, &[("A", "prod")]).is_empty());
+        assert_eq!(
+            problems(&schema, &[("A", "staging")]),
+            ["A must be one of dev, prod"]
+        );
+    }
+
+    #[test]
+    fn reports_every_problem_of_a_value() {
+        let schema = schema(r#"{"A": {"type": "int", "pattern": "[0-9]", "allowed": ["1", "2"]}}"#);
+
+        assert_eq!(problems(&schema, &[("A", "x")]).len(), 3);
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        let schema = schema(r#"{"A": {"pattern": "("}}"#);
+
+        assert!(check_schema(&schema).is_err());
+        assert!(validate(&schema, &EnvMap::default()).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_fields_in_schema_files() {
+        let path = std::env::temp_dir().join(format!(
+            "envio-test-{}-envio.schema.json",
+            std::process::id()
+        ));
+
+        std::fs::write(
+            &path,
+            r#"{"A": {"type": "port"}, "B": {"requried": false}}"#,
+        )
+        .unwrap();
+        let error = read_schema_file(&path).err().map(|e| e.to_string());
+        std::fs::write(&path, r#"{"A": {"type": "port"}}"#).unwrap();
+        let schema = read_schema_file(&path).map_err(|e| e.to_string());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(
+            error
+                .unwrap()
+                .contains("unknown field `requried` in the rule for `B`")
+        );
+        assert_eq!(schema.unwrap()["A"].value_type, ValueType::Port);
+    }
+}
@@ -0,0 +1,166 @@
+use envio::Tags;
+
+use crate::error::{AppError, AppResult};
+
+/// parses a tag given as `NAME=VALUE`, or only `NAME` for a tag without a value
+pub fn parse_tag(tag: &str) -> AppResult<(String, String)> {
+    let (name, value) = tag.split_once('=').unwrap_or((tag, ""));
+    let (name, value) = (name.trim(), value.trim());
+
+    let invalid = |reason: &str| AppError::Msg(format!("`{}` is not a valid tag, {}", tag, reason));
+
+    if name.is_empty() {
+        return Err(invalid("its name must not be empty"));
+    }
+
+    if let Some(c) = name
+        .chars()
+        .find(|c| !c.is_alphanumeric() && !"-_.:/".contains(*c))
+    {
+        return Err(invalid(&format!(
+            "its name may only contain letters, digits, `-`, `_`, `.`, `:` and `/` but contains `{}`",
+            c.escape_default()
+        )));
+    }
+
+    // tags are written as a comma separated list on a single line when editing a profile
+    if value.contains([',', '\n', '\r']) {
+        return Err(invalid("its value must not contain `,` or line breaks"));
+    }
+
+    Ok((name.to_string(), value.to_string()))
+}
+
+pub fn parse_tags<S: AsRef<str>>(tags: &[S]) -> AppResult<Tags> {
+    tags.iter().map(|tag| parse_tag(tag.as_ref())).collect()
+}
+
+/// parses tags written by `format_tags`
+pub fn parse_tag_list(list: &str) -> AppResult<Tags> {
+    parse_tags(
+        &list
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .collect::<Vec<_>>(),
+    )
+}
+
+pub fn format_tags(tags: &Tags) -> String {
+    tags.iter()
+        .map(|(name, value)| {
+            if value.is_empty() {
+                name.clone()
+            } else {
+                format!("{}={}", name, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// selects what has all of the given tags, `NAME` only requires the tag to be present while
+/// `NAME=VALUE` also requires its value to match
+pub struct TagFilter(Vec<(String, Option<String>)>);
+
+impl TagFilter {
+    pub fn new<S: AsRef<str>>(filters: &[S]) -> AppResult<TagFilter> {
+        filters
+            .iter()
+            .map(|filter| {
+                let filter = filter.as_ref();
+                let (name, _) = parse_tag(filter)?;
+                let value = filter
+                    .split_once('=')
+                    .map(|(_, value)| value.trim().to_string());
+
+                Ok((name, value))
+            })
+            .collect::<AppResult<Vec<_>>>()
+            .map(TagFilter)
+    }
+
+    pub fn matches(&self, tags: &Tags) -> bool {
+        self.0
+            .iter()
+            .all(|(name, value)| match (tags.get(name), value) {
+                (Some(tag), Some(value)) => tag == value,
+                (Some(_), None) => true,
+                (None, _) => false,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(tags: &[&str]) -> Tags {
+        parse_tags(tags).unwrap()
+    }
+
+    #[test]
+    fn parses_tags() {
+        assert_eq!(
+            parse_tag(" owner = platform ").unwrap(),
+            ("owner".to_string(), "platform".to_string())
+        );
+        assert_eq!(
+            parse_tag("team/app:tier=a=b").unwrap(),
+            ("team/app:tier".to_string(), "a=b".to_string())
+        );
+        assert_eq!(
+            parse_tag("critical").unwrap(),
+            ("critical".to_string(), String::new())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_tags() {
+        for tag in [
+            "",
+            "=value",
+            "has space=1",
+            "name!=1",
+            "list=a,b",
+            "line=a\nb",
+        ] {
+            assert!(parse_tag(tag).is_err(), "{:?}", tag);
+        }
+    }
+
+    #[test]
+    fn parses_the_tags_it_formatted() {
+        let tags = tags(&["owner=platform", "critical", "tier=1"]);
+        let formatted = format_tags(&tags);
+
+        assert_eq!(formatted, "critical, owner=platform, tier=1");
+        assert_eq!(parse_tag_list(&formatted).unwrap(), tags);
+        assert!(parse_tag_list(" , ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn filters_by_tags() {
+        let tags = tags(&["owner=platform", "critical"]);
+
+        assert!(TagFilter::new::<&str>(&[]).unwrap().matches(&tags));
+        assert!(TagFilter::new(&["owner"]).unwrap().matches(&tags));
+        assert!(TagFilter::new(&["owner=platform"]).unwrap().matches(&tags));
+        assert!(
+            TagFilter::new(&["owner=platform", "critical"])
+                .unwrap()
+                .matches(&tags)
+        );
+        assert!(!TagFilter::new(&["owner=payments"]).unwrap().matches(&tags));
+        assert!(!TagFilter::new(&["owner", "tier"]).unwrap().matches(&tags));
+
+        // `NAME=` requires the tag to be present without a value
+        assert!(TagFilter::new(&["critical="]).unwrap().matches(&tags));
+        assert!(!TagFilter::new(&["owner="]).unwrap().matches(&tags));
+    }
+
+    #[test]
+    fn rejects_invalid_filters() {
+        assert!(TagFilter::new(&["bad name"]).is_err());
+    }
+}
@@ -0,0 +1,166 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use envio::{store::get_profile_store, utils::get_serialized_profile};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{AppError, AppResult},
+    utils::get_configdir,
+};
+
+/// a deleted profile, kept next to a copy of its file until it is restored or purged
+#[derive(Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: String,
+    pub name: String,
+    pub original_path: PathBuf,
+    pub deleted_at: DateTime<Local>,
+}
+
+/// lives outside of every store, so deleted profiles are not synced or committed anywhere
+pub fn get_trash_dir() -> PathBuf {
+    get_configdir().join("trash")
+}
+
+impl TrashEntry {
+    fn profile_path(&self) -> PathBuf {
+        get_trash_dir().join(format!("{}.env", self.id))
+    }
+
+    fn info_path(&self) -> PathBuf {
+        get_trash_dir().join(format!("{}.json", self.id))
+    }
+
+    fn remove_files(&self) -> AppResult<()> {
+        std::fs::remove_file(self.profile_path())?;
+        std::fs::remove_file(self.info_path())?;
+
+        Ok(())
+    }
+}
+
+/// copies a profile into the trash, removing it from its store is up to the caller
+pub fn add(profile_name: &str, profile_path: &Path) -> AppResult<TrashEntry> {
+    let trash_dir = get_trash_dir();
+    std::fs::create_dir_all(&trash_dir)?;
+
+    let deleted_at = Local::now();
+    // profiles in subdirectories are flattened, their name is kept in the entry
+    let base_id = format!(
+        "{}-{}",
+        profile_name.replace('/', "."),
+        deleted_at.format("%Y%m%d%H%M%S")
+    );
+
+    let mut id = base_id.clone();
+    let mut counter = 1;
+    while trash_dir.join(format!("{}.json", id)).exists() {
+        counter += 1;
+        id = format!("{}-{}", base_id, counter);
+    }
+
+    let entry = TrashEntry {
+        id,
+        name: profile_name.to_string(),
+        original_path: std::path::absolute(profile_path)?,
+        deleted_at,
+    };
+
+    std::fs::copy(profile_path, entry.profile_path())?;
+    std::fs::write(entry.info_path(), serde_json::to_string_pretty(&entry)?)?;
+
+    Ok(entry)
+}
+
+/// every entry in the trash, most recently deleted first
+pub fn list() -> AppResult<Vec<TrashEntry>> {
+    let trash_dir = get_trash_dir();
+    let mut entries = Vec::new();
+
+    if !trash_dir.exists() {
+        return Ok(entries);
+    }
+
+    for file in std::fs::read_dir(trash_dir)? {
+        let path = file?.path();
+
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+
+        let entry: TrashEntry = serde_json::from_slice(&std::fs::read(&path)?)?;
+
+        if entry.profile_path().exists() {
+            entries.push(entry);
+        }
+    }
+
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.deleted_at));
+
+    Ok(entries)
+}
+
+/// finds an entry by its id, or the most recently deleted profile with that name
+pub fn find(id_or_name: &str) -> AppResult<TrashEntry> {
+    let mut entries = list()?;
+
+    let index = entries
+        .iter()
+        .position(|entry| entry.id == id_or_name)
+        .or_else(|| entries.iter().position(|entry| entry.name == id_or_name))
+        .ok_or_else(|| {
+            AppError::Msg(format!(
+                "There is nothing named `{}` in the trash",
+                id_or_name
+            ))
+        })?;
+
+    Ok(entries.swap_remove(index))
+}
+
+/// moves a profile back to where it was deleted from
+pub fn restore(entry: &TrashEntry) -> AppResult<()> {
+    if entry.original_path.exists() {
+        return Err(AppError::ProfileExists(entry.name.clone()));
+    }
+
+    if !entry.original_path.parent().is_some_and(|dir| dir.exists()) {
+        return Err(AppError::Msg(format!(
+            "`{}` can not be restored, the directory it was deleted from no longer exists",
+            entry.name
+        )));
+    }
+
+    // saved through the store so git backed stores commit the restored profile
+    get_profile_store(&entry.original_path, &entry.name).save_profile(
+        &entry.original_path,
+        get_serialized_profile(entry.profile_path())?,
+    )?;
+
+    entry.remove_files()
+}
+
+pub fn remove(entry: &TrashEntry) -> AppResult<()> {
+    entry.remove_files()
+}
+
+/// deletes everything that has been in the trash for longer than `retention_days`, a retention
+/// of 0 keeps everything. returns how many entries were purged
+pub fn purge(retention_days: u32) -> AppResult<usize> {
+    if retention_days == 0 {
+        return Ok(0);
+    }
+
+    let cutoff = Local::now() - chrono::Duration::days(retention_days.into());
+    let mut purged = 0;
+
+    for entry in list()? {
+        if entry.deleted_at < cutoff {
+            entry.remove_files()?;
+            purged += 1;
+        }
+    }
+
+    Ok(purged)
+}
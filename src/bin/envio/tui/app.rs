@@ -90,10 +90,10 @@ impl TuiApp {
 
     fn handle_screen_event(&mut self, event: ScreenEvent) -> AppResult<()> {
         match event {
-            ScreenEvent::ProfileDecrypted(profile) => {
+            ScreenEvent::ProfileDecrypted(profile, next) => {
                 let name = profile.metadata.name.clone();
-                self.ctx.cache.insert_profile(name.clone(), profile);
-                self.navigation.push(ScreenId::Edit(name))?;
+                self.ctx.cache.insert_profile(name, profile);
+                self.navigation.push(next)?;
             }
 
             ScreenEvent::ProfileUpdated(profile) => {
@@ -117,9 +117,11 @@ impl TuiApp {
     fn handle_key(&mut self, key: KeyEvent) -> AppResult<()> {
         match self.current_screen.handle_key_event(key)? {
             Action::Exit => self.exit = true,
-            Action::OpenProfile(name) => self.open_profile(&name)?,
+            Action::OpenProfile(name) => self.open_profile(&name, ScreenId::Edit(name.clone()))?,
             Action::NewProfile => self.navigation.push(ScreenId::CreateProfile)?,
-            Action::EditProfile(name) => self.navigation.push(ScreenId::EditProfile(name))?,
+            Action::EditProfile(name) => {
+                self.open_profile(&name, ScreenId::EditProfile(name.clone()))?
+            }
             Action::Back => {
                 let _ = self.navigation.pop();
             }
@@ -128,30 +130,31 @@ impl TuiApp {
         Ok(())
     }
 
-    fn open_profile(&mut self, name: &str) -> AppResult<()> {
+    /// decrypts a profile, asking for its key if needed, and opens `next` for it
+    fn open_profile(&mut self, name: &str, next: ScreenId) -> AppResult<()> {
         let metadata = get_profile_metadata(name)?;
 
         match metadata.cipher_kind {
             envio::cipher::CipherKind::PASSPHRASE | envio::cipher::CipherKind::AGE => {
                 if self.ctx.cache.has_profile(name) {
-                    self.navigation.push(ScreenId::Edit(name.to_string()))?;
+                    self.navigation.push(next)?;
                 } else {
                     self.navigation
-                        .push_overlay(ScreenId::GetKey(name.to_string()))?;
+                        .push_overlay(ScreenId::GetKey(name.to_string(), Box::new(next)))?;
                 }
             }
-            _ => self.open_unencrypted_profile(name)?,
+            _ => self.open_unencrypted_profile(name, next)?,
         }
 
         Ok(())
     }
 
-    fn open_unencrypted_profile(&mut self, name: &str) -> AppResult<()> {
+    fn open_unencrypted_profile(&mut self, name: &str, next: ScreenId) -> AppResult<()> {
         let path = get_profile_path(name)?;
         let profile = envio::get_profile(path, None::<fn() -> Zeroizing<String>>)?;
 
         self.ctx.cache.insert_profile(name.to_string(), profile);
-        self.navigation.push(ScreenId::Edit(name.to_string()))?;
+        self.navigation.push(next)?;
 
         Ok(())
     }
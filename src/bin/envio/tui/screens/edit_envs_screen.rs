@@ -10,7 +10,7 @@ use ratatui::{
 use std::thread::{self, JoinHandle};
 
 use super::{Action, Screen, ScreenEvent, ScreenId};
-use crate::{error::AppResult, tui::context::AppContext};
+use crate::{error::AppResult, tags::format_tags, tui::context::AppContext};
 
 enum EditMode {
     None,
@@ -68,18 +68,20 @@ impl Screen for EditEnvsScreen {
                     self.cancel_edit();
                 }
 
-                KeyCode::Left => {
-                    if matches!(self.edit_mode, EditMode::Value(_)) {
-                        self.finish_edit();
-                        self.start_edit_key();
+                KeyCode::Left if matches!(self.edit_mode, EditMode::Value(_)) => {
+                    if !self.finish_edit() {
+                        return Ok(Action::None);
                     }
+
+                    self.start_edit_key();
                 }
 
-                KeyCode::Right => {
-                    if matches!(self.edit_mode, EditMode::Key(_)) {
-                        self.finish_edit();
-                        self.start_edit_value();
+                KeyCode::Right if matches!(self.edit_mode, EditMode::Key(_)) => {
+                    if !self.finish_edit() {
+                        return Ok(Action::None);
                     }
+
+                    self.start_edit_value();
                 }
 
                 KeyCode::Char(c) => {
@@ -212,11 +214,19 @@ impl EditEnvsScreen {
         }
     }
 
-    fn finish_edit(&mut self) {
+    /// returns false if the edit was rejected, in which case the key stays in edit mode
+    fn finish_edit(&mut self) -> bool {
         match self.edit_mode {
             EditMode::Key(idx) if idx < self.envs.len() => {
                 let trimmed = self.edit_buffer.trim().to_string();
 
+                if !trimmed.is_empty()
+                    && let Err(e) = self.check_key(idx, &trimmed)
+                {
+                    self.status = Status::Error(e, Color::Red);
+                    return false;
+                }
+
                 if trimmed.is_empty() {
                     if self.envs[idx].key.is_empty() && self.envs[idx].value.is_empty() {
                         self.envs.remove(idx);
@@ -251,6 +261,27 @@ impl EditEnvsScreen {
 
         self.edit_mode = EditMode::None;
         self.edit_buffer.clear();
+
+        true
+    }
+
+    fn check_key(&self, idx: usize, key: &str) -> Result<(), String> {
+        self.profile
+            .metadata
+            .key_policy
+            .check(key)
+            .map_err(|e| e.to_string())?;
+
+        if self
+            .envs
+            .iter()
+            .enumerate()
+            .any(|(i, env)| i != idx && env.key == key)
+        {
+            return Err(format!("`{}` already exists", key));
+        }
+
+        Ok(())
     }
 
     fn cancel_edit(&mut self) {
@@ -298,6 +329,9 @@ impl EditEnvsScreen {
 
     fn save_changes(&mut self) -> AppResult<()> {
         self.profile.envs = self.envs.clone().into();
+        self.profile
+            .envs
+            .set_key_policy(self.profile.metadata.key_policy);
 
         let mut profile = self.profile.clone();
         self.pending_cache_update = Some(profile.clone());
@@ -315,9 +349,10 @@ impl EditEnvsScreen {
         if let Some(handle) = self.save_handle.take() {
             match handle.join() {
                 Ok(Ok(())) => self.status = Status::Saved,
-                Ok(Err(e)) => self.status = Status::Error(e.to_string(), Color::Red),
+                Ok(Err(e)) => self.status = Status::Error(format!("Save error: {}", e), Color::Red),
                 Err(_) => {
-                    self.status = Status::Error("Save thread panicked".to_string(), Color::Red)
+                    self.status =
+                        Status::Error("Save error: save thread panicked".to_string(), Color::Red)
                 }
             }
         }
@@ -327,12 +362,16 @@ impl EditEnvsScreen {
         let name = &self.profile.metadata.name;
         let description = self.profile.metadata.description.as_deref().unwrap_or("");
 
-        let title = if description.is_empty() {
+        let mut title = if description.is_empty() {
             format!(" {} ", name)
         } else {
             format!(" {} - {} ", name, description)
         };
 
+        if !self.profile.metadata.tags.is_empty() {
+            title.push_str(&format!("[{}] ", format_tags(&self.profile.metadata.tags)));
+        }
+
         let border_color = match &self.status {
             Status::Saving => Color::Yellow,
             Status::Saved => Color::Green,
@@ -348,9 +387,7 @@ impl EditEnvsScreen {
         let status_text = match &self.status {
             Status::Saving => Span::styled("Saving...", Style::default().fg(Color::Yellow)),
             Status::Saved => Span::styled("Saved", Style::default().fg(Color::Green)),
-            Status::Error(e, color) => {
-                Span::styled(format!("Save error: {}", e), Style::default().fg(*color))
-            }
+            Status::Error(e, color) => Span::styled(e.as_str(), Style::default().fg(*color)),
             Status::Idle => Span::styled(
                 format!("{} environment variables", self.envs.len()),
                 Style::default().fg(Color::DarkGray),
@@ -406,12 +443,19 @@ impl EditEnvsScreen {
                     Style::default().fg(Color::DarkGray)
                 };
 
-                let line = vec![
+                let mut line = vec![
                     Span::styled(key_display.to_string(), key_style),
                     Span::styled(" = ", Style::default().fg(Color::DarkGray)),
                     Span::styled(value_display.to_string(), value_style),
                 ];
 
+                if !env.tags.is_empty() {
+                    line.push(Span::styled(
+                        format!("  [{}]", format_tags(&env.tags)),
+                        Style::default().fg(Color::Magenta),
+                    ));
+                }
+
                 ListItem::new(Line::from(line))
             })
             .collect();
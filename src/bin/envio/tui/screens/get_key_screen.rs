@@ -21,6 +21,7 @@ enum Feedback {
 
 pub struct GetKeyScreen {
     profile_name: String,
+    next: ScreenId,
     key: Zeroizing<String>,
     feedback: Option<Feedback>,
     decrypt_handle: Option<JoinHandle<Option<Profile>>>,
@@ -143,14 +144,15 @@ impl Screen for GetKeyScreen {
     }
 
     fn id(&self) -> ScreenId {
-        ScreenId::GetKey(self.profile_name.clone())
+        ScreenId::GetKey(self.profile_name.clone(), Box::new(self.next.clone()))
     }
 }
 
 impl GetKeyScreen {
-    pub fn new(profile_name: String) -> Self {
+    pub fn new(profile_name: String, next: ScreenId) -> Self {
         Self {
             profile_name,
+            next,
             key: Zeroizing::new(String::new()),
             feedback: None,
             decrypt_handle: None,
@@ -180,7 +182,7 @@ impl GetKeyScreen {
             match handle.join() {
                 Ok(Some(profile)) => {
                     self.feedback = None;
-                    return Some(ScreenEvent::ProfileDecrypted(profile));
+                    return Some(ScreenEvent::ProfileDecrypted(profile, self.next.clone()));
                 }
 
                 Ok(None) => {
@@ -23,7 +23,8 @@ pub enum Action {
 }
 
 pub enum ScreenEvent {
-    ProfileDecrypted(Profile),
+    /// a profile was decrypted in order to open the screen that comes with it
+    ProfileDecrypted(Profile, ScreenId),
     ProfileUpdated(Profile),
 }
 
@@ -32,7 +33,8 @@ pub enum ScreenId {
     Select,
     CreateProfile,
     EditProfile(String),
-    GetKey(String),
+    /// asks for the key of a profile, then opens the second screen
+    GetKey(String, Box<ScreenId>),
     Edit(String),
 }
 
@@ -41,8 +43,12 @@ impl ScreenId {
         match self {
             ScreenId::Select => Ok(Box::new(SelectScreen::new()?)),
             ScreenId::CreateProfile => Ok(Box::new(CreateProfileScreen::new()?)),
-            ScreenId::EditProfile(name) => Ok(Box::new(EditProfileScreen::new(name.clone())?)),
-            ScreenId::GetKey(name) => Ok(Box::new(GetKeyScreen::new(name.clone()))),
+            ScreenId::EditProfile(name) => {
+                Ok(Box::new(EditProfileScreen::new(name.clone(), context)?))
+            }
+            ScreenId::GetKey(name, next) => {
+                Ok(Box::new(GetKeyScreen::new(name.clone(), *next.clone())))
+            }
             ScreenId::Edit(name) => Ok(Box::new(EditEnvsScreen::new(name.clone(), context)?)),
         }
     }
@@ -1,5 +1,5 @@
 use envio::{
-    EnvMap,
+    EnvMap, Profile,
     cipher::{CipherKind, create_cipher, gpg::get_gpg_keys},
 };
 use ratatui::{
@@ -16,9 +16,8 @@ use zeroize::Zeroizing;
 
 use super::{Action, Screen, ScreenEvent, ScreenId};
 use crate::{
-    error::AppResult,
-    ops,
-    utils::{build_profile_path, get_profile_metadata, get_profile_path},
+    error::AppResult, ops, profile_name::ProfileName, tui::context::AppContext,
+    utils::build_profile_path,
 };
 
 enum Status {
@@ -497,9 +496,8 @@ impl CreateProfileScreen {
             return Ok(());
         }
 
-        if self.name.trim().contains(' ') {
-            self.status =
-                Status::Error("Profile name cannot contain spaces".to_string(), Color::Red);
+        if let Err(e) = ProfileName::new(self.name.trim()) {
+            self.status = Status::Error(e.to_string(), Color::Red);
             return Ok(());
         }
 
@@ -544,6 +542,7 @@ impl CreateProfileScreen {
                 Some(description),
                 EnvMap::default(),
                 create_cipher(cipher_kind, key)?,
+                ops::ProfileOptions::default(),
             )?;
 
             Ok(())
@@ -784,6 +783,8 @@ enum EditField {
 
 pub struct EditProfileScreen {
     profile_name: String,
+    profile: Profile,
+    pending_event: Option<ScreenEvent>,
     name: String,
     description: String,
     current_field: EditField,
@@ -892,18 +893,27 @@ impl Screen for EditProfileScreen {
         Ok(Action::None)
     }
 
+    fn tick(&mut self) -> AppResult<Option<ScreenEvent>> {
+        Ok(self.pending_event.take())
+    }
+
     fn id(&self) -> ScreenId {
         ScreenId::EditProfile(self.profile_name.clone())
     }
 }
 
 impl EditProfileScreen {
-    pub fn new(profile_name: String) -> AppResult<Self> {
-        let metadata = get_profile_metadata(&profile_name)?;
+    pub fn new(profile_name: String, ctx: &mut AppContext) -> AppResult<Self> {
+        let profile = ctx.cache.get_profile(&profile_name).ok_or_else(|| {
+            crate::error::AppError::Msg(format!("Profile {} not found in cache", profile_name))
+        })?;
+
         Ok(Self {
             profile_name,
-            name: metadata.name.clone(),
-            description: metadata.description.unwrap_or_default(),
+            name: profile.metadata.name.clone(),
+            description: profile.metadata.description.clone().unwrap_or_default(),
+            profile,
+            pending_event: None,
             current_field: EditField::Name,
             status: Status::Idle,
         })
@@ -929,9 +939,8 @@ impl EditProfileScreen {
             return Ok(());
         }
 
-        if self.name.trim().contains(' ') {
-            self.status =
-                Status::Error("Profile name cannot contain spaces".to_string(), Color::Red);
+        if let Err(e) = ProfileName::new(self.name.trim()) {
+            self.status = Status::Error(e.to_string(), Color::Red);
             return Ok(());
         }
 
@@ -942,18 +951,38 @@ impl EditProfileScreen {
             Some(self.description.trim().to_string())
         };
 
-        let new_file_path = build_profile_path(&new_profile_name);
-        let old_file_path = get_profile_path(&self.profile_name)?;
+        let old_profile_name = self.profile.metadata.name.clone();
 
-        let mut serialized_profile = envio::utils::get_serialized_profile(&old_file_path)?;
+        // the metadata is authenticated, so it can only be changed by saving the whole profile
+        let result = if new_profile_name != old_profile_name {
+            let new_file_path = match build_profile_path(&new_profile_name) {
+                Ok(path) => path,
+                Err(e) => {
+                    self.status = Status::Error(e.to_string(), Color::Red);
+                    return Ok(());
+                }
+            };
 
-        serialized_profile.metadata.name = new_profile_name;
-        serialized_profile.metadata.description = new_profile_description;
-        serialized_profile.metadata.file_path = new_file_path.clone();
+            if new_file_path.exists() {
+                self.status = Status::Error(
+                    "A profile with this name already exists".to_string(),
+                    Color::Red,
+                );
+                return Ok(());
+            }
+
+            self.profile.metadata.description = new_profile_description;
+            self.profile.rename(new_profile_name, new_file_path)
+        } else {
+            self.profile.set_description(new_profile_description)
+        };
 
-        envio::utils::save_serialized_profile(&old_file_path, serialized_profile)?;
+        if let Err(e) = result {
+            self.status = Status::Error(e.to_string(), Color::Red);
+            return Ok(());
+        }
 
-        std::fs::rename(old_file_path, new_file_path)?;
+        self.pending_event = Some(ScreenEvent::ProfileUpdated(self.profile.clone()));
 
         self.status = Status::Saved;
 
@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, HashSet};
+
 use envio::{ProfileMetadata, cipher::CipherKind};
 use ratatui::{
     Frame,
@@ -11,7 +13,8 @@ use ratatui::{
 use super::{Action, Screen, ScreenId};
 use crate::{
     error::AppResult,
-    utils::{get_profile_dir, get_profile_metadata},
+    tags::format_tags,
+    utils::{get_profile_dir, get_profile_metadata, get_profile_names},
 };
 
 fn styled_span(content: impl Into<String>, fg: Color, bold: bool) -> Span<'static> {
@@ -30,9 +33,24 @@ pub struct ProfileInfo {
     pub metadata: ProfileMetadata,
 }
 
+/// a line of the profile list, profiles named `team/service/prod` are shown in collapsible groups
+enum Row {
+    Namespace {
+        path: String,
+        depth: usize,
+        profile_count: usize,
+    },
+    Profile {
+        index: usize,
+        depth: usize,
+    },
+}
+
 pub struct SelectScreen {
     profiles: Vec<ProfileInfo>,
     filtered_profiles: Vec<usize>,
+    rows: Vec<Row>,
+    collapsed: HashSet<String>,
     list_state: ListState,
     search_input: String,
     search_mode: bool,
@@ -82,6 +100,17 @@ impl Screen for SelectScreen {
                 self.move_selection(1);
             }
 
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.collapse_selected();
+            }
+
+            KeyCode::Right | KeyCode::Char('l') => {
+                if let Some(Row::Namespace { path, .. }) = self.get_selected_row() {
+                    let path = path.clone();
+                    self.set_collapsed(&path, false);
+                }
+            }
+
             KeyCode::Char('n') => return Ok(Action::NewProfile),
 
             KeyCode::Char('e') => {
@@ -97,6 +126,13 @@ impl Screen for SelectScreen {
             }
 
             KeyCode::Enter => {
+                if let Some(Row::Namespace { path, .. }) = self.get_selected_row() {
+                    let path = path.clone();
+                    let collapsed = self.collapsed.contains(&path);
+                    self.set_collapsed(&path, !collapsed);
+                    return Ok(Action::None);
+                }
+
                 return Ok(self
                     .get_selected_profile()
                     .map_or(Action::None, |p| Action::OpenProfile(p.name.clone())));
@@ -120,6 +156,8 @@ impl SelectScreen {
         let mut screen = Self {
             profiles: Vec::new(),
             filtered_profiles: Vec::new(),
+            rows: Vec::new(),
+            collapsed: HashSet::new(),
             list_state: ListState::default(),
             search_input: String::new(),
             search_mode: false,
@@ -129,7 +167,7 @@ impl SelectScreen {
         screen.load_profiles()?;
         screen.update_filter();
 
-        if !screen.filtered_profiles.is_empty() {
+        if !screen.rows.is_empty() {
             screen.list_state.select(Some(0));
         }
 
@@ -140,27 +178,11 @@ impl SelectScreen {
         self.profiles.clear();
         let profile_dir = get_profile_dir();
 
-        if !profile_dir.exists() {
-            return Ok(());
-        }
-
-        for entry in std::fs::read_dir(profile_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.extension().and_then(|s| s.to_str()) != Some("env") {
-                continue;
-            }
-
-            let profile_name = match path.file_stem().and_then(|s| s.to_str()) {
-                Some(name) if !name.starts_with('.') => name.to_string(),
-                _ => continue,
-            };
-
-            let metadata = get_profile_metadata(&profile_name)?;
+        for profile_name in get_profile_names(&profile_dir)? {
+            let metadata = get_profile_metadata(profile_name.as_str())?;
 
             self.profiles.push(ProfileInfo {
-                name: profile_name,
+                name: profile_name.to_string(),
                 metadata,
             });
         }
@@ -181,27 +203,103 @@ impl SelectScreen {
                 .profiles
                 .iter()
                 .enumerate()
-                .filter(|(_, p)| p.name.to_lowercase().contains(&search_lower))
+                .filter(|(_, p)| {
+                    p.name.to_lowercase().contains(&search_lower)
+                        || format_tags(&p.metadata.tags)
+                            .to_lowercase()
+                            .contains(&search_lower)
+                })
                 .map(|(idx, _)| idx)
                 .collect();
         }
 
+        self.rows.clear();
+        let filtered = self.filtered_profiles.clone();
+        self.build_rows("", 0, &filtered);
+
         self.adjust_selection();
     }
 
+    /// adds the rows for the profiles in `indices` below the namespace `prefix`, namespaces come
+    /// first and are expanded while searching
+    fn build_rows(&mut self, prefix: &str, depth: usize, indices: &[usize]) {
+        let mut namespaces: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        let mut profiles = Vec::new();
+
+        for &index in indices {
+            let rest = &self.profiles[index].name[prefix.len()..];
+
+            match rest.split_once('/') {
+                Some((namespace, _)) => namespaces
+                    .entry(format!("{}{}/", prefix, namespace))
+                    .or_default()
+                    .push(index),
+                None => profiles.push(index),
+            }
+        }
+
+        for (path, indices) in namespaces {
+            let expanded = !self.search_input.is_empty() || !self.collapsed.contains(&path);
+
+            self.rows.push(Row::Namespace {
+                path: path.clone(),
+                depth,
+                profile_count: indices.len(),
+            });
+
+            if expanded {
+                self.build_rows(&path, depth + 1, &indices);
+            }
+        }
+
+        self.rows.extend(
+            profiles
+                .into_iter()
+                .map(|index| Row::Profile { index, depth }),
+        );
+    }
+
+    fn set_collapsed(&mut self, path: &str, collapsed: bool) {
+        if collapsed {
+            self.collapsed.insert(path.to_string());
+        } else {
+            self.collapsed.remove(path);
+        }
+
+        self.update_filter();
+
+        let row = self
+            .rows
+            .iter()
+            .position(|row| matches!(row, Row::Namespace { path: p, .. } if p == path));
+        self.list_state.select(row);
+    }
+
+    /// collapses the selected namespace or the one the selected profile is in
+    fn collapse_selected(&mut self) {
+        let path = match self.get_selected_row() {
+            Some(Row::Namespace { path, .. }) if !self.collapsed.contains(path) => path.clone(),
+            Some(Row::Namespace { path, .. }) => match path[..path.len() - 1].rfind('/') {
+                Some(end) => path[..=end].to_string(),
+                None => return,
+            },
+            Some(Row::Profile { index, .. }) => match self.profiles[*index].name.rfind('/') {
+                Some(end) => self.profiles[*index].name[..=end].to_string(),
+                None => return,
+            },
+            None => return,
+        };
+
+        self.set_collapsed(&path, true);
+    }
+
     fn adjust_selection(&mut self) {
-        let max_index = self.filtered_profiles.len().saturating_sub(1);
+        let max_index = self.rows.len().saturating_sub(1);
         let new_selection = self
             .list_state
             .selected()
             .filter(|&idx| idx <= max_index)
-            .or({
-                if !self.filtered_profiles.is_empty() {
-                    Some(0)
-                } else {
-                    None
-                }
-            });
+            .or(if !self.rows.is_empty() { Some(0) } else { None });
 
         self.list_state.select(new_selection);
     }
@@ -234,11 +332,38 @@ impl SelectScreen {
 
     fn draw_profile_list(&mut self, frame: &mut Frame, area: Rect) {
         let items: Vec<ListItem> = self
-            .filtered_profiles
+            .rows
             .iter()
-            .map(|&idx| {
+            .map(|row| {
+                let (idx, depth) = match row {
+                    Row::Namespace {
+                        path,
+                        depth,
+                        profile_count,
+                    } => {
+                        let name = path[..path.len() - 1].rsplit('/').next().unwrap_or(path);
+                        let marker =
+                            if self.collapsed.contains(path) && self.search_input.is_empty() {
+                                "▸"
+                            } else {
+                                "▾"
+                            };
+
+                        return ListItem::new(Line::from(vec![
+                            styled_span("  ".repeat(*depth), Color::White, false),
+                            styled_span(format!("{} {}/", marker, name), Color::Cyan, true),
+                            styled_span(format!(" ({})", profile_count), Color::DarkGray, false),
+                        ]));
+                    }
+                    Row::Profile { index, depth } => (*index, *depth),
+                };
+
                 let p = &self.profiles[idx];
-                let mut line = vec![styled_span(&p.name, Color::White, true)];
+                let name = p.name.rsplit('/').next().unwrap_or(&p.name);
+                let mut line = vec![
+                    styled_span("  ".repeat(depth), Color::White, false),
+                    styled_span(name, Color::White, true),
+                ];
 
                 if let Some(desc) = &p.metadata.description
                     && !desc.is_empty()
@@ -259,6 +384,14 @@ impl SelectScreen {
                     false,
                 ));
 
+                if !p.metadata.tags.is_empty() {
+                    line.push(styled_span(
+                        format!(" {{{}}}", format_tags(&p.metadata.tags)),
+                        Color::LightBlue,
+                        false,
+                    ));
+                }
+
                 line.push(styled_span(
                     format!(
                         " (updated: {})",
@@ -289,7 +422,7 @@ impl SelectScreen {
         let text = if self.search_mode {
             "Enter: Select | Esc: Exit search | Ctrl+C: Quit"
         } else {
-            "Enter: Open | /: Search | n: New | e: Edit | d: Delete | Esc: Quit"
+            "Enter: Open | ←/→: Collapse/Expand | /: Search | n: New | e: Edit | d: Delete | Esc: Quit"
         };
         frame.render_widget(
             Paragraph::new(text)
@@ -329,21 +462,27 @@ impl SelectScreen {
     }
 
     fn move_selection(&mut self, delta: i32) {
-        if self.filtered_profiles.is_empty() {
+        if self.rows.is_empty() {
             return;
         }
 
         let new = self.list_state.selected().unwrap_or(0) as i32 + delta;
-        let new = new.clamp(0, self.filtered_profiles.len() as i32 - 1) as usize;
+        let new = new.clamp(0, self.rows.len() as i32 - 1) as usize;
 
         self.list_state.select(Some(new));
     }
 
-    pub fn get_selected_profile(&self) -> Option<&ProfileInfo> {
+    fn get_selected_row(&self) -> Option<&Row> {
         self.list_state
             .selected()
-            .and_then(|idx| self.filtered_profiles.get(idx))
-            .map(|&i| &self.profiles[i])
+            .and_then(|idx| self.rows.get(idx))
+    }
+
+    pub fn get_selected_profile(&self) -> Option<&ProfileInfo> {
+        match self.get_selected_row()? {
+            Row::Profile { index, .. } => Some(&self.profiles[*index]),
+            Row::Namespace { .. } => None,
+        }
     }
 
     fn draw_delete_confirmation(&self, frame: &mut Frame, area: Rect, profile_name: &str) {
@@ -432,8 +571,8 @@ impl SelectScreen {
                 crate::ops::delete_profile(&profile_name)?;
                 self.load_profiles()?;
                 self.update_filter();
-                if !self.filtered_profiles.is_empty() {
-                    let max_idx = self.filtered_profiles.len().saturating_sub(1);
+                if !self.rows.is_empty() {
+                    let max_idx = self.rows.len().saturating_sub(1);
                     let current_idx = self.list_state.selected().unwrap_or(0);
                     self.list_state.select(Some(current_idx.min(max_idx)));
                 }
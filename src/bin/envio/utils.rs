@@ -1,24 +1,69 @@
-#[cfg(target_family = "unix")]
-use std::path::Path;
-use std::{fs::File, io::Write, path::PathBuf};
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
 
+use chrono::{Days, Local, Months, NaiveDate};
 use envio::{
-    Env, EnvMap,
+    Env, EnvMap, KeyPolicy,
     profile::{ProfileMetadata, SerializedProfile},
 };
 use indicatif::{ProgressBar, ProgressState, ProgressStyle};
 use reqwest::Client;
 
-use crate::error::{AppError, AppResult};
+use crate::{
+    error::{AppError, AppResult},
+    profile_name::ProfileName,
+};
+
+static PROFILE_DIR: OnceLock<PathBuf> = OnceLock::new();
 
+/// resolves the config root in the following order: `$ENVIO_HOME`, the legacy
+/// `~/.envio` directory if it already exists, `$XDG_CONFIG_HOME/envio` (unix only)
 pub fn get_configdir() -> PathBuf {
-    dirs::home_dir().unwrap().join(".envio")
+    if let Some(dir) = std::env::var_os("ENVIO_HOME").filter(|dir| !dir.is_empty()) {
+        return PathBuf::from(dir);
+    }
+
+    let legacy_dir = dirs::home_dir().unwrap().join(".envio");
+
+    #[cfg(target_family = "unix")]
+    if !legacy_dir.exists() {
+        return get_xdg_config_home().join("envio");
+    }
+
+    legacy_dir
 }
 
-pub fn get_profile_dir() -> PathBuf {
+#[cfg(target_family = "unix")]
+fn get_xdg_config_home() -> PathBuf {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .unwrap_or_else(|| dirs::home_dir().unwrap().join(".config"))
+}
+
+pub fn get_default_profile_dir() -> PathBuf {
     get_configdir().join("profiles")
 }
 
+/// overrides the profile directory for the rest of the process, used for
+/// `--profile-dir` and `--store`. relative paths are made absolute since the directory also ends
+/// up in the script written by `load`, which runs from wherever the shell is
+pub fn set_profile_dir(dir: PathBuf) {
+    let dir = std::path::absolute(&dir).unwrap_or(dir);
+    let _ = PROFILE_DIR.set(dir);
+}
+
+pub fn get_profile_dir() -> PathBuf {
+    PROFILE_DIR
+        .get()
+        .cloned()
+        .unwrap_or_else(get_default_profile_dir)
+}
+
 #[cfg(target_family = "unix")]
 pub fn get_shellscript_path() -> PathBuf {
     get_configdir().join("setenv.sh")
@@ -32,31 +77,118 @@ pub fn contains_path_separator(s: &str) -> bool {
     s.contains('/') || s.contains('\\')
 }
 
-/// returns the path for a profile that does **not** exist yet
-pub fn build_profile_path(profile_name: &str) -> PathBuf {
-    get_profile_dir().join(format!("{}.env", profile_name))
+/// returns the path for a profile that does **not** exist yet, fails if the name is invalid
+pub fn build_profile_path(profile_name: &str) -> AppResult<PathBuf> {
+    Ok(ProfileName::new(profile_name)?.file_path(&get_profile_dir()))
 }
 
-/// returns the path for a profile that **must exist**
+/// returns the path for a profile that **must exist**, which may have a legacy name
 pub fn get_profile_path(profile_name: &str) -> AppResult<PathBuf> {
-    let path = build_profile_path(profile_name);
-    if !path.exists() {
+    let path = ProfileName::existing(profile_name)?.file_path(&get_profile_dir());
+    if !path.is_file() {
         return Err(AppError::ProfileDoesNotExist(profile_name.to_string()));
     }
 
     Ok(path)
 }
 
+/// returns the names of all profiles stored in `dir` and its subdirectories, sorted
+/// alphabetically
+pub fn get_profile_names(dir: &Path) -> AppResult<Vec<ProfileName>> {
+    let mut profiles = Vec::new();
+
+    if !dir.exists() {
+        return Ok(profiles);
+    }
+
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(current) = dirs.pop() {
+        for entry in std::fs::read_dir(current)? {
+            let entry = entry?;
+            let path = entry.path();
+
+            // hidden directories such as `.git` are never namespaces
+            if entry.file_type()?.is_dir() {
+                if !entry.file_name().to_string_lossy().starts_with('.') {
+                    dirs.push(path);
+                }
+                continue;
+            }
+
+            if let Some(name) = ProfileName::from_file_path(dir, &path) {
+                profiles.push(name);
+            }
+        }
+    }
+
+    profiles.sort();
+
+    Ok(profiles)
+}
+
 pub fn get_profile_metadata(profile_name: &str) -> AppResult<ProfileMetadata> {
     let path = get_profile_path(profile_name)?;
     let serialized_profile: SerializedProfile = envio::utils::get_serialized_profile(path)?;
     Ok(serialized_profile.metadata)
 }
 
-pub fn parse_envs_from_string(buffer: &str) -> AppResult<EnvMap> {
+/// adds a duration such as `30d`, `2w`, `6m` or `1y` to `date`
+pub fn add_duration(date: NaiveDate, duration: &str) -> AppResult<NaiveDate> {
+    let invalid = || {
+        AppError::Msg(format!(
+            "Invalid duration `{}`, expected a number followed by d, w, m or y (e.g. 30d)",
+            duration
+        ))
+    };
+
+    let unit_index = duration
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let (amount, unit) = duration.split_at(unit_index);
+    let amount: u32 = amount.parse().map_err(|_| invalid())?;
+
+    match unit {
+        "d" => date.checked_add_days(Days::new(amount.into())),
+        "w" => date.checked_add_days(Days::new(u64::from(amount) * 7)),
+        "m" => date.checked_add_months(Months::new(amount)),
+        "y" => amount
+            .checked_mul(12)
+            .and_then(|months| date.checked_add_months(Months::new(months))),
+        _ => return Err(invalid()),
+    }
+    .ok_or_else(invalid)
+}
+
+/// parses an absolute date (`2026-12-31`) or one relative to today (`90d`)
+pub fn parse_date(value: &str) -> AppResult<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    add_duration(Local::now().date_naive(), value).map_err(|_| {
+        AppError::Msg(format!(
+            "Invalid date `{}`, expected YYYY-MM-DD or a duration such as 90d",
+            value
+        ))
+    })
+}
+
+/// splits a `KEY=VALUE` argument of `flag`
+pub fn parse_assignment(arg: &str, flag: &str) -> AppResult<(String, String)> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(AppError::Msg(format!(
+            "Invalid value `{}` for `{}`, expected KEY=VALUE",
+            arg, flag
+        ))),
+    }
+}
+
+pub fn parse_envs_from_string(buffer: &str, key_policy: KeyPolicy) -> AppResult<EnvMap> {
     let mut envs_vec = EnvMap::default();
+    envs_vec.set_key_policy(key_policy);
 
-    for line in buffer.lines() {
+    for (index, line) in buffer.lines().enumerate() {
         if line.is_empty() || !line.contains('=') {
             continue;
         }
@@ -69,7 +201,9 @@ pub fn parse_envs_from_string(buffer: &str) -> AppResult<EnvMap> {
             .next()
             .ok_or_else(|| AppError::Msg(format!("Cannot parse value for key: `{}`", key)))?;
 
-        envs_vec.insert(Env::from_key_value(key.to_string(), value.to_string()));
+        envs_vec
+            .insert(Env::from_key_value(key.to_string(), value.to_string()))
+            .map_err(|e| AppError::Msg(format!("Line {}: {}", index + 1, e)))?;
     }
 
     Ok(envs_vec)
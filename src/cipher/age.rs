@@ -8,7 +8,6 @@ use age::{Decryptor, Encryptor, scrypt::Identity, secrecy::SecretString};
 use zeroize::Zeroizing;
 
 use crate::{
-    EnvMap,
     cipher::{Cipher, CipherKind, EncryptedContent},
     error::{Error, Result},
 };
@@ -33,9 +32,7 @@ impl Cipher for AGE {
         CipherKind::AGE
     }
 
-    fn encrypt(&mut self, envs: &EnvMap) -> Result<EncryptedContent> {
-        let data = envs.as_bytes()?;
-
+    fn encrypt_bytes(&mut self, data: &[u8]) -> Result<EncryptedContent> {
         let encryptor = Encryptor::with_user_passphrase(SecretString::from(self.key.as_str()));
 
         let mut encrypted = vec![];
@@ -43,13 +40,13 @@ impl Cipher for AGE {
             .wrap_output(&mut encrypted)
             .map_err(|e| Error::Cipher(e.to_string()))?;
 
-        writer.write_all(&data)?;
+        writer.write_all(data)?;
         writer.finish()?;
 
         Ok(EncryptedContent::Bytes(encrypted))
     }
 
-    fn decrypt(&self, encrypted_data: &EncryptedContent) -> Result<EnvMap> {
+    fn decrypt_bytes(&self, encrypted_data: &EncryptedContent) -> Result<Vec<u8>> {
         let decryptor = Decryptor::new(Cursor::new(encrypted_data.as_bytes()?))
             .map_err(|e| Error::Cipher(e.to_string()))?;
 
@@ -62,7 +59,7 @@ impl Cipher for AGE {
 
         reader.read_to_end(&mut decrypted)?;
 
-        Ok(decrypted.into())
+        Ok(decrypted)
     }
 
     fn as_any(&self) -> &dyn Any {
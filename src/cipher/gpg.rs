@@ -13,7 +13,6 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    EnvMap,
     cipher::{Cipher, CipherKind, EncryptedContent},
     error::{Error, Result},
 };
@@ -49,9 +48,7 @@ impl Cipher for GPG {
         CipherKind::GPG
     }
 
-    fn encrypt(&mut self, envs: &EnvMap) -> Result<EncryptedContent> {
-        let data = envs.as_bytes()?;
-
+    fn encrypt_bytes(&mut self, data: &[u8]) -> Result<EncryptedContent> {
         let mut encrypted_data = Vec::new();
 
         #[cfg(target_family = "unix")]
@@ -95,7 +92,7 @@ impl Cipher for GPG {
                 }
             };
 
-            stdin.write_all(&data)?;
+            stdin.write_all(data)?;
 
             // Wait for the GPG process to finish and capture its output
             let output = gpg_process.wait_with_output()?;
@@ -106,7 +103,7 @@ impl Cipher for GPG {
         Ok(EncryptedContent::Bytes(encrypted_data))
     }
 
-    fn decrypt(&self, encrypted_data: &EncryptedContent) -> Result<EnvMap> {
+    fn decrypt_bytes(&self, encrypted_data: &EncryptedContent) -> Result<Vec<u8>> {
         #[cfg(target_family = "unix")]
         {
             let mut ctx = match Context::from_protocol(Protocol::OpenPgp) {
@@ -128,7 +125,7 @@ impl Cipher for GPG {
                 return Err(Error::Cipher(e.to_string()));
             };
 
-            Ok(plain.into())
+            Ok(plain)
         }
 
         #[cfg(target_family = "windows")]
@@ -152,7 +149,7 @@ impl Cipher for GPG {
 
             let output = gpg_process.wait_with_output()?;
 
-            Ok(output.stdout.into())
+            Ok(output.stdout)
         }
     }
 
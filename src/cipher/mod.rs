@@ -17,7 +17,7 @@ use std::{any::Any, path::Path};
 use strum_macros::{AsRefStr, EnumIter, EnumString};
 use zeroize::Zeroizing;
 
-use crate::{env::EnvMap, error::Result, utils};
+use crate::{env::EnvMap, error::Result, layout::EncryptedEntries, utils};
 
 #[derive(Copy, Clone, PartialEq, Serialize, Deserialize, EnumIter, EnumString, AsRefStr)]
 #[serde(rename_all = "lowercase")]
@@ -51,6 +51,7 @@ impl std::fmt::Display for CipherKind {
 #[serde(untagged)]
 pub enum EncryptedContent {
     Bytes(#[serde_as(as = "Base64")] Vec<u8>),
+    Entries(EncryptedEntries),
     Json(serde_json::Value),
 }
 
@@ -58,16 +59,25 @@ impl EncryptedContent {
     pub fn as_bytes(&self) -> Result<Vec<u8>> {
         match self {
             EncryptedContent::Bytes(b) => Ok(b.clone()),
+            EncryptedContent::Entries(entries) => Ok(serde_json::to_vec(entries)?),
             EncryptedContent::Json(value) => Ok(serde_json::to_vec(value)?),
         }
     }
 }
 
 pub trait Cipher: Any + Send + DynClone {
-    fn encrypt(&mut self, envs: &EnvMap) -> Result<EncryptedContent>;
-    fn decrypt(&self, encrypted_data: &EncryptedContent) -> Result<EnvMap>;
+    fn encrypt_bytes(&mut self, data: &[u8]) -> Result<EncryptedContent>;
+    fn decrypt_bytes(&self, encrypted_data: &EncryptedContent) -> Result<Vec<u8>>;
     fn kind(&self) -> CipherKind;
 
+    fn encrypt(&mut self, envs: &EnvMap) -> Result<EncryptedContent> {
+        self.encrypt_bytes(&envs.as_bytes()?)
+    }
+
+    fn decrypt(&self, encrypted_data: &EncryptedContent) -> Result<EnvMap> {
+        Ok(self.decrypt_bytes(encrypted_data)?.into())
+    }
+
     fn export_metadata(&self) -> Option<serde_json::Value> {
         None
     }
@@ -14,6 +14,14 @@ impl Cipher for NONE {
         CipherKind::NONE
     }
 
+    fn encrypt_bytes(&mut self, data: &[u8]) -> Result<EncryptedContent> {
+        Ok(EncryptedContent::Bytes(data.to_vec()))
+    }
+
+    fn decrypt_bytes(&self, encrypted_data: &EncryptedContent) -> Result<Vec<u8>> {
+        encrypted_data.as_bytes()
+    }
+
     fn encrypt(&mut self, envs: &EnvMap) -> Result<EncryptedContent> {
         Ok(EncryptedContent::Json(serde_json::to_value(envs)?))
     }
@@ -1,14 +1,14 @@
 #[macro_use]
 mod metadata;
 mod v1;
+mod v2;
 
 use std::any::Any;
 use zeroize::Zeroizing;
 
 use crate::{
-    EnvMap,
     cipher::{Cipher, CipherKind, EncryptedContent},
-    error::Result,
+    error::{Error, Result},
 };
 
 use metadata::VersionedMetadata;
@@ -19,9 +19,53 @@ include!(concat!(
 ));
 include!(concat!(env!("OUT_DIR"), "/passphrase_encrypt_generated.rs"));
 
+/// the highest parameters a profile may use. they are read from the cleartext metadata before
+/// anything is authenticated, so a crafted profile could otherwise make envio allocate gigabytes
+/// of memory or spin for hours when it is opened
+pub const MAX_MEMORY: u32 = 1024 * 1024;
+pub const MAX_ITERATIONS: u32 = 64;
+pub const MAX_PARALLELISM: u32 = 16;
+
+/// cost of deriving the encryption key from the passphrase with Argon2id
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct KdfParams {
+    /// memory in KiB
+    pub memory: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        KdfParams {
+            memory: argon2::Params::DEFAULT_M_COST,
+            iterations: argon2::Params::DEFAULT_T_COST,
+            parallelism: argon2::Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl KdfParams {
+    pub fn to_argon2(&self) -> Result<argon2::Params> {
+        if self.memory > MAX_MEMORY
+            || self.iterations > MAX_ITERATIONS
+            || self.parallelism > MAX_PARALLELISM
+        {
+            return Err(Error::Cipher(format!(
+                "Invalid Argon2 parameters: at most {} KiB of memory, {} iterations and {} lanes are allowed",
+                MAX_MEMORY, MAX_ITERATIONS, MAX_PARALLELISM
+            )));
+        }
+
+        argon2::Params::new(self.memory, self.iterations, self.parallelism, Some(32))
+            .map_err(|e| Error::Cipher(format!("Invalid Argon2 parameters: {}", e)))
+    }
+}
+
 #[derive(Clone)]
 pub struct PASSPHRASE {
     key: Zeroizing<String>,
+    params: KdfParams,
     metadata: VersionedMetadata,
 }
 
@@ -29,6 +73,7 @@ impl PASSPHRASE {
     pub fn new(key: Zeroizing<String>) -> Self {
         PASSPHRASE {
             key,
+            params: KdfParams::default(),
             metadata: VersionedMetadata::default(),
         }
     }
@@ -36,6 +81,19 @@ impl PASSPHRASE {
     pub fn set_key(&mut self, key: Zeroizing<String>) {
         self.key = key;
     }
+
+    /// the parameters used the next time the profile is saved, which is when older versions of
+    /// the format are upgraded as well
+    pub fn set_params(&mut self, params: KdfParams) -> Result<()> {
+        params.to_argon2()?;
+        self.params = params;
+
+        Ok(())
+    }
+
+    pub fn params(&self) -> KdfParams {
+        self.params
+    }
 }
 
 impl Cipher for PASSPHRASE {
@@ -43,16 +101,15 @@ impl Cipher for PASSPHRASE {
         CipherKind::PASSPHRASE
     }
 
-    fn encrypt(&mut self, envs: &EnvMap) -> Result<EncryptedContent> {
-        let data = envs.as_bytes()?;
-        let (encrypted, metadata) = encrypt_latest(&self.key, &data)?;
+    fn encrypt_bytes(&mut self, data: &[u8]) -> Result<EncryptedContent> {
+        let (encrypted, metadata) = encrypt_latest(&self.key, &self.params, data)?;
         self.metadata = metadata;
 
         Ok(EncryptedContent::Bytes(encrypted))
     }
 
-    fn decrypt(&self, encrypted_data: &EncryptedContent) -> Result<EnvMap> {
-        Ok(decrypt_match!(self, &encrypted_data.as_bytes()?)?.into())
+    fn decrypt_bytes(&self, encrypted_data: &EncryptedContent) -> Result<Vec<u8>> {
+        decrypt_match!(self, &encrypted_data.as_bytes()?)
     }
 
     fn export_metadata(&self) -> Option<serde_json::Value> {
@@ -62,6 +119,11 @@ impl Cipher for PASSPHRASE {
     fn import_metadata(&mut self, data: serde_json::Value) -> Result<()> {
         self.metadata = serde_json::from_value(data)?;
 
+        // keep the parameters the profile was saved with, v1 always used the defaults
+        if let VersionedMetadata::V2(metadata) = &self.metadata {
+            self.params = metadata.params();
+        }
+
         Ok(())
     }
 
@@ -73,3 +135,144 @@ impl Cipher for PASSPHRASE {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the smallest parameters Argon2 accepts, so the tests do not spend their time deriving keys
+    const CHEAP: KdfParams = KdfParams {
+        memory: 8,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    fn cipher(key: &str) -> PASSPHRASE {
+        let mut cipher = PASSPHRASE::new(Zeroizing::new(key.to_string()));
+        cipher.set_params(CHEAP).unwrap();
+        cipher
+    }
+
+    /// a cipher as it is created when a profile is read, before it knows the parameters
+    fn reader(key: &str, metadata: serde_json::Value) -> PASSPHRASE {
+        let mut cipher = PASSPHRASE::new(Zeroizing::new(key.to_string()));
+        cipher.import_metadata(metadata).unwrap();
+        cipher
+    }
+
+    #[test]
+    fn decrypts_what_it_encrypted() {
+        // longer than a chunk so the stream is split
+        let data = vec![7u8; 3000];
+
+        let mut cipher = cipher("correct horse");
+        let encrypted = cipher.encrypt_bytes(&data).unwrap();
+        let metadata = cipher.export_metadata().unwrap();
+
+        assert_eq!(metadata["version"], "2");
+        assert_eq!(metadata["memory"], 8);
+
+        let reader = reader("correct horse", metadata);
+        assert_eq!(reader.params(), CHEAP);
+        assert_eq!(reader.decrypt_bytes(&encrypted).unwrap(), data);
+    }
+
+    #[test]
+    fn decrypts_profiles_saved_with_v1() {
+        use base64::{Engine, engine::general_purpose::STANDARD};
+
+        let encrypted = STANDARD
+            .decode("p/r12l+VGQbD9/Yu7/OmFxFBD4OOb9joKj82dV0egU+S")
+            .unwrap();
+        let reader = reader(
+            "correct horse",
+            serde_json::json!({
+                "version": "1",
+                "salt": "ChQZUDch8Sw3GLaA3uWAYg",
+                "nonce": "VuctK92zSoc3E4RIBv0pzWsSWQ==",
+            }),
+        );
+
+        assert_eq!(reader.params(), KdfParams::default());
+        assert_eq!(
+            reader
+                .decrypt_bytes(&EncryptedContent::Bytes(encrypted))
+                .unwrap(),
+            b"KEY=value from v1"
+        );
+    }
+
+    #[test]
+    fn rejects_a_wrong_passphrase() {
+        let mut cipher = cipher("correct horse");
+        let encrypted = cipher.encrypt_bytes(b"secret").unwrap();
+
+        let reader = reader("battery staple", cipher.export_metadata().unwrap());
+        assert!(reader.decrypt_bytes(&encrypted).is_err());
+    }
+
+    #[test]
+    fn detects_modified_ciphertext() {
+        let mut cipher = cipher("correct horse");
+        let EncryptedContent::Bytes(mut encrypted) = cipher.encrypt_bytes(b"secret").unwrap()
+        else {
+            panic!("expected bytes");
+        };
+        encrypted[0] ^= 1;
+
+        let reader = reader("correct horse", cipher.export_metadata().unwrap());
+        assert!(
+            reader
+                .decrypt_bytes(&EncryptedContent::Bytes(encrypted))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_params() {
+        let mut cipher = cipher("correct horse");
+
+        for params in [
+            KdfParams { memory: 1, ..CHEAP },
+            KdfParams {
+                iterations: 0,
+                ..CHEAP
+            },
+            KdfParams {
+                parallelism: 0,
+                ..CHEAP
+            },
+            KdfParams {
+                memory: MAX_MEMORY + 1,
+                ..CHEAP
+            },
+            KdfParams {
+                iterations: MAX_ITERATIONS + 1,
+                ..CHEAP
+            },
+            KdfParams {
+                parallelism: MAX_PARALLELISM + 1,
+                ..CHEAP
+            },
+        ] {
+            assert!(cipher.set_params(params).is_err());
+        }
+
+        assert_eq!(cipher.params(), CHEAP);
+    }
+
+    #[test]
+    fn refuses_to_decrypt_with_unbounded_params() {
+        let mut cipher = cipher("correct horse");
+        let encrypted = cipher.encrypt_bytes(b"secret").unwrap();
+
+        let mut metadata = cipher.export_metadata().unwrap();
+        metadata["memory"] = (MAX_MEMORY * 4).into();
+
+        let reader = reader("correct horse", metadata);
+        assert!(matches!(
+            reader.decrypt_bytes(&encrypted),
+            Err(Error::Cipher(message)) if message.contains("at most")
+        ));
+    }
+}
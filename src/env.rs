@@ -1,15 +1,160 @@
-use chrono::NaiveDate;
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Local, NaiveDate};
 use indexmap::IndexMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use strum_macros::{AsRefStr, EnumIter, EnumString};
 
 use crate::error::{Error, Result};
 
+/// which names environment variables are allowed to have
+#[derive(
+    Copy, Clone, PartialEq, Default, Debug, Serialize, Deserialize, EnumIter, EnumString, AsRefStr,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyPolicy {
+    /// letters, digits and underscores, not starting with a digit, which every shell accepts
+    #[default]
+    #[strum(ascii_case_insensitive, to_string = "posix")]
+    Posix,
+    /// anything the operating system accepts, which is everything but `=` and NUL
+    #[strum(ascii_case_insensitive, to_string = "lenient")]
+    Lenient,
+}
+
+impl KeyPolicy {
+    pub fn is_posix(&self) -> bool {
+        *self == KeyPolicy::Posix
+    }
+
+    pub fn check(&self, key: &str) -> Result<()> {
+        let reason = match self {
+            _ if key.is_empty() => "it must not be empty",
+            KeyPolicy::Posix if key.starts_with(|c: char| c.is_ascii_digit()) => {
+                "it must not start with a digit"
+            }
+            KeyPolicy::Posix if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') => {
+                "it may only contain letters, digits and underscores"
+            }
+            KeyPolicy::Lenient if key.contains(['=', '\0']) => "it must not contain `=` or NUL",
+            _ => return Ok(()),
+        };
+
+        Err(Error::InvalidKey(key.to_string(), reason.to_string()))
+    }
+
+    /// the closest name that is valid under the posix policy, `None` if there is nothing left
+    /// to build one from
+    pub fn normalize(key: &str) -> Option<String> {
+        let mut normalized: String = key
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        if normalized.chars().all(|c| c == '_') {
+            return None;
+        }
+
+        if normalized.starts_with(|c: char| c.is_ascii_digit()) {
+            normalized.insert(0, '_');
+        }
+
+        Some(normalized)
+    }
+}
+
+impl std::fmt::Display for KeyPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+/// labels such as `owner=platform`, a label without a value has an empty one
+pub type Tags = BTreeMap<String, String>;
+
+/// how often an environment variable needs a new value and how to produce it
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct RotationPolicy {
+    /// a duration such as `90d`, measured from when the value last changed
+    pub max_age: String,
+    /// a generator such as `hex:32`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generator: Option<String>,
+    /// a command whose output becomes the new value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Env {
     pub key: String,
     pub value: String,
     pub comment: Option<String>,
     pub expiration_date: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: Tags,
+    /// set when the profile is saved, see `Profile::save`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Local>>,
+    /// when the value last changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Local>>,
+    /// the user who last changed the value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<RotationPolicy>,
+}
+
+/// the binary encoding of an environment variable, everything added after it is stored in an
+/// `EnvExtension` so payloads written before can still be read
+type BinaryEnv = (String, String, Option<String>, Option<NaiveDate>);
+
+/// the parts of an environment variable that are appended to its binary encoding. it is encoded
+/// as json so fields can be added without breaking older payloads
+#[derive(Serialize, Deserialize, Default, PartialEq)]
+pub(crate) struct EnvExtension {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    tags: Tags,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at: Option<DateTime<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_at: Option<DateTime<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rotation: Option<RotationPolicy>,
+}
+
+impl EnvExtension {
+    pub fn from_env(env: &Env) -> EnvExtension {
+        EnvExtension {
+            tags: env.tags.clone(),
+            created_at: env.created_at,
+            updated_at: env.updated_at,
+            updated_by: env.updated_by.clone(),
+            rotation: env.rotation.clone(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == EnvExtension::default()
+    }
+
+    pub fn apply(self, env: &mut Env) {
+        env.tags = self.tags;
+        env.created_at = self.created_at;
+        env.updated_at = self.updated_at;
+        env.updated_by = self.updated_by;
+        env.rotation = self.rotation;
+    }
 }
 
 impl Env {
@@ -24,6 +169,11 @@ impl Env {
             value: value.into(),
             comment,
             expiration_date,
+            tags: Tags::new(),
+            created_at: None,
+            updated_at: None,
+            updated_by: None,
+            rotation: None,
         }
     }
 
@@ -35,6 +185,8 @@ impl Env {
 #[derive(Default, Clone)]
 pub struct EnvMap {
     envs: IndexMap<String, Env>,
+    /// only applies to new environment variables, loaded ones are kept as they are
+    key_policy: KeyPolicy,
 }
 
 impl Serialize for EnvMap {
@@ -59,18 +211,114 @@ impl<'de> Deserialize<'de> for EnvMap {
 
 impl EnvMap {
     pub fn as_bytes(&self) -> Result<Vec<u8>> {
-        Ok(bincode::serde::encode_to_vec(
-            self,
-            bincode::config::standard(),
-        )?)
+        let envs: Vec<_> = self
+            .envs
+            .values()
+            .map(|env| (&env.key, &env.value, &env.comment, &env.expiration_date))
+            .collect();
+
+        let mut bytes = bincode::serde::encode_to_vec(&envs, bincode::config::standard())?;
+
+        let extensions: IndexMap<&String, EnvExtension> = self
+            .envs
+            .values()
+            .map(|env| (&env.key, EnvExtension::from_env(env)))
+            .filter(|(_, extension)| !extension.is_empty())
+            .collect();
+
+        if !extensions.is_empty() {
+            bytes.extend(serde_json::to_vec(&extensions)?);
+        }
+
+        Ok(bytes)
+    }
+
+    /// decodes the environment variables written by `as_bytes` without their extensions, along
+    /// with the number of bytes they took up
+    pub(crate) fn decode(bytes: &[u8]) -> Result<(EnvMap, usize)> {
+        let (envs, len): (Vec<BinaryEnv>, usize) =
+            bincode::serde::decode_from_slice(bytes, bincode::config::standard())?;
+
+        let envs: Vec<Env> = envs
+            .into_iter()
+            .map(|(key, value, comment, expiration_date)| {
+                Env::new(key, value, comment, expiration_date)
+            })
+            .collect();
+
+        Ok((envs.into(), len))
+    }
+
+    /// applies the extensions `as_bytes` wrote after the environment variables
+    pub(crate) fn decode_extensions(&mut self, bytes: &[u8]) -> Result<()> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+
+        let extensions: HashMap<String, EnvExtension> = serde_json::from_slice(bytes)?;
+
+        for (key, extension) in extensions {
+            if let Some(env) = self.envs.get_mut(&key) {
+                extension.apply(env);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn key_policy(&self) -> KeyPolicy {
+        self.key_policy
     }
 
-    pub fn insert(&mut self, env: Env) {
+    pub fn set_key_policy(&mut self, key_policy: KeyPolicy) {
+        self.key_policy = key_policy;
+    }
+
+    /// inserts or replaces an environment variable, the key of a new one has to be valid under the
+    /// key policy
+    pub fn insert(&mut self, env: Env) -> Result<()> {
+        if !self.envs.contains_key(&env.key) {
+            self.key_policy.check(&env.key)?;
+        }
+
+        self.insert_unchecked(env);
+
+        Ok(())
+    }
+
+    pub(crate) fn insert_unchecked(&mut self, env: Env) {
         self.envs.insert(env.key.clone(), env);
     }
 
-    pub fn insert_from_key_value(&mut self, key: impl Into<String>, value: impl Into<String>) {
-        self.insert(Env::from_key_value(key, value));
+    pub fn insert_from_key_value(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Result<()> {
+        self.insert(Env::from_key_value(key, value))
+    }
+
+    /// renames an environment variable in place, keeping its position
+    pub fn rename(&mut self, key: &str, new_key: &str) -> Result<()> {
+        self.key_policy.check(new_key)?;
+
+        if self.envs.contains_key(new_key) {
+            return Err(Error::Msg(format!(
+                "environment variable `{}` already exists",
+                new_key
+            )));
+        }
+
+        let index = self
+            .envs
+            .get_index_of(key)
+            .ok_or_else(|| Error::EnvDoesNotExist(key.to_string()))?;
+
+        let (_, mut env) = self.envs.shift_remove_index(index).unwrap();
+        env.key = new_key.to_string();
+        self.envs.shift_insert(index, new_key.to_string(), env);
+
+        Ok(())
     }
 
     pub fn remove(&mut self, key: &str) -> Result<()> {
@@ -84,6 +332,10 @@ impl EnvMap {
         self.envs.get(key)
     }
 
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Env> {
+        self.envs.get_mut(key)
+    }
+
     pub fn contains_key(&self, key: &str) -> bool {
         self.envs.contains_key(key)
     }
@@ -120,7 +372,7 @@ impl From<IndexMap<String, String>> for EnvMap {
     fn from(map: IndexMap<String, String>) -> Self {
         let mut env_map = Self::default();
         for (k, v) in map {
-            env_map.insert(Env::from_key_value(k, v));
+            env_map.insert_unchecked(Env::from_key_value(k, v));
         }
         env_map
     }
@@ -136,7 +388,7 @@ impl From<Vec<Env>> for EnvMap {
     fn from(envs: Vec<Env>) -> Self {
         let mut env_map = Self::default();
         for env in envs {
-            env_map.insert(env);
+            env_map.insert_unchecked(env);
         }
         env_map
     }
@@ -144,9 +396,9 @@ impl From<Vec<Env>> for EnvMap {
 
 impl From<&[u8]> for EnvMap {
     fn from(bytes: &[u8]) -> Self {
-        let (envs, _): (EnvMap, usize) =
-            bincode::serde::decode_from_slice(bytes, bincode::config::standard())
-                .expect("Failed to deserialize bytes to EnvMap");
+        let (mut envs, len) = EnvMap::decode(bytes).expect("Failed to deserialize bytes to EnvMap");
+        envs.decode_extensions(&bytes[len..])
+            .expect("Failed to deserialize the extensions of EnvMap");
         envs
     }
 }
@@ -183,3 +435,91 @@ impl<'a> IntoIterator for &'a mut EnvMap {
         self.envs.values_mut()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_keys_against_the_posix_policy() {
+        for key in ["KEY", "_key", "Key_2", "_"] {
+            assert!(KeyPolicy::Posix.check(key).is_ok(), "{}", key);
+        }
+
+        for key in ["", "2KEY", "my-key", "KEY NAME", "KEY=1", "SCHLÜSSEL"] {
+            assert!(KeyPolicy::Posix.check(key).is_err(), "{}", key);
+        }
+    }
+
+    #[test]
+    fn checks_keys_against_the_lenient_policy() {
+        for key in ["KEY", "2KEY", "my-key", "key.name", "KEY NAME", "SCHLÜSSEL"] {
+            assert!(KeyPolicy::Lenient.check(key).is_ok(), "{}", key);
+        }
+
+        for key in ["", "KEY=1", "KEY\0"] {
+            assert!(KeyPolicy::Lenient.check(key).is_err(), "{}", key);
+        }
+    }
+
+    #[test]
+    fn normalizes_keys() {
+        assert_eq!(KeyPolicy::normalize("my-key").as_deref(), Some("my_key"));
+        assert_eq!(
+            KeyPolicy::normalize(" api.url ").as_deref(),
+            Some("api_url")
+        );
+        assert_eq!(KeyPolicy::normalize("2FA").as_deref(), Some("_2FA"));
+        assert_eq!(KeyPolicy::normalize("KEY").as_deref(), Some("KEY"));
+        assert_eq!(KeyPolicy::normalize("--"), None);
+        assert_eq!(KeyPolicy::normalize(""), None);
+
+        for key in ["my-key", "2FA", "a b", "SCHLÜSSEL"] {
+            let normalized = KeyPolicy::normalize(key).unwrap();
+            assert!(
+                KeyPolicy::Posix.check(&normalized).is_ok(),
+                "{}",
+                normalized
+            );
+        }
+    }
+
+    #[test]
+    fn applies_the_key_policy_to_inserted_keys() {
+        let mut envs = EnvMap::default();
+
+        assert!(envs.insert_from_key_value("my-key", "1").is_err());
+        assert!(envs.is_empty());
+
+        envs.set_key_policy(KeyPolicy::Lenient);
+        assert!(envs.insert_from_key_value("my-key", "1").is_ok());
+        assert_eq!(envs.get("my-key").unwrap().value, "1");
+    }
+
+    #[test]
+    fn replaces_existing_keys_the_key_policy_does_not_allow() {
+        let mut envs = EnvMap::from(vec![Env::from_key_value("my-key", "1")]);
+
+        assert!(envs.insert_from_key_value("my-key", "2").is_ok());
+        assert_eq!(envs.get("my-key").unwrap().value, "2");
+    }
+
+    #[test]
+    fn renames_keys_in_place() {
+        let mut envs = EnvMap::from(vec![
+            Env::from_key_value("A", "1"),
+            Env::from_key_value("my-key", "2"),
+            Env::from_key_value("C", "3"),
+        ]);
+
+        assert!(envs.rename("my-key", "also-invalid").is_err());
+        assert!(envs.rename("my-key", "A").is_err());
+
+        envs.rename("my-key", "MY_KEY").unwrap();
+        assert_eq!(
+            envs.keys().map(String::as_str).collect::<Vec<_>>(),
+            ["A", "MY_KEY", "C"]
+        );
+        assert_eq!(envs.get("MY_KEY").unwrap().value, "2");
+    }
+}
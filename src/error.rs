@@ -18,9 +18,23 @@ pub enum Error {
     #[error("environment variable `{0}` does not exist")]
     EnvDoesNotExist(String),
 
+    #[error("`{0}` is not a valid environment variable name, {1}")]
+    InvalidKey(String, String),
+
     #[error("{0}")]
     Cipher(String),
 
+    #[error("{0}")]
+    Store(String),
+
+    #[error(
+        "the profile metadata does not match its encrypted content, it may have been tampered with"
+    )]
+    MetadataTampered,
+
+    #[error("profiles changed both locally and remotely: {}", .0.join(", "))]
+    SyncConflict(Vec<String>),
+
     #[error(transparent)]
     Utf8Error(#[from] std::str::Utf8Error),
 
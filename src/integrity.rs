@@ -0,0 +1,248 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use chacha20poly1305::{
+    Key, XChaCha20Poly1305, XNonce,
+    aead::{Aead, AeadCore, KeyInit, Payload},
+};
+use hkdf::Hkdf;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::{
+    env::EnvMap,
+    error::{Error, Result},
+};
+
+pub const MAC_KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 24;
+
+const DOMAIN: &[u8] = b"envio profile metadata\0";
+const MAC_KEY_INFO: &[u8] = b"envio metadata mac key";
+
+pub(crate) fn generate_mac_key() -> Zeroizing<Vec<u8>> {
+    let mut mac_key = Zeroizing::new(vec![0u8; MAC_KEY_LEN]);
+    OsRng.fill_bytes(&mut mac_key);
+    mac_key
+}
+
+/// the key the metadata of a per-entry profile is authenticated with, which is derived from its
+/// data key so the data key itself is only ever used to encrypt entries
+pub(crate) fn derive_mac_key(data_key: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+    let mut mac_key = Zeroizing::new(vec![0u8; MAC_KEY_LEN]);
+
+    Hkdf::<Sha256>::new(None, data_key)
+        .expand(MAC_KEY_INFO, &mut mac_key)
+        .map_err(|e| Error::Cipher(e.to_string()))?;
+
+    Ok(mac_key)
+}
+
+/// the metadata as it is authenticated, leaving out what legitimately changes without saving.
+/// this works on the stored JSON rather than `ProfileMetadata`, so fields it does not know such
+/// as the ones added by newer versions are authenticated as they were written
+fn canonical_metadata(metadata: &serde_json::Value) -> Result<Vec<u8>> {
+    let mut value = metadata.clone();
+
+    if let Some(fields) = value.as_object_mut() {
+        // the path is rewritten whenever the profile is moved, copied or synced and the name is
+        // derived from it, for example when a profile is imported under a different name
+        fields.remove("file_path");
+        fields.remove("name");
+    }
+
+    Ok([DOMAIN, &serde_json::to_vec(&value)?].concat())
+}
+
+/// computes a tag over the stored metadata, which is an empty message encrypted with the
+/// metadata as associated data
+pub(crate) fn authenticate(mac_key: &[u8], metadata: &serde_json::Value) -> Result<Vec<u8>> {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let tag = XChaCha20Poly1305::new(Key::from_slice(mac_key))
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: &[],
+                aad: &canonical_metadata(metadata)?,
+            },
+        )
+        .map_err(|e| Error::Cipher(e.to_string()))?;
+
+    Ok([nonce.as_slice(), &tag].concat())
+}
+
+pub(crate) fn verify(
+    mac_key: &[u8],
+    metadata: &serde_json::Value,
+    mac: Option<&[u8]>,
+) -> Result<()> {
+    let mac = mac.ok_or(Error::MetadataTampered)?;
+
+    if mac.len() < NONCE_LEN {
+        return Err(Error::MetadataTampered);
+    }
+
+    let (nonce, tag) = mac.split_at(NONCE_LEN);
+
+    XChaCha20Poly1305::new(Key::from_slice(mac_key))
+        .decrypt(
+            XNonce::from_slice(nonce),
+            Payload {
+                msg: tag,
+                aad: &canonical_metadata(metadata)?,
+            },
+        )
+        .map_err(|_| Error::MetadataTampered)?;
+
+    Ok(())
+}
+
+/// the plaintext of a blob profile, the serialized envs followed by the key its metadata is
+/// authenticated with
+pub(crate) fn seal_envs(envs: &EnvMap, mac_key: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+    let mut plaintext = Zeroizing::new(envs.as_bytes()?);
+    plaintext.extend_from_slice(mac_key);
+
+    Ok(plaintext)
+}
+
+/// splits the plaintext of a blob profile, profiles saved by older versions have no key. the
+/// key always comes last, anything between it and the envs are their extensions
+pub(crate) fn open_envs(plaintext: &[u8]) -> Result<(EnvMap, Option<Zeroizing<Vec<u8>>>)> {
+    let (mut envs, len) = EnvMap::decode(plaintext)?;
+
+    match &plaintext[len..] {
+        [] => Ok((envs, None)),
+        rest if rest.len() >= MAC_KEY_LEN => {
+            let (extensions, mac_key) = rest.split_at(rest.len() - MAC_KEY_LEN);
+            envs.decode_extensions(extensions)?;
+
+            Ok((envs, Some(Zeroizing::new(mac_key.to_vec()))))
+        }
+        _ => Err(Error::Cipher("Invalid profile content".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::Env;
+
+    fn metadata() -> serde_json::Value {
+        serde_json::json!({
+            "name": "test",
+            "version": "0.0.0",
+            "file_path": "/profiles/test.env",
+            "cipher_kind": "passphrase",
+            "created_at": "2026-01-01T00:00:00+02:00",
+            "updated_at": "2026-01-01T00:00:00+02:00",
+        })
+    }
+
+    #[test]
+    fn verifies_authenticated_metadata() {
+        let mac_key = generate_mac_key();
+        let metadata = metadata();
+        let mac = authenticate(&mac_key, &metadata).unwrap();
+
+        assert!(verify(&mac_key, &metadata, Some(&mac)).is_ok());
+    }
+
+    #[test]
+    fn ignores_the_file_path_and_name() {
+        let mac_key = generate_mac_key();
+        let mut metadata = metadata();
+        let mac = authenticate(&mac_key, &metadata).unwrap();
+
+        metadata["name"] = "renamed".into();
+        metadata["file_path"] = "/elsewhere/renamed.env".into();
+
+        assert!(verify(&mac_key, &metadata, Some(&mac)).is_ok());
+    }
+
+    #[test]
+    fn detects_tampered_metadata() {
+        let mac_key = generate_mac_key();
+        let metadata = metadata();
+        let mac = authenticate(&mac_key, &metadata).unwrap();
+
+        for (field, value) in [
+            ("description", "changed".into()),
+            ("created_at", "2026-01-01T00:00:00+03:00".into()),
+            ("added_later", true.into()),
+        ] {
+            let mut tampered = metadata.clone();
+            tampered[field] = value;
+
+            assert!(matches!(
+                verify(&mac_key, &tampered, Some(&mac)),
+                Err(Error::MetadataTampered)
+            ));
+        }
+    }
+
+    #[test]
+    fn authenticates_fields_it_does_not_know() {
+        let mac_key = generate_mac_key();
+        let mut metadata = metadata();
+        metadata["added_later"] = serde_json::json!({ "enabled": true });
+
+        let mac = authenticate(&mac_key, &metadata).unwrap();
+
+        assert!(verify(&mac_key, &metadata, Some(&mac)).is_ok());
+    }
+
+    #[test]
+    fn derives_a_mac_key_distinct_from_the_data_key() {
+        let data_key = [7u8; 32];
+        let mac_key = derive_mac_key(&data_key).unwrap();
+
+        assert_eq!(mac_key.len(), MAC_KEY_LEN);
+        assert_ne!(mac_key.as_slice(), data_key.as_slice());
+        assert_eq!(derive_mac_key(&data_key).unwrap(), mac_key);
+        assert_ne!(derive_mac_key(&[8u8; 32]).unwrap(), mac_key);
+    }
+
+    #[test]
+    fn detects_a_wrong_or_missing_mac() {
+        let mac_key = generate_mac_key();
+        let metadata = metadata();
+        let mut mac = authenticate(&mac_key, &metadata).unwrap();
+
+        assert!(verify(&generate_mac_key(), &metadata, Some(&mac)).is_err());
+        assert!(verify(&mac_key, &metadata, None).is_err());
+        assert!(verify(&mac_key, &metadata, Some(&mac[..NONCE_LEN - 1])).is_err());
+
+        *mac.last_mut().unwrap() ^= 1;
+        assert!(verify(&mac_key, &metadata, Some(&mac)).is_err());
+    }
+
+    #[test]
+    fn opens_sealed_envs() {
+        let envs = EnvMap::from(vec![Env::from_key_value("KEY", "value")]);
+        let mac_key = generate_mac_key();
+
+        let (opened, opened_key) = open_envs(&seal_envs(&envs, &mac_key).unwrap()).unwrap();
+
+        assert_eq!(opened.get("KEY").unwrap().value, "value");
+        assert_eq!(opened_key.unwrap().as_slice(), mac_key.as_slice());
+    }
+
+    #[test]
+    fn opens_envs_saved_without_a_mac_key() {
+        let envs = EnvMap::from(vec![Env::from_key_value("KEY", "value")]);
+
+        let (opened, mac_key) = open_envs(&envs.as_bytes().unwrap()).unwrap();
+
+        assert_eq!(opened.get("KEY").unwrap().value, "value");
+        assert!(mac_key.is_none());
+    }
+
+    #[test]
+    fn rejects_a_truncated_mac_key() {
+        let envs = EnvMap::from(vec![Env::from_key_value("KEY", "value")]);
+        let mut plaintext = envs.as_bytes().unwrap();
+        plaintext.extend_from_slice(&[0; 3]);
+
+        assert!(open_envs(&plaintext).is_err());
+    }
+}
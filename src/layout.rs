@@ -0,0 +1,517 @@
+use std::collections::HashMap;
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use chacha20poly1305::{
+    Key, XChaCha20Poly1305, XNonce,
+    aead::{Aead, AeadCore, KeyInit, Payload},
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_with::{base64::Base64, serde_as};
+use strum_macros::{AsRefStr, EnumIter, EnumString};
+use zeroize::Zeroizing;
+
+use crate::{
+    cipher::{Cipher, CipherKind, EncryptedContent},
+    env::{Env, EnvExtension, EnvMap},
+    error::{Error, Result},
+};
+
+const DATA_KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 24;
+
+/// how the environment variables of a profile are laid out in its file
+#[derive(
+    Copy, Clone, PartialEq, Default, Debug, Serialize, Deserialize, EnumIter, EnumString, AsRefStr,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum ContentLayout {
+    /// the whole `EnvMap` is encrypted as a single blob
+    #[default]
+    #[strum(ascii_case_insensitive, to_string = "blob")]
+    Blob,
+    /// keys stay in cleartext and every value is encrypted on its own
+    #[strum(ascii_case_insensitive, to_string = "per-entry")]
+    PerEntry,
+}
+
+impl ContentLayout {
+    pub fn is_blob(&self) -> bool {
+        *self == ContentLayout::Blob
+    }
+}
+
+impl std::fmt::Display for ContentLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct EncryptedEnv {
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiration_date: Option<NaiveDate>,
+    /// nonce followed by the ciphertext of the value (and comment unless it is in cleartext)
+    #[serde_as(as = "Base64")]
+    pub value: Vec<u8>,
+}
+
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct EncryptedEntries {
+    /// the data key every entry is encrypted with, encrypted by the profile's cipher
+    #[serde_as(as = "Base64")]
+    pub key: Vec<u8>,
+    pub entries: Vec<EncryptedEnv>,
+}
+
+/// the extension is appended after the rest when it is not empty, so entries sealed before it
+/// existed can still be opened
+#[derive(Serialize, Deserialize, PartialEq)]
+struct Secret {
+    value: String,
+    comment: Option<String>,
+    #[serde(skip)]
+    extension: EnvExtension,
+}
+
+/// the unwrapped data key of a per-entry profile along with the entries as they were loaded, so
+/// unchanged entries keep their ciphertext and do not show up in diffs
+#[derive(Clone)]
+pub(crate) struct EntryState {
+    data_key: Zeroizing<Vec<u8>>,
+    wrapped_key: Vec<u8>,
+    loaded: HashMap<String, EncryptedEnv>,
+}
+
+impl EntryState {
+    pub fn generate(cipher: &mut dyn Cipher) -> Result<EntryState> {
+        if cipher.kind() == CipherKind::NONE {
+            return Err(Error::Cipher(
+                "The per-entry layout requires an encrypting cipher".to_string(),
+            ));
+        }
+
+        let mut data_key = Zeroizing::new(vec![0u8; DATA_KEY_LEN]);
+        OsRng.fill_bytes(&mut data_key);
+
+        let wrapped_key = cipher.encrypt_bytes(&data_key)?.as_bytes()?;
+
+        Ok(EntryState {
+            data_key,
+            wrapped_key,
+            loaded: HashMap::new(),
+        })
+    }
+
+    pub fn unwrap(cipher: &dyn Cipher, entries: &EncryptedEntries) -> Result<EntryState> {
+        let data_key =
+            Zeroizing::new(cipher.decrypt_bytes(&EncryptedContent::Bytes(entries.key.clone()))?);
+
+        if data_key.len() != DATA_KEY_LEN {
+            return Err(Error::Cipher("Invalid data key".to_string()));
+        }
+
+        Ok(EntryState {
+            data_key,
+            wrapped_key: entries.key.clone(),
+            loaded: entries
+                .entries
+                .iter()
+                .map(|entry| (entry.key.clone(), entry.clone()))
+                .collect(),
+        })
+    }
+
+    /// wraps the data key again, for example after the parameters of the cipher changed. the
+    /// entries keep their ciphertext
+    pub fn rewrap(&mut self, cipher: &mut dyn Cipher) -> Result<()> {
+        self.wrapped_key = cipher.encrypt_bytes(&self.data_key)?.as_bytes()?;
+
+        Ok(())
+    }
+
+    pub fn data_key(&self) -> &[u8] {
+        &self.data_key
+    }
+
+    fn aead(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(Key::from_slice(&self.data_key))
+    }
+
+    /// the cleartext parts of an entry are bound to its value so they can not be swapped around
+    fn associated_data(entry: &EncryptedEnv) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&(
+            &entry.key,
+            &entry.comment,
+            &entry.expiration_date,
+        ))?)
+    }
+
+    fn seal(&self, entry: &EncryptedEnv, secret: &Secret) -> Result<Vec<u8>> {
+        let mut plaintext = Zeroizing::new(bincode::serde::encode_to_vec(
+            secret,
+            bincode::config::standard(),
+        )?);
+
+        if !secret.extension.is_empty() {
+            plaintext.extend(serde_json::to_vec(&secret.extension)?);
+        }
+
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+        let ciphertext = self
+            .aead()
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: &plaintext,
+                    aad: &Self::associated_data(entry)?,
+                },
+            )
+            .map_err(|e| Error::Cipher(e.to_string()))?;
+
+        Ok([nonce.as_slice(), &ciphertext].concat())
+    }
+
+    fn open(&self, entry: &EncryptedEnv) -> Result<Secret> {
+        if entry.value.len() < NONCE_LEN {
+            return Err(Error::Cipher(format!(
+                "Invalid ciphertext for `{}`",
+                entry.key
+            )));
+        }
+
+        let (nonce, ciphertext) = entry.value.split_at(NONCE_LEN);
+
+        let plaintext = Zeroizing::new(
+            self.aead()
+                .decrypt(
+                    XNonce::from_slice(nonce),
+                    Payload {
+                        msg: ciphertext,
+                        aad: &Self::associated_data(entry)?,
+                    },
+                )
+                .map_err(|_| {
+                    Error::Cipher(format!("Failed to decrypt the value of `{}`", entry.key))
+                })?,
+        );
+
+        let (mut secret, len): (Secret, usize) =
+            bincode::serde::decode_from_slice(&plaintext, bincode::config::standard())?;
+
+        if len < plaintext.len() {
+            secret.extension = serde_json::from_slice(&plaintext[len..])?;
+        }
+
+        Ok(secret)
+    }
+
+    pub fn encrypt(&mut self, envs: &EnvMap, clear_comments: bool) -> Result<EncryptedEntries> {
+        let mut entries = Vec::with_capacity(envs.len());
+
+        for env in envs {
+            let secret = Secret {
+                value: env.value.clone(),
+                comment: if clear_comments {
+                    None
+                } else {
+                    env.comment.clone()
+                },
+                extension: EnvExtension::from_env(env),
+            };
+
+            let mut entry = EncryptedEnv {
+                key: env.key.clone(),
+                comment: if clear_comments {
+                    env.comment.clone()
+                } else {
+                    None
+                },
+                expiration_date: env.expiration_date,
+                value: vec![],
+            };
+
+            entry.value = match self.loaded.get(&env.key) {
+                Some(loaded)
+                    if loaded.comment == entry.comment
+                        && loaded.expiration_date == entry.expiration_date
+                        && self.open(loaded).is_ok_and(|s| s == secret) =>
+                {
+                    loaded.value.clone()
+                }
+                _ => self.seal(&entry, &secret)?,
+            };
+
+            entries.push(entry);
+        }
+
+        self.loaded = entries
+            .iter()
+            .map(|entry| (entry.key.clone(), entry.clone()))
+            .collect();
+
+        Ok(EncryptedEntries {
+            key: self.wrapped_key.clone(),
+            entries,
+        })
+    }
+
+    /// decrypts the entries whose key is in `keys`, or all of them
+    pub fn decrypt(&self, entries: &EncryptedEntries, keys: Option<&[String]>) -> Result<EnvMap> {
+        let mut envs = EnvMap::default();
+
+        for entry in &entries.entries {
+            if keys.is_some_and(|keys| !keys.contains(&entry.key)) {
+                continue;
+            }
+
+            let secret = self.open(entry)?;
+
+            let mut env = Env::new(
+                entry.key.clone(),
+                secret.value,
+                entry.comment.clone().or(secret.comment),
+                entry.expiration_date,
+            );
+            secret.extension.apply(&mut env);
+
+            envs.insert_unchecked(env);
+        }
+
+        Ok(envs)
+    }
+}
+
+/// three-way merge of two per-entry contents on the level of single entries, returns `None` if
+/// the same entry was changed on both sides or the contents do not share the same data key
+pub fn merge_entries(
+    base: &EncryptedEntries,
+    ours: &EncryptedEntries,
+    theirs: &EncryptedEntries,
+) -> Option<EncryptedEntries> {
+    if base.key != ours.key || base.key != theirs.key {
+        return None;
+    }
+
+    let find = |entries: &EncryptedEntries, key: &str| {
+        entries.entries.iter().find(|e| e.key == key).cloned()
+    };
+
+    let mut keys: Vec<&String> = ours.entries.iter().map(|e| &e.key).collect();
+    for entry in theirs.entries.iter().chain(base.entries.iter()) {
+        if !keys.contains(&&entry.key) {
+            keys.push(&entry.key);
+        }
+    }
+
+    let mut entries = Vec::new();
+
+    for key in keys {
+        let (b, o, t) = (find(base, key), find(ours, key), find(theirs, key));
+
+        let merged = if o == t || t == b {
+            o
+        } else if o == b {
+            t
+        } else {
+            return None;
+        };
+
+        entries.extend(merged);
+    }
+
+    Some(EncryptedEntries {
+        key: ours.key.clone(),
+        entries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// wraps the data key with a single byte, enough to tell a wrong key apart without paying
+    /// for a real key derivation
+    #[derive(Clone)]
+    struct TestCipher(u8);
+
+    impl Cipher for TestCipher {
+        fn encrypt_bytes(&mut self, data: &[u8]) -> Result<EncryptedContent> {
+            Ok(EncryptedContent::Bytes(
+                std::iter::once(self.0)
+                    .chain(data.iter().copied())
+                    .collect(),
+            ))
+        }
+
+        fn decrypt_bytes(&self, encrypted_data: &EncryptedContent) -> Result<Vec<u8>> {
+            match encrypted_data.as_bytes()?.split_first() {
+                Some((&key, data)) if key == self.0 => Ok(data.to_vec()),
+                _ => Err(Error::Cipher("wrong key".to_string())),
+            }
+        }
+
+        fn kind(&self) -> CipherKind {
+            CipherKind::PASSPHRASE
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+    }
+
+    fn envs() -> EnvMap {
+        EnvMap::from(vec![
+            Env::new("A", "1", Some("first".to_string()), None),
+            Env::from_key_value("B", "2"),
+        ])
+    }
+
+    fn encrypt(clear_comments: bool) -> (EntryState, EncryptedEntries) {
+        let mut state = EntryState::generate(&mut TestCipher(1)).unwrap();
+        let entries = state.encrypt(&envs(), clear_comments).unwrap();
+        (state, entries)
+    }
+
+    fn entry(key: &str, value: u8) -> EncryptedEnv {
+        EncryptedEnv {
+            key: key.to_string(),
+            comment: None,
+            expiration_date: None,
+            value: vec![value],
+        }
+    }
+
+    fn entries(entries: Vec<EncryptedEnv>) -> EncryptedEntries {
+        EncryptedEntries {
+            key: vec![0],
+            entries,
+        }
+    }
+
+    #[test]
+    fn decrypts_encrypted_entries() {
+        let (_, entries) = encrypt(false);
+
+        let state = EntryState::unwrap(&TestCipher(1), &entries).unwrap();
+        let decrypted = state.decrypt(&entries, None).unwrap();
+
+        let a = decrypted.get("A").unwrap();
+        assert_eq!(a.value, "1");
+        assert_eq!(a.comment.as_deref(), Some("first"));
+        assert_eq!(decrypted.get("B").unwrap().value, "2");
+        assert!(entries.entries.iter().all(|entry| entry.comment.is_none()));
+    }
+
+    #[test]
+    fn keeps_clear_comments_next_to_the_key() {
+        let (state, entries) = encrypt(true);
+
+        assert_eq!(entries.entries[0].comment.as_deref(), Some("first"));
+        assert_eq!(
+            state
+                .decrypt(&entries, None)
+                .unwrap()
+                .get("A")
+                .unwrap()
+                .comment
+                .as_deref(),
+            Some("first")
+        );
+    }
+
+    #[test]
+    fn decrypts_only_the_requested_keys() {
+        let (state, entries) = encrypt(false);
+
+        let decrypted = state.decrypt(&entries, Some(&["A".to_string()])).unwrap();
+
+        assert!(decrypted.contains_key("A"));
+        assert!(!decrypted.contains_key("B"));
+    }
+
+    #[test]
+    fn keeps_the_ciphertext_of_unchanged_entries() {
+        let (mut state, before) = encrypt(false);
+
+        let mut changed = envs();
+        changed.iter_mut().find(|env| env.key == "B").unwrap().value = "3".to_string();
+        let after = state.encrypt(&changed, false).unwrap();
+
+        assert!(before.entries[0] == after.entries[0]);
+        assert!(before.entries[1] != after.entries[1]);
+    }
+
+    #[test]
+    fn detects_tampered_entries() {
+        let (state, entries) = encrypt(true);
+
+        let mut flipped = entries.clone();
+        *flipped.entries[0].value.last_mut().unwrap() ^= 1;
+        assert!(state.decrypt(&flipped, None).is_err());
+
+        // the cleartext parts are bound to the value they belong to
+        let mut swapped = entries.clone();
+        swapped.entries[0].key = "B".to_string();
+        swapped.entries[1].key = "A".to_string();
+        assert!(state.decrypt(&swapped, None).is_err());
+
+        let mut recommented = entries.clone();
+        recommented.entries[0].comment = Some("changed".to_string());
+        assert!(state.decrypt(&recommented, None).is_err());
+    }
+
+    #[test]
+    fn rejects_a_wrong_key() {
+        let (_, entries) = encrypt(false);
+
+        assert!(EntryState::unwrap(&TestCipher(2), &entries).is_err());
+    }
+
+    #[test]
+    fn requires_an_encrypting_cipher() {
+        assert!(EntryState::generate(&mut crate::cipher::NONE).is_err());
+    }
+
+    #[test]
+    fn merges_changes_to_different_entries() {
+        let base = entries(vec![entry("A", 1), entry("B", 1)]);
+        let ours = entries(vec![entry("A", 2), entry("B", 1)]);
+        let theirs = entries(vec![entry("A", 1), entry("C", 1)]);
+
+        let merged = merge_entries(&base, &ours, &theirs).unwrap();
+
+        assert!(merged.entries == vec![entry("A", 2), entry("C", 1)]);
+    }
+
+    #[test]
+    fn does_not_merge_changes_to_the_same_entry() {
+        let base = entries(vec![entry("A", 1)]);
+        let ours = entries(vec![entry("A", 2)]);
+        let theirs = entries(vec![entry("A", 3)]);
+
+        assert!(merge_entries(&base, &ours, &theirs).is_none());
+
+        let removed = entries(vec![]);
+        assert!(merge_entries(&base, &ours, &removed).is_none());
+    }
+
+    #[test]
+    fn does_not_merge_different_data_keys() {
+        let base = entries(vec![entry("A", 1)]);
+        let mut theirs = entries(vec![entry("B", 1)]);
+        theirs.key = vec![1];
+
+        assert!(merge_entries(&base, &base, &theirs).is_none());
+    }
+}
@@ -1,21 +1,26 @@
 pub mod cipher;
 pub mod env;
 pub mod error;
+pub mod integrity;
+pub mod layout;
 pub mod profile;
+pub mod schema;
+pub mod store;
 pub mod utils;
 
 use std::path::Path;
 use zeroize::Zeroizing;
 
-pub use env::{Env, EnvMap};
+pub use env::{Env, EnvMap, KeyPolicy, RotationPolicy, Tags};
 pub use profile::{Profile, ProfileMetadata};
+pub use schema::{Schema, SchemaRule, ValueType};
 
 use crate::{
-    cipher::{AGE, CipherKind, PASSPHRASE, get_profile_cipher},
+    cipher::{AGE, Cipher, CipherKind, PASSPHRASE, get_profile_cipher},
     error::{Error, Result},
 };
 
-pub fn get_profile<P, F>(file_path: P, key_provider: Option<F>) -> Result<Profile>
+fn get_cipher<P, F>(file_path: P, key_provider: Option<F>) -> Result<Box<dyn Cipher>>
 where
     P: AsRef<Path>,
     F: FnOnce() -> Zeroizing<String>,
@@ -44,9 +49,34 @@ where
         }
     }
 
+    Ok(cipher)
+}
+
+pub fn get_profile<P, F>(file_path: P, key_provider: Option<F>) -> Result<Profile>
+where
+    P: AsRef<Path>,
+    F: FnOnce() -> Zeroizing<String>,
+{
+    let cipher = get_cipher(&file_path, key_provider)?;
+
     Profile::from_file(file_path, cipher)
 }
 
+/// like `get_profile` but only decrypts the environment variables in `keys`
+pub fn get_partial_profile<P, F>(
+    file_path: P,
+    key_provider: Option<F>,
+    keys: &[String],
+) -> Result<Profile>
+where
+    P: AsRef<Path>,
+    F: FnOnce() -> Zeroizing<String>,
+{
+    let cipher = get_cipher(&file_path, key_provider)?;
+
+    Profile::from_file_partial(file_path, cipher, keys)
+}
+
 pub fn load_profile<P, F>(file_path: P, key_provider: Option<F>) -> Result<Profile>
 where
     P: AsRef<Path>,
@@ -1,16 +1,55 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 use chrono::{DateTime, Local};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_with::{base64::Base64, serde_as};
+use strum_macros::{AsRefStr, EnumIter, EnumString};
+use zeroize::Zeroizing;
 
 use crate::{
     cipher::{Cipher, CipherKind, EncryptedContent},
-    env::EnvMap,
-    error::Result,
-    utils::{get_serialized_profile, save_serialized_profile},
+    env::{EnvMap, KeyPolicy, Tags},
+    error::{Error, Result},
+    integrity::{authenticate, derive_mac_key, generate_mac_key, open_envs, seal_envs, verify},
+    layout::{ContentLayout, EntryState, merge_entries},
+    schema::Schema,
+    store::{ProfileStore, get_profile_store, open_store},
 };
 
-#[derive(Clone, Serialize, Deserialize)]
+/// what happens to expired environment variables when a profile is used
+#[derive(
+    Copy, Clone, PartialEq, Default, Debug, Serialize, Deserialize, EnumIter, EnumString, AsRefStr,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ExpiredPolicy {
+    /// print a warning and use them anyway
+    #[default]
+    #[strum(ascii_case_insensitive, to_string = "warn")]
+    Warn,
+    /// leave them out
+    #[strum(ascii_case_insensitive, to_string = "skip")]
+    Skip,
+    /// refuse to use the profile
+    #[strum(ascii_case_insensitive, to_string = "fail")]
+    Fail,
+}
+
+impl ExpiredPolicy {
+    pub fn is_warn(&self) -> bool {
+        *self == ExpiredPolicy::Warn
+    }
+}
+
+impl std::fmt::Display for ExpiredPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProfileMetadata {
     pub name: String,
     pub version: String,
@@ -20,6 +59,21 @@ pub struct ProfileMetadata {
     pub cipher_kind: CipherKind,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cipher_metadata: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "ContentLayout::is_blob")]
+    pub layout: ContentLayout,
+    /// keep the comments of a per-entry profile in cleartext next to the keys
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub clear_comments: bool,
+    #[serde(default, skip_serializing_if = "ExpiredPolicy::is_warn")]
+    pub on_expired: ExpiredPolicy,
+    #[serde(default, skip_serializing_if = "KeyPolicy::is_posix")]
+    pub key_policy: KeyPolicy,
+    /// kept in the clear so profiles can be filtered by them without being decrypted
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: Tags,
+    /// the environment variables the profile is expected to have, checked by `envio check`
+    #[serde(default, skip_serializing_if = "Schema::is_empty")]
+    pub schema: Schema,
     pub created_at: DateTime<Local>,
     pub updated_at: DateTime<Local>,
 }
@@ -29,12 +83,59 @@ pub struct Profile {
     pub metadata: ProfileMetadata,
     pub envs: EnvMap,
     pub cipher: Box<dyn Cipher>,
+    pub store: Box<dyn ProfileStore>,
+    entries: Option<EntryState>,
+    partial: bool,
+    /// the environment variables as they were last read or saved, to tell which ones changed
+    saved_envs: EnvMap,
 }
 
-#[derive(Serialize, Deserialize)]
 pub struct SerializedProfile {
     pub metadata: ProfileMetadata,
     pub content: EncryptedContent,
+    /// authenticates the metadata with a key only known to those able to decrypt the content
+    pub mac: Option<Vec<u8>>,
+    /// the metadata exactly as it was stored, which is what the mac covers. it is written back
+    /// as long as `metadata` only differs from it in the name and path, so fields added by newer
+    /// versions survive profiles being synced, merged or imported
+    pub stored_metadata: Option<serde_json::Value>,
+}
+
+/// how a profile is laid out in its file
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+struct StoredProfile<M, C> {
+    metadata: M,
+    content: C,
+    #[serde_as(as = "Option<Base64>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mac: Option<Vec<u8>>,
+}
+
+impl Serialize for SerializedProfile {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        StoredProfile {
+            metadata: self.metadata_json().map_err(serde::ser::Error::custom)?,
+            content: &self.content,
+            mac: self.mac.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SerializedProfile {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let stored =
+            StoredProfile::<serde_json::Value, EncryptedContent>::deserialize(deserializer)?;
+
+        Ok(SerializedProfile {
+            metadata: ProfileMetadata::deserialize(&stored.metadata)
+                .map_err(serde::de::Error::custom)?,
+            content: stored.content,
+            mac: stored.mac,
+            stored_metadata: Some(stored.metadata),
+        })
+    }
 }
 
 impl Profile {
@@ -45,6 +146,8 @@ impl Profile {
         envs: EnvMap,
         cipher: Box<dyn Cipher>,
     ) -> Profile {
+        let store = get_profile_store(&file_path, &name);
+
         Profile {
             metadata: ProfileMetadata {
                 name,
@@ -53,41 +156,573 @@ impl Profile {
                 file_path,
                 cipher_kind: cipher.kind(),
                 cipher_metadata: cipher.export_metadata(),
+                layout: ContentLayout::default(),
+                clear_comments: false,
+                on_expired: ExpiredPolicy::default(),
+                key_policy: envs.key_policy(),
+                tags: Tags::new(),
+                schema: Schema::new(),
                 created_at: Local::now(),
                 updated_at: Local::now(),
             },
             envs,
             cipher,
+            store,
+            entries: None,
+            partial: false,
+            saved_envs: EnvMap::default(),
         }
     }
 
-    pub fn from_file<P: AsRef<Path>>(file_path: P, mut cipher: Box<dyn Cipher>) -> Result<Profile> {
-        let serialized_profile = get_serialized_profile(&file_path)?;
+    pub fn from_file<P: AsRef<Path>>(file_path: P, cipher: Box<dyn Cipher>) -> Result<Profile> {
+        Self::read(file_path, cipher, None)
+    }
+
+    /// only decrypts the environment variables in `keys`, which for per-entry profiles avoids
+    /// touching the other values at all. partial profiles can not be saved
+    pub fn from_file_partial<P: AsRef<Path>>(
+        file_path: P,
+        cipher: Box<dyn Cipher>,
+        keys: &[String],
+    ) -> Result<Profile> {
+        Self::read(file_path, cipher, Some(keys))
+    }
+
+    fn read<P: AsRef<Path>>(
+        file_path: P,
+        mut cipher: Box<dyn Cipher>,
+        keys: Option<&[String]>,
+    ) -> Result<Profile> {
+        // the store a namespaced profile belongs to can only be found once its name is known,
+        // until then it is read through the store of the directory it is in
+        let dir = file_path
+            .as_ref()
+            .parent()
+            .unwrap_or_else(|| Path::new("."));
+        let mut serialized_profile = open_store(dir).read_profile(file_path.as_ref())?;
+        let store = get_profile_store(&file_path, &serialized_profile.metadata.name);
+
+        // the stored path is stale when the profile was copied or synced from elsewhere
+        serialized_profile.metadata.file_path = file_path.as_ref().to_path_buf();
 
         if let Some(cipher_metadata) = &serialized_profile.metadata.cipher_metadata {
             cipher.import_metadata(cipher_metadata.clone())?;
         }
 
+        let metadata = &serialized_profile.metadata;
+        let stored_metadata = serialized_profile.metadata_json()?;
+        let mac = serialized_profile.mac.as_deref();
+
+        let (mut envs, entries) = match &serialized_profile.content {
+            EncryptedContent::Entries(encrypted_entries) => {
+                let state = EntryState::unwrap(cipher.as_ref(), encrypted_entries)?;
+                verify(&derive_mac_key(state.data_key())?, &stored_metadata, mac)?;
+
+                (state.decrypt(encrypted_entries, keys)?, Some(state))
+            }
+            content if cipher.kind() == CipherKind::NONE => (cipher.decrypt(content)?, None),
+            content => {
+                let plaintext = Zeroizing::new(cipher.decrypt_bytes(content)?);
+
+                match open_envs(&plaintext)? {
+                    (envs, Some(mac_key)) => {
+                        verify(&mac_key, &stored_metadata, mac)?;
+                        (envs, None)
+                    }
+                    // saved before metadata was authenticated, it will be on the next save
+                    (envs, None) if mac.is_none() => (envs, None),
+                    _ => return Err(Error::MetadataTampered),
+                }
+            }
+        };
+
+        if let Some(keys) = keys {
+            envs.retain(|env| keys.contains(&env.key));
+        }
+
+        envs.set_key_policy(metadata.key_policy);
+
+        // saved before changes were tracked, the values are assumed to be as old as the profile
+        for env in envs.iter_mut().filter(|env| env.updated_at.is_none()) {
+            env.created_at = Some(metadata.created_at);
+            env.updated_at = Some(metadata.created_at);
+        }
+
         Ok(Profile {
             metadata: serialized_profile.metadata,
-            envs: cipher.decrypt(&serialized_profile.content)?,
+            saved_envs: envs.clone(),
+            envs,
             cipher,
+            store,
+            entries,
+            partial: keys.is_some(),
         })
     }
 
+    /// makes the next save encrypt everything with the current settings of the cipher. blob
+    /// profiles are always encrypted from scratch, per-entry profiles otherwise keep the data key
+    /// as it was wrapped when they were created
+    pub fn rewrap_data_key(&mut self) -> Result<()> {
+        if let Some(state) = &mut self.entries {
+            state.rewrap(self.cipher.as_mut())?;
+        }
+
+        Ok(())
+    }
+
     pub fn save(&mut self) -> Result<()> {
-        let encrypted_envs = self.cipher.encrypt(&self.envs)?;
+        if self.partial {
+            return Err(Error::Msg(
+                "Can not save a partially decrypted profile".to_string(),
+            ));
+        }
+
+        self.record_changes();
+
+        let (encrypted_envs, mac_key) = match self.metadata.layout {
+            ContentLayout::Blob => {
+                self.entries = None;
+
+                if self.cipher.kind() == CipherKind::NONE {
+                    // there is no secret to authenticate the metadata of unencrypted profiles with
+                    (self.cipher.encrypt(&self.envs)?, None)
+                } else {
+                    let mac_key = generate_mac_key();
+                    let plaintext = seal_envs(&self.envs, &mac_key)?;
+
+                    (self.cipher.encrypt_bytes(&plaintext)?, Some(mac_key))
+                }
+            }
+            ContentLayout::PerEntry => {
+                // the data key is only wrapped once, so the cipher metadata stays the same too
+                let state = match &mut self.entries {
+                    Some(state) => state,
+                    None => self
+                        .entries
+                        .insert(EntryState::generate(self.cipher.as_mut())?),
+                };
+
+                (
+                    EncryptedContent::Entries(
+                        state.encrypt(&self.envs, self.metadata.clear_comments)?,
+                    ),
+                    Some(derive_mac_key(state.data_key())?),
+                )
+            }
+        };
 
         self.metadata.updated_at = Local::now();
         self.metadata.cipher_metadata = self.cipher.export_metadata();
 
+        let metadata_json = serde_json::to_value(&self.metadata)?;
+        let mac = mac_key
+            .map(|mac_key| authenticate(&mac_key, &metadata_json))
+            .transpose()?;
+
         let serialized_profile = SerializedProfile {
             metadata: self.metadata.clone(),
             content: encrypted_envs,
+            mac,
+            stored_metadata: Some(metadata_json),
         };
 
-        save_serialized_profile(&self.metadata.file_path, serialized_profile)?;
+        self.store
+            .save_profile(&self.metadata.file_path, serialized_profile)?;
+
+        self.saved_envs = self.envs.clone();
 
         Ok(())
     }
+
+    /// stamps the environment variables whose value changed since the profile was last read or
+    /// saved with the current time and user. replaced variables with the same value keep their
+    /// history, added ones that already have one such as copies keep it as well
+    fn record_changes(&mut self) {
+        let now = Local::now();
+        let user = current_user();
+
+        for env in self.envs.iter_mut() {
+            match self.saved_envs.get(&env.key) {
+                Some(saved) if saved.value == env.value && env.updated_at.is_none() => {
+                    env.created_at = saved.created_at;
+                    env.updated_at = saved.updated_at;
+                    env.updated_by = saved.updated_by.clone();
+                }
+                Some(saved) if saved.value != env.value => {
+                    env.created_at = env.created_at.or(saved.created_at).or(Some(now));
+                    env.updated_at = Some(now);
+                    env.updated_by = user.clone();
+                }
+                None if env.updated_at.is_none() => {
+                    env.created_at = Some(now);
+                    env.updated_at = Some(now);
+                    env.updated_by = user.clone();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// renames the profile and moves its file to `file_path` within the same store, the metadata
+    /// is saved again so it stays authenticated
+    pub fn rename(&mut self, name: String, file_path: PathBuf) -> Result<()> {
+        if file_path.exists() {
+            return Err(Error::Msg(format!(
+                "`{}` already exists",
+                file_path.display()
+            )));
+        }
+
+        if self.partial {
+            return Err(Error::Msg(
+                "Can not rename a partially decrypted profile".to_string(),
+            ));
+        }
+
+        if let Some(dir) = file_path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        self.store
+            .rename_profile(&self.metadata.file_path, &file_path)?;
+
+        self.metadata.name = name;
+        self.metadata.file_path = file_path;
+        self.save()
+    }
+
+    pub fn set_description(&mut self, description: Option<String>) -> Result<()> {
+        self.metadata.description = description;
+        self.save()
+    }
+
+    /// changes which keys new environment variables may have, existing ones are left untouched
+    pub fn set_key_policy(&mut self, key_policy: KeyPolicy) -> Result<()> {
+        self.metadata.key_policy = key_policy;
+        self.envs.set_key_policy(key_policy);
+        self.save()
+    }
+
+    /// saves a duplicate of the profile as `name` at `file_path`, encrypted with `cipher` or the
+    /// cipher of this profile
+    pub fn copy(
+        &self,
+        name: String,
+        file_path: PathBuf,
+        cipher: Option<Box<dyn Cipher>>,
+    ) -> Result<Profile> {
+        if file_path.exists() {
+            return Err(Error::Msg(format!(
+                "`{}` already exists",
+                file_path.display()
+            )));
+        }
+
+        if self.partial {
+            return Err(Error::Msg(
+                "Can not copy a partially decrypted profile".to_string(),
+            ));
+        }
+
+        let mut copy = Profile::new(
+            name,
+            self.metadata.description.clone(),
+            file_path,
+            self.envs.clone(),
+            cipher.unwrap_or_else(|| self.cipher.clone()),
+        );
+
+        copy.metadata.layout = self.metadata.layout;
+        copy.metadata.clear_comments = self.metadata.clear_comments;
+        copy.metadata.on_expired = self.metadata.on_expired;
+        copy.metadata.key_policy = self.metadata.key_policy;
+        copy.metadata.tags = self.metadata.tags.clone();
+        copy.metadata.schema = self.metadata.schema.clone();
+        copy.save()?;
+
+        Ok(copy)
+    }
+}
+
+impl SerializedProfile {
+    /// the metadata as it is written, which is what the mac is computed over
+    pub(crate) fn metadata_json(&self) -> Result<serde_json::Value> {
+        if let Some(stored) = &self.stored_metadata {
+            let mut stored = stored.clone();
+            stored["name"] = self.metadata.name.clone().into();
+            stored["file_path"] = serde_json::to_value(&self.metadata.file_path)?;
+
+            if ProfileMetadata::deserialize(&stored).is_ok_and(|m| m == self.metadata) {
+                return Ok(stored);
+            }
+        }
+
+        Ok(serde_json::to_value(&self.metadata)?)
+    }
+
+    /// three-way merge of two versions of a per-entry profile, returns `None` if they can not be
+    /// merged without picking a side
+    pub fn merge(
+        base: &SerializedProfile,
+        ours: &SerializedProfile,
+        theirs: &SerializedProfile,
+    ) -> Option<SerializedProfile> {
+        let (
+            EncryptedContent::Entries(base_entries),
+            EncryptedContent::Entries(our_entries),
+            EncryptedContent::Entries(their_entries),
+        ) = (&base.content, &ours.content, &theirs.content)
+        else {
+            return None;
+        };
+
+        let entries = merge_entries(base_entries, our_entries, their_entries)?;
+
+        // the metadata is authenticated as a whole, so it is taken from one side along with its
+        // mac. every save touches `updated_at` which is why it is left out of the comparison
+        let comparable = |profile: &SerializedProfile| ProfileMetadata {
+            file_path: PathBuf::new(),
+            updated_at: profile.metadata.created_at,
+            ..profile.metadata.clone()
+        };
+
+        let (b, o, t) = (comparable(base), comparable(ours), comparable(theirs));
+
+        let side = if o == t {
+            if ours.metadata.updated_at >= theirs.metadata.updated_at {
+                ours
+            } else {
+                theirs
+            }
+        } else if o == b {
+            theirs
+        } else if t == b {
+            ours
+        } else {
+            return None;
+        };
+
+        Some(SerializedProfile {
+            metadata: ProfileMetadata {
+                file_path: ours.metadata.file_path.clone(),
+                ..side.metadata.clone()
+            },
+            content: EncryptedContent::Entries(entries),
+            mac: side.mac.clone(),
+            stored_metadata: side.stored_metadata.clone(),
+        })
+    }
+}
+
+/// who changes are attributed to, `USERNAME` is what Windows sets
+fn current_user() -> Option<String> {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok()
+        .filter(|user| !user.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{EncryptedEntries, EncryptedEnv};
+
+    fn profile(description: &str, entries: &[(&str, u8)]) -> SerializedProfile {
+        let entries = EncryptedEntries {
+            key: vec![0],
+            entries: entries
+                .iter()
+                .map(|(key, value)| EncryptedEnv {
+                    key: key.to_string(),
+                    comment: None,
+                    expiration_date: None,
+                    value: vec![*value],
+                })
+                .collect(),
+        };
+
+        serde_json::from_value(serde_json::json!({
+            "metadata": {
+                "name": "test",
+                "version": "0.0.0",
+                "description": description,
+                "file_path": "/profiles/test.env",
+                "cipher_kind": "passphrase",
+                "layout": "per-entry",
+                "created_at": "2026-01-01T00:00:00Z",
+                "updated_at": "2026-01-01T00:00:00Z",
+            },
+            "content": entries,
+        }))
+        .unwrap()
+    }
+
+    fn keys(profile: &SerializedProfile) -> Vec<String> {
+        match &profile.content {
+            EncryptedContent::Entries(entries) => {
+                entries.entries.iter().map(|e| e.key.clone()).collect()
+            }
+            _ => panic!("expected entries"),
+        }
+    }
+
+    #[test]
+    fn writes_back_fields_it_does_not_know() {
+        let mut stored = serde_json::to_value(profile("base", &[("A", 1)])).unwrap();
+        stored["metadata"]["added_later"] = true.into();
+
+        let mut profile: SerializedProfile = serde_json::from_value(stored).unwrap();
+        profile.metadata.name = "imported".to_string();
+        profile.metadata.file_path = "/profiles/imported.env".into();
+
+        let written = serde_json::to_value(&profile).unwrap();
+        assert_eq!(written["metadata"]["added_later"], true);
+        assert_eq!(written["metadata"]["name"], "imported");
+        assert_eq!(written["metadata"]["file_path"], "/profiles/imported.env");
+
+        // anything else that changed has to be authenticated again, so it is written as it is now
+        profile.metadata.description = Some("changed".to_string());
+
+        let written = serde_json::to_value(&profile).unwrap();
+        assert!(written["metadata"].get("added_later").is_none());
+        assert_eq!(written["metadata"]["description"], "changed");
+    }
+
+    fn passphrase() -> Box<dyn Cipher> {
+        use crate::cipher::{PASSPHRASE, passphrase::KdfParams};
+
+        let mut cipher = PASSPHRASE::new(Zeroizing::new("correct horse".to_string()));
+        cipher
+            .set_params(KdfParams {
+                memory: 8,
+                iterations: 1,
+                parallelism: 1,
+            })
+            .unwrap();
+        Box::new(cipher)
+    }
+
+    /// saves a profile, lets `edit` change its file and reads it back
+    fn resave(
+        layout: ContentLayout,
+        edit: impl Fn(&Profile, &mut serde_json::Value),
+    ) -> Result<Profile> {
+        let path =
+            std::env::temp_dir().join(format!("envio-test-{}-{}.env", std::process::id(), layout));
+
+        let mut profile = Profile::new(
+            "test".to_string(),
+            None,
+            path.clone(),
+            EnvMap::from(vec![crate::env::Env::from_key_value("A", "1")]),
+            passphrase(),
+        );
+        profile.metadata.layout = layout;
+        profile.save()?;
+
+        let mut stored: serde_json::Value = serde_json::from_slice(&std::fs::read(&path)?)?;
+        edit(&profile, &mut stored);
+        std::fs::write(&path, serde_json::to_vec(&stored)?)?;
+
+        let read = Profile::from_file(&path, passphrase());
+        std::fs::remove_file(&path)?;
+        read
+    }
+
+    #[test]
+    fn authenticates_the_stored_metadata() {
+        for layout in [ContentLayout::Blob, ContentLayout::PerEntry] {
+            let renamed = resave(layout, |_, stored| {
+                stored["metadata"]["name"] = "renamed".into();
+                stored["metadata"]["file_path"] = "/elsewhere/renamed.env".into();
+            })
+            .unwrap();
+            assert_eq!(renamed.envs.get("A").unwrap().value, "1");
+
+            for (field, value) in [("description", "changed"), ("added_later", "true")] {
+                let tampered = resave(layout, |_, stored| {
+                    stored["metadata"][field] = value.into();
+                });
+                assert!(
+                    matches!(tampered, Err(Error::MetadataTampered)),
+                    "{} {}",
+                    layout,
+                    field
+                );
+            }
+        }
+    }
+
+    /// authenticates the stored metadata of a per-entry profile again, as a version of envio
+    /// that knows about the changes to it would have
+    fn reauthenticate(profile: &Profile, stored: &mut serde_json::Value) {
+        use base64::{Engine, engine::general_purpose::STANDARD};
+
+        let state = profile.entries.as_ref().unwrap();
+        let mac_key = derive_mac_key(state.data_key()).unwrap();
+        let mac = authenticate(&mac_key, &stored["metadata"]).unwrap();
+        stored["mac"] = STANDARD.encode(mac).into();
+    }
+
+    #[test]
+    fn reads_metadata_written_by_newer_versions() {
+        let profile = resave(ContentLayout::PerEntry, |profile, stored| {
+            stored["metadata"]["added_later"] = true.into();
+            reauthenticate(profile, stored);
+        })
+        .unwrap();
+
+        assert_eq!(profile.envs.get("A").unwrap().value, "1");
+    }
+
+    #[test]
+    fn reads_schemas_with_rule_fields_it_does_not_know() {
+        let profile = resave(ContentLayout::PerEntry, |profile, stored| {
+            stored["metadata"]["schema"] = serde_json::json!({
+                "A": {"type": "port", "added_later": true},
+            });
+            reauthenticate(profile, stored);
+        })
+        .unwrap();
+
+        assert_eq!(
+            profile.metadata.schema["A"].value_type,
+            crate::schema::ValueType::Port
+        );
+    }
+
+    #[test]
+    fn merges_per_entry_profiles() {
+        let base = profile("base", &[("A", 1)]);
+        let ours = profile("base", &[("A", 1), ("B", 1)]);
+        let theirs = profile("changed", &[("A", 1), ("C", 1)]);
+
+        let merged = SerializedProfile::merge(&base, &ours, &theirs).unwrap();
+
+        assert_eq!(keys(&merged), ["A", "B", "C"]);
+        assert_eq!(merged.metadata.description.as_deref(), Some("changed"));
+    }
+
+    #[test]
+    fn does_not_merge_conflicting_entries() {
+        let base = profile("base", &[("A", 1)]);
+
+        assert!(
+            SerializedProfile::merge(
+                &base,
+                &profile("base", &[("A", 2)]),
+                &profile("base", &[("A", 3)])
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn does_not_merge_blob_profiles() {
+        let base = profile("base", &[("A", 1)]);
+        let mut blob = profile("base", &[]);
+        blob.content = EncryptedContent::Bytes(vec![1, 2, 3]);
+
+        assert!(SerializedProfile::merge(&blob, &base, &base).is_none());
+    }
 }
@@ -0,0 +1,120 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumIter, EnumString};
+
+/// the environment variables a profile is expected to have, by key
+pub type Schema = IndexMap<String, SchemaRule>;
+
+/// the kind of value an environment variable holds
+#[derive(
+    Copy, Clone, PartialEq, Default, Debug, Serialize, Deserialize, EnumIter, EnumString, AsRefStr,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum ValueType {
+    /// any value
+    #[default]
+    String,
+    /// an absolute url such as `https://example.com`
+    Url,
+    /// a signed 64-bit integer
+    Int,
+    /// a port number between 1 and 65535
+    Port,
+    /// `true`, `false`, `1`, `0`, `yes`, `no`, `on` or `off`
+    Bool,
+    /// standard base64 with padding
+    Base64,
+}
+
+impl ValueType {
+    pub fn is_string(&self) -> bool {
+        *self == ValueType::String
+    }
+}
+
+impl std::fmt::Display for ValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+/// what is expected of a single environment variable
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SchemaRule {
+    /// whether the variable has to be present with a value that is not empty
+    #[serde(default = "default_required", skip_serializing_if = "is_true")]
+    pub required: bool,
+    #[serde(default, rename = "type", skip_serializing_if = "ValueType::is_string")]
+    pub value_type: ValueType,
+    /// a regular expression the whole value has to match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// the only values the variable may have
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
+impl Default for SchemaRule {
+    fn default() -> Self {
+        SchemaRule {
+            required: true,
+            value_type: ValueType::default(),
+            pattern: None,
+            allowed: Vec::new(),
+            description: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_in_defaults() {
+        let schema: Schema = serde_json::from_str(r#"{"A": {}, "B": {"type": "port"}}"#).unwrap();
+
+        assert_eq!(schema["A"], SchemaRule::default());
+        assert!(schema["B"].required);
+        assert_eq!(schema["B"].value_type, ValueType::Port);
+    }
+
+    #[test]
+    fn leaves_out_defaults_when_serialized() {
+        let schema = Schema::from([
+            ("A".to_string(), SchemaRule::default()),
+            (
+                "B".to_string(),
+                SchemaRule {
+                    required: false,
+                    value_type: ValueType::Bool,
+                    ..SchemaRule::default()
+                },
+            ),
+        ]);
+
+        let json = serde_json::to_string(&schema).unwrap();
+
+        assert_eq!(json, r#"{"A":{},"B":{"required":false,"type":"bool"}}"#);
+        assert_eq!(serde_json::from_str::<Schema>(&json).unwrap(), schema);
+    }
+
+    #[test]
+    fn ignores_unknown_fields_in_stored_rules() {
+        let schema: Schema =
+            serde_json::from_str(r#"{"A": {"type": "port", "added_later": true}}"#).unwrap();
+
+        assert_eq!(schema["A"].value_type, ValueType::Port);
+    }
+}
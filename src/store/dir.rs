@@ -0,0 +1,32 @@
+use std::{
+    any::Any,
+    path::{Path, PathBuf},
+};
+
+use crate::store::{ProfileStore, StoreKind};
+
+/// plain directory on disk, no history is kept
+#[derive(Clone)]
+pub struct DirStore {
+    root: PathBuf,
+}
+
+impl DirStore {
+    pub fn new(root: PathBuf) -> Self {
+        DirStore { root }
+    }
+}
+
+impl ProfileStore for DirStore {
+    fn kind(&self) -> StoreKind {
+        StoreKind::DIR
+    }
+
+    fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
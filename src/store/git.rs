@@ -0,0 +1,416 @@
+use std::{
+    any::Any,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+use crate::{
+    error::{Error, Result},
+    profile::SerializedProfile,
+    store::{ConflictResolution, ProfileStore, StoreKind},
+    utils::save_serialized_profile,
+};
+
+const REMOTE: &str = "origin";
+
+// encrypted profiles can not be merged line by line, so git never mixes both versions of a
+// profile. per-entry profiles are merged entry by entry afterwards, everything else is reported
+// as a conflict
+const GITATTRIBUTES: &str = "*.env -merge\n";
+
+/// directory inside a git repository, every change to a profile is committed
+#[derive(Clone)]
+pub struct GitStore {
+    root: PathBuf,
+}
+
+impl GitStore {
+    pub fn new(root: PathBuf) -> Self {
+        GitStore { root }
+    }
+
+    /// turns `root` into a git backed store, cloning `remote` if the directory is empty
+    pub fn init(root: PathBuf, remote: Option<&str>) -> Result<Self> {
+        std::fs::create_dir_all(&root)?;
+        let store = GitStore::new(root);
+
+        let is_empty = std::fs::read_dir(&store.root)?.next().is_none();
+
+        match remote {
+            Some(remote) if is_empty => {
+                store.git(&["clone", "--quiet", remote, "."])?;
+            }
+            _ => {
+                if !store.root.join(".git").exists() {
+                    store.git(&["init", "--quiet"])?;
+                }
+
+                // initializing an existing store again points it at the new remote
+                if let Some(remote) = remote {
+                    if store.has_remote()? {
+                        store.git(&["remote", "set-url", REMOTE, remote])?;
+                    } else {
+                        store.git(&["remote", "add", REMOTE, remote])?;
+                    }
+                }
+            }
+        }
+
+        let gitattributes = store.root.join(".gitattributes");
+        if !gitattributes.exists() {
+            std::fs::write(gitattributes, GITATTRIBUTES)?;
+        }
+
+        store.commit("Initialize envio store")?;
+
+        Ok(store)
+    }
+
+    fn run(&self, args: &[&str]) -> Result<Output> {
+        let mut command = Command::new("git");
+        command.arg("-C").arg(&self.root);
+
+        // committing fails without an identity, which is common on CI runners
+        if !self.has_identity() {
+            command.args(["-c", "user.name=envio", "-c", "user.email=envio@localhost"]);
+        }
+
+        command
+            .args(args)
+            .output()
+            .map_err(|e| Error::Store(format!("Failed to execute git: {}", e)))
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String> {
+        let output = self.run(args)?;
+
+        if !output.status.success() {
+            return Err(Error::Store(format!(
+                "`git {}` failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn has_identity(&self) -> bool {
+        Command::new("git")
+            .arg("-C")
+            .arg(&self.root)
+            .args(["config", "user.email"])
+            .output()
+            .is_ok_and(|output| output.status.success())
+    }
+
+    fn has_remote(&self) -> Result<bool> {
+        Ok(self.git(&["remote"])?.lines().any(|r| r == REMOTE))
+    }
+
+    fn has_staged_changes(&self) -> Result<bool> {
+        Ok(!self.run(&["diff", "--cached", "--quiet"])?.status.success())
+    }
+
+    fn profile_label(&self, file_path: &Path) -> String {
+        file_path
+            .strip_prefix(&self.root)
+            .unwrap_or(file_path)
+            .with_extension("")
+            .display()
+            .to_string()
+    }
+
+    /// commits every pending change in the store
+    pub fn commit(&self, message: &str) -> Result<()> {
+        self.git(&["add", "--all", "."])?;
+
+        if self.has_staged_changes()? {
+            self.git(&["commit", "--quiet", "--message", message])?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_conflicts(&self) -> Result<Vec<String>> {
+        Ok(self
+            .git(&["diff", "--name-only", "--diff-filter=U"])?
+            .lines()
+            .map(|line| line.to_string())
+            .collect())
+    }
+
+    fn merge(&self, upstream: &str, resolution: Option<ConflictResolution>) -> Result<()> {
+        let output = self.run(&[
+            "merge",
+            "--no-edit",
+            "--allow-unrelated-histories",
+            upstream,
+        ])?;
+
+        if output.status.success() {
+            return Ok(());
+        }
+
+        let conflicts = self.get_conflicts()?;
+
+        if conflicts.is_empty() {
+            return Err(Error::Store(format!(
+                "Failed to merge {}: {}",
+                upstream,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        // per-entry profiles can still be merged if both sides changed different entries
+        let mut unresolved = Vec::new();
+        for conflict in conflicts {
+            if !self.merge_entries(&conflict)? {
+                unresolved.push(conflict);
+            }
+        }
+
+        if unresolved.is_empty() {
+            return self.conclude_merge(&format!("Merge {}", upstream));
+        }
+
+        let (side, label) = match resolution {
+            Some(ConflictResolution::Local) => ("--ours", "local"),
+            Some(ConflictResolution::Remote) => ("--theirs", "remote"),
+            None => {
+                self.git(&["merge", "--abort"])?;
+                return Err(Error::SyncConflict(
+                    unresolved
+                        .iter()
+                        .map(|c| self.profile_label(Path::new(c)))
+                        .collect(),
+                ));
+            }
+        };
+
+        for conflict in &unresolved {
+            self.git(&["checkout", side, "--", conflict])?;
+        }
+
+        self.conclude_merge(&format!(
+            "Merge {} keeping the {} profiles",
+            upstream, label
+        ))
+    }
+
+    fn read_stage(&self, stage: u8, path: &str) -> Option<SerializedProfile> {
+        let output = self.run(&["show", &format!(":{}:{}", stage, path)]).ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        serde_json::from_slice(&output.stdout).ok()
+    }
+
+    /// tries to merge a conflicting profile entry by entry, returns whether it was resolved
+    fn merge_entries(&self, path: &str) -> Result<bool> {
+        let (Some(base), Some(ours), Some(theirs)) = (
+            self.read_stage(1, path),
+            self.read_stage(2, path),
+            self.read_stage(3, path),
+        ) else {
+            return Ok(false);
+        };
+
+        let Some(merged) = SerializedProfile::merge(&base, &ours, &theirs) else {
+            return Ok(false);
+        };
+
+        save_serialized_profile(self.root.join(path), merged)?;
+        self.git(&["add", "--", path])?;
+
+        Ok(true)
+    }
+
+    /// the merge commit is needed even when the result matches the local tree
+    fn conclude_merge(&self, message: &str) -> Result<()> {
+        self.git(&["add", "--all", "."])?;
+        self.git(&["commit", "--quiet", "--message", message])?;
+
+        Ok(())
+    }
+}
+
+impl ProfileStore for GitStore {
+    fn kind(&self) -> StoreKind {
+        StoreKind::GIT
+    }
+
+    fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn save_profile(&self, file_path: &Path, serialized_profile: SerializedProfile) -> Result<()> {
+        save_serialized_profile(file_path, serialized_profile)?;
+        self.commit(&format!(
+            "Update profile `{}`",
+            self.profile_label(file_path)
+        ))
+    }
+
+    fn delete_profile(&self, file_path: &Path) -> Result<()> {
+        std::fs::remove_file(file_path)?;
+        self.commit(&format!(
+            "Delete profile `{}`",
+            self.profile_label(file_path)
+        ))
+    }
+
+    fn rename_profile(&self, from: &Path, to: &Path) -> Result<()> {
+        std::fs::rename(from, to)?;
+        self.commit(&format!(
+            "Rename profile `{}` to `{}`",
+            self.profile_label(from),
+            self.profile_label(to)
+        ))
+    }
+
+    fn sync(&self, resolution: Option<ConflictResolution>) -> Result<()> {
+        if !self.has_remote()? {
+            return Err(Error::Store(format!(
+                "No remote configured, run `git -C {} remote add {} <URL>`",
+                self.root.display(),
+                REMOTE
+            )));
+        }
+
+        self.commit("Record pending changes")?;
+
+        let branch = self.git(&["rev-parse", "--abbrev-ref", "HEAD"])?;
+        let upstream = format!("{}/{}", REMOTE, branch);
+
+        self.git(&["fetch", "--quiet", REMOTE])?;
+
+        if self
+            .run(&["rev-parse", "--verify", "--quiet", &upstream])?
+            .status
+            .success()
+        {
+            self.merge(&upstream, resolution)?;
+        }
+
+        self.git(&["push", "--quiet", "--set-upstream", REMOTE, &branch])?;
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// an empty directory that is removed again when the test is done
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let path =
+                std::env::temp_dir().join(format!("envio-test-{}-{}", std::process::id(), name));
+            let _ = std::fs::remove_dir_all(&path);
+            std::fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+
+        fn join(&self, path: &str) -> PathBuf {
+            self.0.join(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn syncs_through_a_bare_remote() {
+        let dir = TempDir::new("git-sync");
+        let remote = dir.join("remote.git");
+
+        let status = Command::new("git")
+            .args(["init", "--quiet", "--bare"])
+            .arg(&remote)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let remote = remote.to_str().unwrap();
+        let first = GitStore::init(dir.join("first"), Some(remote)).unwrap();
+        let first_file = first.root().join("first.env");
+        std::fs::write(&first_file, "{}").unwrap();
+        first.commit("Add first").unwrap();
+        first.sync(None).unwrap();
+
+        let second = GitStore::init(dir.join("second"), Some(remote)).unwrap();
+        assert!(second.root().join("first.env").exists());
+        assert!(second.root().join(".gitattributes").exists());
+
+        std::fs::write(second.root().join("second.env"), "{}").unwrap();
+        second
+            .delete_profile(&second.root().join("first.env"))
+            .unwrap();
+        second.sync(None).unwrap();
+
+        first.sync(None).unwrap();
+        assert!(first.root().join("second.env").exists());
+        assert!(!first_file.exists());
+    }
+
+    #[test]
+    fn reports_conflicting_profiles() {
+        let dir = TempDir::new("git-conflict");
+        let remote = dir.join("remote.git");
+
+        let status = Command::new("git")
+            .args(["init", "--quiet", "--bare"])
+            .arg(&remote)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let remote = remote.to_str().unwrap();
+        let first = GitStore::init(dir.join("first"), Some(remote)).unwrap();
+        std::fs::write(first.root().join("shared.env"), "{}").unwrap();
+        first.sync(None).unwrap();
+
+        let second = GitStore::init(dir.join("second"), Some(remote)).unwrap();
+        std::fs::write(second.root().join("shared.env"), "{\"second\":1}").unwrap();
+        second.sync(None).unwrap();
+
+        std::fs::write(first.root().join("shared.env"), "{\"first\":1}").unwrap();
+        match first.sync(None) {
+            Err(Error::SyncConflict(profiles)) => assert_eq!(profiles, vec!["shared"]),
+            _ => panic!("expected a conflict"),
+        }
+
+        first.sync(Some(ConflictResolution::Remote)).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(first.root().join("shared.env")).unwrap(),
+            "{\"second\":1}"
+        );
+    }
+
+    #[test]
+    fn points_an_initialized_store_at_a_new_remote() {
+        let dir = TempDir::new("git-remote");
+        let store = GitStore::init(dir.join("store"), None).unwrap();
+
+        GitStore::init(dir.join("store"), Some("https://example.com/old.git")).unwrap();
+        GitStore::init(dir.join("store"), Some("https://example.com/new.git")).unwrap();
+
+        assert_eq!(
+            store.git(&["remote", "get-url", REMOTE]).unwrap(),
+            "https://example.com/new.git"
+        );
+    }
+}
@@ -0,0 +1,121 @@
+pub mod dir;
+pub mod git;
+
+// re-export the store types
+pub use dir::DirStore;
+pub use git::GitStore;
+
+use dyn_clone::DynClone;
+use serde::{Deserialize, Serialize};
+use std::{
+    any::Any,
+    path::{Path, PathBuf},
+};
+use strum_macros::{AsRefStr, EnumIter, EnumString};
+
+use crate::{
+    error::Result,
+    profile::SerializedProfile,
+    utils::{get_serialized_profile, save_serialized_profile},
+};
+
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, EnumIter, EnumString, AsRefStr)]
+#[serde(rename_all = "lowercase")]
+pub enum StoreKind {
+    #[strum(ascii_case_insensitive, to_string = "dir")]
+    DIR,
+    #[strum(ascii_case_insensitive, to_string = "git")]
+    GIT,
+}
+
+impl std::fmt::Display for StoreKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+/// which side wins when a profile was changed both locally and remotely
+#[derive(Copy, Clone, PartialEq)]
+pub enum ConflictResolution {
+    Local,
+    Remote,
+}
+
+/// a directory that profiles are read from and written to
+pub trait ProfileStore: Any + Send + DynClone {
+    fn kind(&self) -> StoreKind;
+    fn root(&self) -> &Path;
+
+    fn read_profile(&self, file_path: &Path) -> Result<SerializedProfile> {
+        get_serialized_profile(file_path)
+    }
+
+    fn save_profile(&self, file_path: &Path, serialized_profile: SerializedProfile) -> Result<()> {
+        save_serialized_profile(file_path, serialized_profile)
+    }
+
+    fn delete_profile(&self, file_path: &Path) -> Result<()> {
+        Ok(std::fs::remove_file(file_path)?)
+    }
+
+    fn rename_profile(&self, from: &Path, to: &Path) -> Result<()> {
+        Ok(std::fs::rename(from, to)?)
+    }
+
+    /// exchanges changes with the remote the store is backed by, if any
+    #[allow(unused)]
+    fn sync(&self, resolution: Option<ConflictResolution>) -> Result<()> {
+        Err(crate::error::Error::Store(format!(
+            "`{}` stores can not be synced",
+            self.kind()
+        )))
+    }
+
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl Clone for Box<dyn ProfileStore> {
+    fn clone(&self) -> Box<dyn ProfileStore> {
+        dyn_clone::clone_box(self.as_ref())
+    }
+}
+
+pub fn detect_store_kind<P: AsRef<Path>>(root: P) -> StoreKind {
+    if root.as_ref().join(".git").exists() {
+        StoreKind::GIT
+    } else {
+        StoreKind::DIR
+    }
+}
+
+pub fn create_store<P: AsRef<Path>>(kind: StoreKind, root: P) -> Box<dyn ProfileStore> {
+    let root = PathBuf::from(root.as_ref());
+
+    match kind {
+        StoreKind::DIR => Box::new(DirStore::new(root)),
+        StoreKind::GIT => Box::new(GitStore::new(root)),
+    }
+}
+
+/// opens the store rooted at `root`, picking the backend based on its contents
+pub fn open_store<P: AsRef<Path>>(root: P) -> Box<dyn ProfileStore> {
+    create_store(detect_store_kind(&root), root)
+}
+
+/// opens the store a profile file lives in. profiles named `team/service/prod` live in
+/// subdirectories of their store, which is only climbed out of if the path matches the name
+pub fn get_profile_store<P: AsRef<Path>>(
+    profile_filepath: P,
+    profile_name: &str,
+) -> Box<dyn ProfileStore> {
+    let file_path = profile_filepath.as_ref();
+    let mut root = file_path.parent().unwrap_or_else(|| Path::new("."));
+
+    if file_path.with_extension("").ends_with(profile_name) {
+        for _ in profile_name.matches('/') {
+            root = root.parent().unwrap_or(root);
+        }
+    }
+
+    open_store(root)
+}
@@ -12,6 +12,10 @@ pub fn save_serialized_profile<P: AsRef<Path>>(
     file_path: P,
     serialized_profile: SerializedProfile,
 ) -> Result<()> {
+    if let Some(dir) = file_path.as_ref().parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
     let file = std::fs::OpenOptions::new()
         .write(true)
         .append(false)